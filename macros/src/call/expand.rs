@@ -10,7 +10,7 @@ pub fn expand_call(def: CallDef) -> proc_macro2::TokenStream {
 
 	// This is a nested vector of all the arguments for each of the functions in `fn_name`. It does
 	// not include the `self` or `caller: T::AccountId` parameter, which we always assume are the
-	// first two parameters to these calls.
+	// first two parameters to these calls, nor `origin` when it takes the place of `caller`.
 	let args_name = methods
 		.iter()
 		.map(|method| method.args.iter().map(|(name, _)| name.clone()).collect::<Vec<_>>())
@@ -23,12 +23,42 @@ pub fn expand_call(def: CallDef) -> proc_macro2::TokenStream {
 		.map(|method| method.args.iter().map(|(_, type_)| type_.clone()).collect::<Vec<_>>())
 		.collect::<Vec<_>>();
 
+	// This is a vector of the expressions used to call each of the functions in `fn_name`, with
+	// the `caller` and all the arguments. Calls taking `self` are made on the pallet, which we get
+	// from the runtime using `GetPallet`. Calls taking `runtime: &mut T` are given the runtime.
+	// Calls taking `caller` can only be made by a signed origin, whose account is the `caller`.
+	// Calls taking `origin` are given the origin as is.
+	let fn_call = methods
+		.iter()
+		.zip(&args_name)
+		.map(|(method, args_name)| {
+			let name = &method.name;
+			let caller = if method.with_origin {
+				quote! { origin }
+			} else {
+				quote! { origin.ensure_signed()? }
+			};
+			if method.with_runtime {
+				quote! { #pallet_struct::<T>::#name(runtime, #caller, #( #args_name ),*) }
+			} else {
+				quote! {
+					crate::support::GetPallet::<#pallet_struct<T>>::pallet_mut(runtime)
+						.#name(#caller, #( #args_name ),*)
+				}
+			}
+		})
+		.collect::<Vec<_>>();
+
 	// This quote block creates an `enum Call` which contains all the calls exposed by our pallet,
-	// and the `Dispatch` trait logic to route a `caller` to access those functions.
+	// and the dispatch logic to route an `origin` to access those functions.
 	let dispatch_impl = quote! {
 		// The callable functions exposed by this pallet.
 		//
 		// The parsed function names will be `snake_case`, and that will show up in the enum.
+		//
+		// Calls implement `Debug`, which is also how they are encoded to be hashed, and `Clone`, so
+		// they can be stored and dispatched more than once.
+		#[derive(Debug, Clone)]
 		#[allow(non_camel_case_types)]
 		pub enum Call<T: Config> {
 			#(
@@ -37,20 +67,25 @@ pub fn expand_call(def: CallDef) -> proc_macro2::TokenStream {
 		}
 
 		// Dispatch logic at the pallet level, mapping each of the items in the `Call` enum to the
-		// appropriate function call with all arguments, including the `caller`.
-		impl<T: Config> crate::support::Dispatch for #pallet_struct<T> {
-			type Caller = T::AccountId;
-			type Call = Call<T>;
-
-			fn dispatch(&mut self, caller: Self::Caller, call: Self::Call) -> crate::support::DispatchResult {
-				match call {
+		// appropriate function call with all arguments, including the `caller` or `origin`.
+		//
+		// The runtime which includes this pallet is passed in, so that calls can access this pallet
+		// and, when they need to, other pallets too.
+		impl<T: Config> Call<T> {
+			pub fn dispatch(
+				self,
+				runtime: &mut T,
+				origin: crate::support::Origin<T::AccountId>,
+			) -> crate::support::DispatchResult
+			where
+				T: crate::support::GetPallet<#pallet_struct<T>>,
+			{
+				match self {
 					#(
 						Call::#fn_name { #( #args_name ),* } => {
-							self.#fn_name(
-								// Note that we assume the first argument of every call is the `caller`.
-								caller,
-								#( #args_name ),*
-							)?;
+							// Note that we assume the first argument of every call after `self` or
+							// `runtime` is the `caller` or the `origin`.
+							#fn_call?;
 						},
 					)*
 				}
@@ -5,6 +5,7 @@ use syn::spanned::Spanned;
 mod keyword {
 	syn::custom_keyword!(T);
 	syn::custom_keyword!(AccountId);
+	syn::custom_keyword!(Origin);
 }
 
 /// This object will collect all the information we need to keep while parsing the callable
@@ -23,6 +24,11 @@ pub struct CallDef {
 pub struct CallVariantDef {
 	/// The function name.
 	pub name: syn::Ident,
+	/// Whether the function takes `runtime: &mut T` instead of `self` as its first argument.
+	pub with_runtime: bool,
+	/// Whether the function takes `origin: Origin<T::AccountId>` instead of
+	/// `caller: T::AccountId` as its second argument.
+	pub with_origin: bool,
 	/// Information on args of the function: `(name, type)`.
 	pub args: Vec<(syn::Ident, Box<syn::Type>)>,
 }
@@ -50,27 +56,41 @@ impl CallDef {
 				// Here is where we will store all the args for each callable functions.
 				let mut args = vec![];
 
-				// First argument should be some variant of `self`.
-				match method.sig.inputs.first() {
-					Some(syn::FnArg::Receiver(_)) => {},
+				// First argument should be some variant of `self`, or `runtime: &mut T` for calls
+				// which need access to other pallets through the runtime.
+				let with_runtime = match method.sig.inputs.first() {
+					Some(syn::FnArg::Receiver(_)) => false,
+					Some(syn::FnArg::Typed(arg)) => {
+						check_runtime_arg(arg)?;
+						true
+					},
 					_ => {
-						let msg = "Invalid call, first argument must be a variant of self";
+						let msg = "Invalid call, first argument must be a variant of self or \
+							`runtime: &mut T`";
 						return Err(syn::Error::new(method.sig.span(), msg))
 					},
-				}
-
-				// The second argument should be the `caller: T::AccountId` argument.
-				match method.sig.inputs.iter().skip(1).next() {
+				};
+
+				// The second argument should be the `caller: T::AccountId` argument, or
+				// `origin: Origin<T::AccountId>` for calls which can be made by other origins than
+				// a signed account, like root.
+				let with_origin = match method.sig.inputs.iter().skip(1).next() {
+					Some(syn::FnArg::Typed(arg)) if is_origin_arg(arg) => {
+						check_origin_arg(arg)?;
+						true
+					},
 					Some(syn::FnArg::Typed(arg)) => {
 						// Here we specifically check that this argument is as we expect for
 						// `caller: T::AccountId`.
 						check_caller_arg(arg)?;
+						false
 					},
 					_ => {
-						let msg = "Invalid call, second argument should be `caller: T::AccountId`";
+						let msg = "Invalid call, second argument should be `caller: T::AccountId` \
+							or `origin: Origin<T::AccountId>`";
 						return Err(syn::Error::new(method.sig.span(), msg))
 					},
-				}
+				};
 
 				let fn_name = method.sig.ident.clone();
 
@@ -96,7 +116,7 @@ impl CallDef {
 				}
 
 				// Store all the function name and the arg data for the function.
-				methods.push(CallVariantDef { name: fn_name, args });
+				methods.push(CallVariantDef { name: fn_name, with_runtime, with_origin, args });
 			}
 		}
 
@@ -105,6 +125,41 @@ impl CallDef {
 	}
 }
 
+/// Check runtime arg is exactly: `runtime: &mut T`.
+///
+/// This is kept strict to keep the code simple.
+pub fn check_runtime_arg(arg: &syn::PatType) -> syn::Result<()> {
+	pub struct CheckRuntimeArg;
+	impl syn::parse::Parse for CheckRuntimeArg {
+		fn parse(input: syn::parse::ParseStream) -> syn::Result<Self> {
+			input.parse::<syn::Token![&]>()?;
+			input.parse::<syn::Token![mut]>()?;
+			input.parse::<keyword::T>()?;
+			Ok(Self)
+		}
+	}
+
+	// This checks the arg name is `runtime`.
+	match &*arg.pat {
+		syn::Pat::Ident(ident) if ident.ident == "runtime" => {},
+		_ => {
+			let msg = "Invalid name for first parameter: expected `runtime: &mut T` or self";
+			return Err(syn::Error::new(arg.pat.span(), msg))
+		},
+	}
+
+	// This checks the type is `&mut T` with `CheckRuntimeArg`
+	let ty = &arg.ty;
+	syn::parse2::<CheckRuntimeArg>(ty.to_token_stream()).map_err(|e| {
+		let msg = "Invalid type for first parameter: expected `runtime: &mut T`";
+		let mut err = syn::Error::new(ty.span(), msg);
+		err.combine(e);
+		err
+	})?;
+
+	Ok(())
+}
+
 /// Check caller arg is exactly: `caller: T::AccountId`.
 ///
 /// This is kept strict to keep the code simple.
@@ -139,3 +194,37 @@ pub fn check_caller_arg(arg: &syn::PatType) -> syn::Result<()> {
 
 	Ok(())
 }
+
+/// Whether the arg is named `origin` or `_origin`.
+fn is_origin_arg(arg: &syn::PatType) -> bool {
+	matches!(&*arg.pat, syn::Pat::Ident(ident) if ident.ident == "origin" || ident.ident == "_origin")
+}
+
+/// Check origin arg is exactly: `origin: Origin<T::AccountId>`.
+///
+/// This is kept strict to keep the code simple.
+pub fn check_origin_arg(arg: &syn::PatType) -> syn::Result<()> {
+	pub struct CheckOriginArg;
+	impl syn::parse::Parse for CheckOriginArg {
+		fn parse(input: syn::parse::ParseStream) -> syn::Result<Self> {
+			input.parse::<keyword::Origin>()?;
+			input.parse::<syn::Token![<]>()?;
+			input.parse::<keyword::T>()?;
+			input.parse::<syn::Token![::]>()?;
+			input.parse::<keyword::AccountId>()?;
+			input.parse::<syn::Token![>]>()?;
+			Ok(Self)
+		}
+	}
+
+	// This checks the type is `Origin<T::AccountId>` with `CheckOriginArg`
+	let ty = &arg.ty;
+	syn::parse2::<CheckOriginArg>(ty.to_token_stream()).map_err(|e| {
+		let msg = "Invalid type for second parameter: expected `origin: Origin<T::AccountId>`";
+		let mut err = syn::Error::new(ty.span(), msg);
+		err.combine(e);
+		err
+	})?;
+
+	Ok(())
+}
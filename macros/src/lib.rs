@@ -1,6 +1,24 @@
 mod call;
 mod runtime;
 
+/// Expand the callable functions of a pallet.
+///
+/// This generates an `enum Call` with one variant per function in the annotated `impl` block, and a
+/// `Call::dispatch` function which routes a call and its `support::Origin` to the matching function.
+///
+/// The first argument of every callable function must be either a variant of `self`, or
+/// `runtime: &mut T` for calls which need to access other pallets through the runtime. The second
+/// argument must be either `caller: T::AccountId`, for calls which can only be made by a signed
+/// origin, or `origin: Origin<T::AccountId>`, for calls which check the origin themselves, like
+/// calls which can only be made by root.
+///
+/// The `Call` enum implements `Debug`, which is how calls are encoded, for example to be hashed, and
+/// `Clone`.
+///
+/// A function which takes other calls of the runtime as an argument, like `sudo`, must take them as
+/// `Box<T::RuntimeCall>`: the `Call` enum of the pallet contains the argument, and is contained by
+/// `RuntimeCall`, which would otherwise have an infinite size. Clippy warns about boxed arguments,
+/// so such `impl` blocks are annotated with `#[allow(clippy::boxed_local)]`.
 #[proc_macro_attribute]
 pub fn call(
 	attr: proc_macro::TokenStream,
@@ -16,15 +34,44 @@ pub fn call(
 ///   included in the runtime.
 /// - `fn execute_block()` - which handles basic logic for executing a block of extrinsics. It does
 ///   basic actions like incrementing the block number and checking the block to be executed has a
-///   valid block number.
+///   valid block number. Before anything is changed, it checks that the inherents come first, are
+///   accepted by the `support::ProvideInherent` of their pallet, and include every required
+///   inherent. Each signed extrinsic is dispatched with the origin of its caller, whose nonce is
+///   incremented. Inherents, and unsigned extrinsics accepted by the `support::ValidateUnsigned`
+///   of their pallet, are dispatched with no origin, and the block is rejected if an inherent
+///   fails. It calls the `support::Hooks` of every pallet other than system before and after the
+///   extrinsics, so every such pallet must implement `Hooks`. Finally, it commits the storage of
+///   all pallets to a state root, which is only stored once it matches the state root of the
+///   header, when present. An invalid block is rolled back entirely, including the block number
+///   and the nonces of the system pallet, which costs a copy of every pallet per block through
+///   `support::Transactional`.
+/// - `fn storage()` - which collects the storage of every pallet, including system, through
+///   `support::Storage`, with keys prefixed by the pallet name.
+///
+/// Only the pallets marked with `#[inherent]` in the `Runtime` struct provide inherents, and they
+/// must implement `support::ProvideInherent`. Likewise, only the pallets marked with
+/// `#[validate_unsigned]` accept unsigned extrinsics, and they must implement
+/// `support::ValidateUnsigned`. These attributes are removed from the struct.
 ///
 /// This also generates code needed for dispatching calls to the pallets:
 /// - Note: For simplicity, we assume that the system pallet is not callable.
 /// - `enum RuntimeCall` - an "outer"-enum representing the accumulation of all possible calls to
-///   all pallets. The system pallet is not included.
-/// - implements the trait `support::Dispatch` to dispatch calls to the appropriate pallet. Basic
-///   logic like incrementing the nonce of the user is included in the generated code. The system
+///   all pallets, which implements `Debug` and `Clone` like the calls of each pallet. The system
 ///   pallet is not included.
+/// - implements the trait `support::Dispatch` to dispatch calls, with a `support::Origin`, to the
+///   appropriate pallet. The system pallet is not included.
+/// - implements `From` for the `Call` of every pallet into `RuntimeCall`.
+///
+/// It implements the trait `support::Transactional`, which reverts every pallet other than system
+/// when a function fails, so every such pallet must implement `Clone`.
+///
+/// The `Runtime` usually derives `Debug` and `Clone`, so every pallet must implement them as well.
+/// When a pallet derives them, the types of its fields are written out in full, like
+/// `BTreeMap<T::AccountId, T::Balance>`, instead of through aliases like `BalanceOf<T>`: `derive`
+/// only requires `Debug` and `Clone` of the associated types of `T` which it sees in the fields.
+///
+/// Finally, it implements `support::GetPallet` on `Runtime` for every pallet, including system, so
+/// that pallets can access each other through the runtime.
 #[proc_macro_attribute]
 pub fn runtime(
 	attr: proc_macro::TokenStream,
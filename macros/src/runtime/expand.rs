@@ -3,12 +3,18 @@ use quote::quote;
 
 /// See the `fn runtime` docs at the `lib.rs` of this crate for a high level definition.
 pub fn expand_runtime(def: RuntimeDef) -> proc_macro2::TokenStream {
-	let RuntimeDef { runtime_struct, pallets } = def;
+	let RuntimeDef { runtime_struct, pallets, inherent_pallets, unsigned_pallets } = def;
 
 	// This is a vector of all the pallet names, not including system.
 	let pallet_names = pallets.iter().map(|(name, _)| name.clone()).collect::<Vec<_>>();
 	// This is a vector of all the pallet types, not including system.
 	let pallet_types = pallets.iter().map(|(_, type_)| type_.clone()).collect::<Vec<_>>();
+	// The names and types of the pallets marked with `#[inherent]`.
+	let inherent_names = inherent_pallets.iter().map(|(name, _)| name.clone()).collect::<Vec<_>>();
+	let inherent_types = inherent_pallets.iter().map(|(_, type_)| type_.clone()).collect::<Vec<_>>();
+	// The names and types of the pallets marked with `#[validate_unsigned]`.
+	let unsigned_names = unsigned_pallets.iter().map(|(name, _)| name.clone()).collect::<Vec<_>>();
+	let unsigned_types = unsigned_pallets.iter().map(|(_, type_)| type_.clone()).collect::<Vec<_>>();
 
 	// This quote block implements functions on the `Runtime` struct.
 	let runtime_impl = quote! {
@@ -25,22 +31,141 @@ pub fn expand_runtime(def: RuntimeDef) -> proc_macro2::TokenStream {
 			}
 
 			// Execute a block of extrinsics. Increments the block number.
+			//
+			// The inherents are checked before anything is changed. An invalid block is rolled back
+			// entirely: every pallet, the block number and the nonces are left as they were before
+			// the block.
 			fn execute_block(&mut self, block: types::Block) -> crate::support::DispatchResult {
+				Self::check_inherents(&block.extrinsics)?;
+				let checkpoint = self.system.checkpoint();
+				let res = crate::support::Transactional::transactional(self, |runtime| {
+					runtime.apply_block(block)
+				});
+				if res.is_err() {
+					self.system.restore(checkpoint);
+				}
+				res
+			}
+
+			// Apply a block of extrinsics on top of the current state. If the block is invalid, the
+			// state is left halfway through the block, so this must only be called through
+			// `execute_block`.
+			fn apply_block(&mut self, block: types::Block) -> crate::support::DispatchResult {
 				self.system.inc_block_number();
 				if block.header.block_number != self.system.block_number() {
 					return Err(&"block number does not match what is expected")
 				}
-				for (i, support::Extrinsic { caller, call }) in block.extrinsics.into_iter().enumerate() {
-					self.system.inc_nonce(&caller);
-					let _res = self.dispatch(caller, call).map_err(|e| {
+				let block_number = block.header.block_number;
+				#(
+					<#pallet_types as crate::support::Hooks<Self>>::on_initialize(self, block_number);
+				)*
+				for (i, extrinsic) in block.extrinsics.into_iter().enumerate() {
+					let res = match extrinsic {
+						crate::support::Extrinsic::Signed { caller, call } => {
+							self.system.inc_nonce(&caller);
+							self.dispatch(crate::support::Origin::Signed(caller), call)
+						}
+						// Nobody pays for an unsigned transaction, so the block author must only
+						// include the ones accepted by their pallet.
+						crate::support::Extrinsic::Unsigned { call } => {
+							self.validate_unsigned(&call)?;
+							self.dispatch(crate::support::Origin::None, call)
+						}
+						// Inherents are provided by the block author, so a failing inherent makes
+						// the whole block invalid.
+						crate::support::Extrinsic::Inherent { call } => {
+							self.dispatch(crate::support::Origin::None, call)?;
+							Ok(())
+						}
+					};
+					let _res = res.map_err(|e| {
 						eprintln!(
 							"Extrinsic Error\n\tBlock Number: {}\n\tExtrinsic Number: {}\n\tError: {}",
 							block.header.block_number, i, e
 						)
 					});
 				}
+				#(
+					<#pallet_types as crate::support::Hooks<Self>>::on_finalize(self, block_number);
+				)*
+				// Commit to the state after the block, once it matches the state root included by
+				// the block author, if any.
+				let state = self.storage();
+				self.system.commit_state(state, block.header.state_root)?;
+				Ok(())
+			}
+
+			// Check the inherents of a block: they must all come before the other extrinsics, each
+			// must be accepted as an inherent by its pallet, and every pallet which requires an
+			// inherent must have one. Only the pallets marked with `#[inherent]` have inherents.
+			fn check_inherents(extrinsics: &[types::Extrinsic]) -> crate::support::DispatchResult {
+				let count = extrinsics
+					.iter()
+					.take_while(|extrinsic| matches!(extrinsic, crate::support::Extrinsic::Inherent { .. }))
+					.count();
+				let (inherents, others) = extrinsics.split_at(count);
+				if others.iter().any(|extrinsic| matches!(extrinsic, crate::support::Extrinsic::Inherent { .. })) {
+					return Err(&"inherents must come before other extrinsics")
+				}
+				for extrinsic in inherents {
+					let crate::support::Extrinsic::Inherent { call } = extrinsic else { continue };
+					#[allow(unreachable_patterns)]
+					let is_inherent = match call {
+						#(
+							RuntimeCall::#inherent_names(call) => {
+								<#inherent_types as crate::support::ProvideInherent<#inherent_names::Call<Self>>>::is_inherent(call)
+							}
+						)*
+						_ => false,
+					};
+					if !is_inherent {
+						return Err(&"call is not an inherent")
+					}
+				}
+				#(
+					let required = <#inherent_types as crate::support::ProvideInherent<#inherent_names::Call<Self>>>::INHERENT_REQUIRED;
+					let provided = inherents.iter().any(|extrinsic| {
+						matches!(
+							extrinsic,
+							crate::support::Extrinsic::Inherent { call: RuntimeCall::#inherent_names(_) }
+						)
+					});
+					if required && !provided {
+						return Err(&"a required inherent is missing")
+					}
+				)*
 				Ok(())
 			}
+
+			// Check that the pallet of `runtime_call` accepts it as an unsigned transaction. Only
+			// the pallets marked with `#[validate_unsigned]` accept unsigned transactions.
+			fn validate_unsigned(&self, runtime_call: &RuntimeCall) -> crate::support::DispatchResult {
+				#[allow(unreachable_patterns)]
+				match runtime_call {
+					#(
+						RuntimeCall::#unsigned_names(call) => {
+							<#unsigned_types as crate::support::ValidateUnsigned<Self, #unsigned_names::Call<Self>>>::validate_unsigned(self, call)
+						}
+					)*
+					_ => Err(&"Unsigned transactions are not accepted for this call."),
+				}
+			}
+
+			// Collect the storage of every pallet, including system, as key-value pairs. Each key
+			// is prefixed with the name of the pallet in the runtime.
+			fn storage(&self) -> Vec<(Vec<u8>, Vec<u8>)> {
+				let mut storage = Vec::new();
+				for (key, value) in crate::support::Storage::storage(&self.system) {
+					storage.push(([b"system::".as_slice(), &key].concat(), value));
+				}
+				#(
+					let prefix = concat!(stringify!(#pallet_names), "::").as_bytes();
+					for (key, value) in crate::support::Storage::storage(&self.#pallet_names) {
+						storage.push(([prefix, &key].concat(), value));
+					}
+				)*
+				storage
+			}
 		}
 	};
 
@@ -50,22 +175,23 @@ pub fn expand_runtime(def: RuntimeDef) -> proc_macro2::TokenStream {
 		// Note that it is just an accumulation of the calls exposed by each pallet.
 		//
 		// The parsed function names will be `snake_case`, and that will show up in the enum.
+		#[derive(Debug, Clone)]
 		#[allow(non_camel_case_types)]
 		pub enum RuntimeCall {
 			#( #pallet_names(#pallet_names::Call<#runtime_struct>) ),*
 		}
 
 		impl crate::support::Dispatch for #runtime_struct {
-			type Caller = <Runtime as system::Config>::AccountId;
+			type Caller = crate::support::Origin<<#runtime_struct as system::Config>::AccountId>;
 			type Call = RuntimeCall;
-			// Dispatch a call on behalf of a caller. Increments the caller's nonce.
+			// Dispatch a call on behalf of an origin.
 			//
 			// Dispatch allows us to identify which underlying pallet call we want to execute.
-			// Note that we extract the `caller` from the extrinsic, and use that information
-			// to determine who we are executing the call on behalf of.
+			// Note that extrinsics are dispatched with the signed origin of their `caller`, and
+			// other origins, like root, only come from pallets which dispatch calls themselves.
 			fn dispatch(
 				&mut self,
-				caller: Self::Caller,
+				origin: Self::Caller,
 				runtime_call: Self::Call,
 			) -> crate::support::DispatchResult {
 				// This match statement will allow us to correctly route `RuntimeCall`s
@@ -73,19 +199,77 @@ pub fn expand_runtime(def: RuntimeDef) -> proc_macro2::TokenStream {
 				match runtime_call {
 					#(
 						RuntimeCall::#pallet_names(call) => {
-							self.#pallet_names.dispatch(caller, call)?;
+							call.dispatch(self, origin)?;
 						}
 					),*
 				}
 				Ok(())
 			}
 		}
+
+		// Convert the calls of each pallet into a `RuntimeCall`, so pallets which take calls as
+		// arguments, like a batch of calls, can be built from the pallet level calls.
+		#(
+			impl From<#pallet_names::Call<#runtime_struct>> for RuntimeCall {
+				fn from(call: #pallet_names::Call<#runtime_struct>) -> Self {
+					RuntimeCall::#pallet_names(call)
+				}
+			}
+		)*
+	};
+
+	// This quote block implements `Transactional` on the `Runtime`, by keeping a copy of every
+	// pallet which is restored if the function fails. The system pallet is not callable, so it is
+	// not included.
+	let transactional_impl = quote! {
+		impl crate::support::Transactional for #runtime_struct {
+			fn transactional(
+				&mut self,
+				f: impl FnOnce(&mut Self) -> crate::support::DispatchResult,
+			) -> crate::support::DispatchResult {
+				let backup = ( #( self.#pallet_names.clone(), )* );
+				let res = f(self);
+				if res.is_err() {
+					( #( self.#pallet_names, )* ) = backup;
+				}
+				res
+			}
+		}
+	};
+
+	// This quote block implements `GetPallet` for every pallet in the `Runtime`, including system.
+	// This is how a pallet gets access to other pallets, for example a currency provider named in
+	// its `Config`, through the runtime it is given.
+	let get_pallet_impl = quote! {
+		impl crate::support::GetPallet<system::Pallet<#runtime_struct>> for #runtime_struct {
+			fn pallet(&self) -> &system::Pallet<#runtime_struct> {
+				&self.system
+			}
+
+			fn pallet_mut(&mut self) -> &mut system::Pallet<#runtime_struct> {
+				&mut self.system
+			}
+		}
+
+		#(
+			impl crate::support::GetPallet<#pallet_types> for #runtime_struct {
+				fn pallet(&self) -> &#pallet_types {
+					&self.#pallet_names
+				}
+
+				fn pallet_mut(&mut self) -> &mut #pallet_types {
+					&mut self.#pallet_names
+				}
+			}
+		)*
 	};
 
 	// We combine and return all the generated code.
 	quote! {
 		#dispatch_impl
 		#runtime_impl
+		#transactional_impl
+		#get_pallet_impl
 	}
 	.into()
 }
@@ -8,8 +8,18 @@ pub struct RuntimeDef {
 	/// This is the list of pallets included in the `Runtime` struct. We omit `system` from this
 	/// list, but during parsing we check that system exists.
 	pub pallets: Vec<(syn::Ident, syn::Type)>,
+	/// The pallets marked with `#[inherent]`, which implement `support::ProvideInherent`.
+	pub inherent_pallets: Vec<(syn::Ident, syn::Type)>,
+	/// The pallets marked with `#[validate_unsigned]`, which implement
+	/// `support::ValidateUnsigned`.
+	pub unsigned_pallets: Vec<(syn::Ident, syn::Type)>,
 }
 
+/// The attribute which marks a pallet as providing inherents.
+pub const INHERENT_ATTR: &str = "inherent";
+/// The attribute which marks a pallet as accepting unsigned transactions.
+pub const VALIDATE_UNSIGNED_ATTR: &str = "validate_unsigned";
+
 impl RuntimeDef {
 	pub fn try_from(item: syn::Item) -> syn::Result<Self> {
 		// First we check that we are parsing a `struct`.
@@ -26,14 +36,25 @@ impl RuntimeDef {
 
 		// Here is where we will store a list of all the pallets.
 		let mut pallets = vec![];
+		let mut inherent_pallets = vec![];
+		let mut unsigned_pallets = vec![];
 		// We skip `system`, which we ensure is the first field in `check_system`.
 		for field in item_struct.fields.into_iter().skip(1) {
 			if let Some(ident) = field.ident {
+				for attr in &field.attrs {
+					if attr.path().is_ident(INHERENT_ATTR) {
+						attr.meta.require_path_only()?;
+						inherent_pallets.push((ident.clone(), field.ty.clone()));
+					} else if attr.path().is_ident(VALIDATE_UNSIGNED_ATTR) {
+						attr.meta.require_path_only()?;
+						unsigned_pallets.push((ident.clone(), field.ty.clone()));
+					}
+				}
 				pallets.push((ident, field.ty))
 			}
 		}
 
-		Ok(Self { runtime_struct, pallets })
+		Ok(Self { runtime_struct, pallets, inherent_pallets, unsigned_pallets })
 	}
 }
 
@@ -0,0 +1,639 @@
+use crate::support::{
+    storage_map, BalanceOf, DispatchResult, GetPallet, Hooks, NamedReservableCurrency,
+    ReserveIdentifier, Storage,
+};
+use core::fmt::Debug;
+use num::traits::{CheckedAdd, CheckedSub, Zero};
+use std::collections::{BTreeMap, BTreeSet};
+
+/// A característica de configuração do Módulo Assets.
+pub trait Config: crate::system::Config + Sized + GetPallet<Pallet<Self>> {
+    /// O identificador de um ativo.
+    type AssetId: Debug + Ord + Clone;
+    /// O tipo usado para representar os saldos dos ativos.
+    type Balance: Zero + CheckedSub + CheckedAdd + Copy + Ord + Debug;
+    /// A moeda da qual é reservado o depósito de cada ativo.
+    type Currency: NamedReservableCurrency<Self>;
+    /// O depósito reservado de quem cria um ativo, enquanto ele existir.
+    const ASSET_DEPOSIT: DepositOf<Self>;
+    /// O tamanho máximo, em bytes, do nome e do símbolo de um ativo.
+    const STRING_LIMIT: u32;
+}
+
+/// O identificador da reserva que guarda os depósitos dos ativos.
+pub const ASSETS_RESERVE_ID: ReserveIdentifier = *b"assetdep";
+
+/// O tipo de saldo dos depósitos dos ativos.
+pub type DepositOf<T> = BalanceOf<<T as Config>::Currency, T>;
+
+/// Os dados de um ativo.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct AssetDetails<AccountId, Balance, Deposit> {
+    /// A conta que criou o ativo. Pode definir os metadados e destruir o ativo, e recebe o
+    /// depósito de volta quando o ativo é destruído.
+    pub owner: AccountId,
+    /// A conta que pode emitir, queimar, congelar e descongelar o ativo.
+    pub admin: AccountId,
+    /// A quantia total do ativo que existe.
+    pub supply: Balance,
+    /// O depósito reservado do proprietário pelo ativo.
+    pub deposit: Deposit,
+}
+
+/// Os dados de um ativo com os tipos do runtime `T`.
+pub type AssetDetailsOf<T> =
+    AssetDetails<<T as crate::system::Config>::AccountId, <T as Config>::Balance, DepositOf<T>>;
+
+/// Os metadados de um ativo.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct AssetMetadata {
+    /// O nome do ativo.
+    pub name: Vec<u8>,
+    /// O símbolo do ativo, como o código de uma moeda.
+    pub symbol: Vec<u8>,
+    /// O número de casas decimais usadas para exibir as quantias do ativo.
+    pub decimals: u8,
+}
+
+/// Os eventos emitidos pelo Módulo Assets.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Event<T: Config> {
+    /// O ativo `asset_id` foi criado por `owner`.
+    Created {
+        asset_id: T::AssetId,
+        owner: T::AccountId,
+        admin: T::AccountId,
+    },
+    /// `amount` do ativo foi emitido para `owner`.
+    Issued {
+        asset_id: T::AssetId,
+        owner: T::AccountId,
+        amount: T::Balance,
+    },
+    /// `amount` do ativo foi queimado da conta de `owner`.
+    Burned {
+        asset_id: T::AssetId,
+        owner: T::AccountId,
+        amount: T::Balance,
+    },
+    /// `amount` do ativo foi transferido de `from` para `to`.
+    Transferred {
+        asset_id: T::AssetId,
+        from: T::AccountId,
+        to: T::AccountId,
+        amount: T::Balance,
+    },
+    /// A conta `who` foi congelada para o ativo.
+    Frozen {
+        asset_id: T::AssetId,
+        who: T::AccountId,
+    },
+    /// A conta `who` foi descongelada para o ativo.
+    Thawed {
+        asset_id: T::AssetId,
+        who: T::AccountId,
+    },
+    /// Os metadados do ativo foram definidos.
+    MetadataSet {
+        asset_id: T::AssetId,
+        name: Vec<u8>,
+        symbol: Vec<u8>,
+        decimals: u8,
+    },
+    /// O ativo foi destruído.
+    Destroyed { asset_id: T::AssetId },
+}
+
+/// Este é o Módulo de Ativos.
+/// Permite que as contas criem e administrem os seus próprios tokens fungíveis, lado a lado com o
+/// saldo nativo do Módulo Balances.
+#[derive(Debug, Clone)]
+pub struct Pallet<T: Config> {
+    /// Os dados de cada ativo que existe.
+    #[allow(clippy::type_complexity)]
+    assets: BTreeMap<T::AssetId, AssetDetails<T::AccountId, T::Balance, DepositOf<T>>>,
+    /// O saldo de cada conta em cada ativo. Os saldos ficam agrupados por ativo para que destruir
+    /// um ativo não percorra as contas dos outros.
+    accounts: BTreeMap<T::AssetId, BTreeMap<T::AccountId, T::Balance>>,
+    /// As contas congeladas de cada ativo, que não podem transferir esse ativo.
+    frozen: BTreeMap<T::AssetId, BTreeSet<T::AccountId>>,
+    /// Os metadados de cada ativo.
+    metadata: BTreeMap<T::AssetId, AssetMetadata>,
+    /// Os eventos emitidos por este pallet no bloco atual, em ordem.
+    events: Vec<Event<T>>,
+}
+
+#[macros::call]
+impl<T: Config> Pallet<T> {
+    /// Cria o ativo `asset_id`, tendo `caller` como proprietário e `admin` como administrador, e
+    /// reserva `ASSET_DEPOSIT` de `caller`.
+    pub fn create(
+        runtime: &mut T,
+        caller: T::AccountId,
+        asset_id: T::AssetId,
+        admin: T::AccountId,
+    ) -> DispatchResult {
+        let pallet: &Self = runtime.pallet();
+        if pallet.assets.contains_key(&asset_id) {
+            return Err("Asset already exists.");
+        }
+
+        let deposit = T::ASSET_DEPOSIT;
+        T::Currency::reserve_named(runtime, &ASSETS_RESERVE_ID, &caller, deposit)?;
+        let pallet: &mut Self = runtime.pallet_mut();
+        pallet.assets.insert(
+            asset_id.clone(),
+            AssetDetails {
+                owner: caller.clone(),
+                admin: admin.clone(),
+                supply: T::Balance::zero(),
+                deposit,
+            },
+        );
+        pallet.deposit_event(Event::Created {
+            asset_id,
+            owner: caller,
+            admin,
+        });
+        Ok(())
+    }
+
+    /// Emite `amount` de novas unidades do ativo para `beneficiary`.
+    /// Apenas o administrador do ativo pode emitir.
+    pub fn mint(
+        &mut self,
+        caller: T::AccountId,
+        asset_id: T::AssetId,
+        beneficiary: T::AccountId,
+        amount: T::Balance,
+    ) -> DispatchResult {
+        let details = self.ensure_admin(&caller, &asset_id)?;
+        let new_supply = details.supply.checked_add(&amount).ok_or("Overflow")?;
+        let new_balance = self
+            .balance(&asset_id, &beneficiary)
+            .checked_add(&amount)
+            .ok_or("Overflow")?;
+
+        self.set_account_balance(&asset_id, &beneficiary, new_balance);
+        self.set_supply(&asset_id, new_supply);
+        self.deposit_event(Event::Issued {
+            asset_id,
+            owner: beneficiary,
+            amount,
+        });
+        Ok(())
+    }
+
+    /// Queima `amount` unidades do ativo da conta de `who`.
+    /// Apenas o administrador do ativo pode queimar.
+    pub fn burn(
+        &mut self,
+        caller: T::AccountId,
+        asset_id: T::AssetId,
+        who: T::AccountId,
+        amount: T::Balance,
+    ) -> DispatchResult {
+        let details = self.ensure_admin(&caller, &asset_id)?;
+        let new_balance = self
+            .balance(&asset_id, &who)
+            .checked_sub(&amount)
+            .ok_or("Insufficient balance.")?;
+        let new_supply = details.supply.checked_sub(&amount).ok_or("Underflow")?;
+
+        self.set_account_balance(&asset_id, &who, new_balance);
+        self.set_supply(&asset_id, new_supply);
+        self.deposit_event(Event::Burned {
+            asset_id,
+            owner: who,
+            amount,
+        });
+        Ok(())
+    }
+
+    /// Transfere `amount` unidades do ativo de `caller` para `to`.
+    /// Falha se a conta de `caller` estiver congelada para este ativo.
+    pub fn transfer(
+        &mut self,
+        caller: T::AccountId,
+        asset_id: T::AssetId,
+        to: T::AccountId,
+        amount: T::Balance,
+    ) -> DispatchResult {
+        self.ensure_asset(&asset_id)?;
+        if self.is_frozen(&asset_id, &caller) {
+            return Err("Account is frozen.");
+        }
+
+        let new_caller_balance = self
+            .balance(&asset_id, &caller)
+            .checked_sub(&amount)
+            .ok_or("Insufficient balance.")?;
+        let new_to_balance = self
+            .balance(&asset_id, &to)
+            .checked_add(&amount)
+            .ok_or("Overflow")?;
+
+        // Uma transferência para a própria conta não altera nenhum saldo.
+        if caller != to {
+            self.set_account_balance(&asset_id, &caller, new_caller_balance);
+            self.set_account_balance(&asset_id, &to, new_to_balance);
+        }
+        self.deposit_event(Event::Transferred {
+            asset_id,
+            from: caller,
+            to,
+            amount,
+        });
+        Ok(())
+    }
+
+    /// Congela a conta de `who` para o ativo, impedindo-a de transferi-lo.
+    /// Apenas o administrador do ativo pode congelar contas.
+    pub fn freeze(
+        &mut self,
+        caller: T::AccountId,
+        asset_id: T::AssetId,
+        who: T::AccountId,
+    ) -> DispatchResult {
+        self.ensure_admin(&caller, &asset_id)?;
+        self.frozen
+            .entry(asset_id.clone())
+            .or_default()
+            .insert(who.clone());
+        self.deposit_event(Event::Frozen { asset_id, who });
+        Ok(())
+    }
+
+    /// Descongela a conta de `who` para o ativo.
+    /// Apenas o administrador do ativo pode descongelar contas.
+    pub fn thaw(
+        &mut self,
+        caller: T::AccountId,
+        asset_id: T::AssetId,
+        who: T::AccountId,
+    ) -> DispatchResult {
+        self.ensure_admin(&caller, &asset_id)?;
+        if let Some(frozen) = self.frozen.get_mut(&asset_id) {
+            frozen.remove(&who);
+            if frozen.is_empty() {
+                self.frozen.remove(&asset_id);
+            }
+        }
+        self.deposit_event(Event::Thawed { asset_id, who });
+        Ok(())
+    }
+
+    /// Define o nome, o símbolo e as casas decimais do ativo.
+    /// Apenas o proprietário do ativo pode definir os metadados.
+    pub fn set_metadata(
+        &mut self,
+        caller: T::AccountId,
+        asset_id: T::AssetId,
+        name: Vec<u8>,
+        symbol: Vec<u8>,
+        decimals: u8,
+    ) -> DispatchResult {
+        self.ensure_owner(&caller, &asset_id)?;
+        let limit = T::STRING_LIMIT as usize;
+        if name.len() > limit || symbol.len() > limit {
+            return Err("Metadata string too long.");
+        }
+
+        self.metadata.insert(
+            asset_id.clone(),
+            AssetMetadata {
+                name: name.clone(),
+                symbol: symbol.clone(),
+                decimals,
+            },
+        );
+        self.deposit_event(Event::MetadataSet {
+            asset_id,
+            name,
+            symbol,
+            decimals,
+        });
+        Ok(())
+    }
+
+    /// Destrói o ativo, removendo todos os saldos, contas congeladas e metadados dele, e devolve
+    /// o depósito. Apenas o proprietário do ativo pode destruí-lo.
+    pub fn destroy(runtime: &mut T, caller: T::AccountId, asset_id: T::AssetId) -> DispatchResult {
+        let pallet: &mut Self = runtime.pallet_mut();
+        pallet.ensure_owner(&caller, &asset_id)?;
+
+        let deposit = pallet
+            .assets
+            .remove(&asset_id)
+            .map(|details| details.deposit);
+        pallet.metadata.remove(&asset_id);
+        pallet.accounts.remove(&asset_id);
+        pallet.frozen.remove(&asset_id);
+        pallet.deposit_event(Event::Destroyed { asset_id });
+
+        if let Some(deposit) = deposit {
+            T::Currency::unreserve_named(runtime, &ASSETS_RESERVE_ID, &caller, deposit);
+        }
+        Ok(())
+    }
+}
+
+impl<T: Config> Pallet<T> {
+    /// Cria uma nova instância do Módulo de Ativos.
+    pub fn new() -> Self {
+        Self {
+            assets: BTreeMap::new(),
+            accounts: BTreeMap::new(),
+            frozen: BTreeMap::new(),
+            metadata: BTreeMap::new(),
+            events: Vec::new(),
+        }
+    }
+
+    /// Obtém os dados de um ativo, se ele existir.
+    pub fn asset(&self, asset_id: &T::AssetId) -> Option<&AssetDetailsOf<T>> {
+        self.assets.get(asset_id)
+    }
+
+    /// Obtém os metadados de um ativo, se tiverem sido definidos.
+    pub fn metadata(&self, asset_id: &T::AssetId) -> Option<&AssetMetadata> {
+        self.metadata.get(asset_id)
+    }
+
+    /// Obtém a quantia total de um ativo que existe.
+    pub fn total_supply(&self, asset_id: &T::AssetId) -> T::Balance {
+        self.assets
+            .get(asset_id)
+            .map(|details| details.supply)
+            .unwrap_or(T::Balance::zero())
+    }
+
+    /// Obtém o saldo de `who` no ativo.
+    pub fn balance(&self, asset_id: &T::AssetId, who: &T::AccountId) -> T::Balance {
+        self.accounts
+            .get(asset_id)
+            .and_then(|accounts| accounts.get(who))
+            .copied()
+            .unwrap_or(T::Balance::zero())
+    }
+
+    /// Verifica se a conta de `who` está congelada para o ativo.
+    pub fn is_frozen(&self, asset_id: &T::AssetId, who: &T::AccountId) -> bool {
+        self.frozen
+            .get(asset_id)
+            .is_some_and(|frozen| frozen.contains(who))
+    }
+
+    /// Obtém os eventos emitidos por este pallet no bloco atual, em ordem.
+    pub fn events(&self) -> &[Event<T>] {
+        &self.events
+    }
+
+    /// Registra um evento emitido por este pallet.
+    fn deposit_event(&mut self, event: Event<T>) {
+        self.events.push(event);
+    }
+
+    /// Obtém os dados do ativo, ou um erro se ele não existir.
+    fn ensure_asset(&self, asset_id: &T::AssetId) -> Result<&AssetDetailsOf<T>, &'static str> {
+        self.assets.get(asset_id).ok_or("Unknown asset.")
+    }
+
+    /// Garante que `who` é o administrador do ativo.
+    fn ensure_admin(
+        &self,
+        who: &T::AccountId,
+        asset_id: &T::AssetId,
+    ) -> Result<&AssetDetailsOf<T>, &'static str> {
+        let details = self.ensure_asset(asset_id)?;
+        if &details.admin != who {
+            return Err("Not the asset admin.");
+        }
+        Ok(details)
+    }
+
+    /// Garante que `who` é o proprietário do ativo.
+    fn ensure_owner(&self, who: &T::AccountId, asset_id: &T::AssetId) -> DispatchResult {
+        if &self.ensure_asset(asset_id)?.owner != who {
+            return Err("Not the asset owner.");
+        }
+        Ok(())
+    }
+
+    /// Define o saldo de `who` no ativo. Saldos zerados são removidos do armazenamento.
+    fn set_account_balance(
+        &mut self,
+        asset_id: &T::AssetId,
+        who: &T::AccountId,
+        amount: T::Balance,
+    ) {
+        if !amount.is_zero() {
+            self.accounts
+                .entry(asset_id.clone())
+                .or_default()
+                .insert(who.clone(), amount);
+        } else if let Some(accounts) = self.accounts.get_mut(asset_id) {
+            accounts.remove(who);
+            if accounts.is_empty() {
+                self.accounts.remove(asset_id);
+            }
+        }
+    }
+
+    /// Define a quantia total do ativo que existe.
+    fn set_supply(&mut self, asset_id: &T::AssetId, supply: T::Balance) {
+        if let Some(details) = self.assets.get_mut(asset_id) {
+            details.supply = supply;
+        }
+    }
+}
+
+impl<T: Config + GetPallet<Self>> Hooks<T> for Pallet<T> {
+    fn on_initialize(runtime: &mut T, _n: T::BlockNumber) {
+        let pallet: &mut Self = runtime.pallet_mut();
+        pallet.events.clear();
+    }
+}
+
+impl<T: Config> Storage for Pallet<T> {
+    fn storage(&self) -> Vec<(Vec<u8>, Vec<u8>)> {
+        let mut storage: Vec<_> = storage_map("assets", &self.assets).collect();
+        storage.extend(storage_map("accounts", &self.accounts));
+        storage.extend(storage_map("frozen", &self.frozen));
+        storage.extend(storage_map("metadata", &self.metadata));
+        storage
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::{balances, system};
+
+    /// Um runtime mínimo com os pallets dos quais este pallet depende.
+    struct TestRuntime {
+        balances: balances::Pallet<TestRuntime>,
+        assets: Pallet<TestRuntime>,
+    }
+
+    impl TestRuntime {
+        /// Cria o runtime com saldo para os depósitos de alice e bob.
+        fn new() -> Self {
+            let mut runtime = Self {
+                balances: balances::Pallet::new(),
+                assets: Pallet::new(),
+            };
+            for who in ["alice", "bob"] {
+                runtime.balances.set_balance(&who, 100);
+            }
+            runtime
+        }
+    }
+
+    impl super::Config for TestRuntime {
+        type AssetId = u32;
+        type Balance = u128;
+        type Currency = balances::Pallet<Self>;
+        const ASSET_DEPOSIT: u128 = 10;
+        const STRING_LIMIT: u32 = 8;
+    }
+
+    impl balances::Config for TestRuntime {
+        type Balance = u128;
+        const EXISTENTIAL_DEPOSIT: u128 = 1;
+    }
+
+    impl system::Config for TestRuntime {
+        type AccountId = &'static str;
+        type BlockNumber = u32;
+        type Nonce = u32;
+        type Hash = crate::hashing::H256;
+        type Hashing = crate::hashing::Sha256;
+    }
+
+    impl GetPallet<balances::Pallet<TestRuntime>> for TestRuntime {
+        fn pallet(&self) -> &balances::Pallet<TestRuntime> {
+            &self.balances
+        }
+
+        fn pallet_mut(&mut self) -> &mut balances::Pallet<TestRuntime> {
+            &mut self.balances
+        }
+    }
+
+    impl GetPallet<Pallet<TestRuntime>> for TestRuntime {
+        fn pallet(&self) -> &Pallet<TestRuntime> {
+            &self.assets
+        }
+
+        fn pallet_mut(&mut self) -> &mut Pallet<TestRuntime> {
+            &mut self.assets
+        }
+    }
+
+    type Assets = Pallet<TestRuntime>;
+
+    #[test]
+    fn create_mint_and_transfer() {
+        let mut runtime = TestRuntime::new();
+
+        // Criar um ativo reserva o depósito de quem o cria
+        assert_eq!(Assets::create(&mut runtime, "alice", 1, "bob"), Ok(()));
+        assert_eq!(
+            Assets::create(&mut runtime, "bob", 1, "bob"),
+            Err("Asset already exists.")
+        );
+        assert_eq!(runtime.balances.reserved_balance(&"alice"), 10);
+        assert_eq!(
+            Assets::create(&mut runtime, "charlie", 2, "charlie"),
+            Err("Insufficient balance")
+        );
+        let assets = &mut runtime.assets;
+
+        // Apenas o administrador pode emitir
+        assert_eq!(
+            assets.mint("alice", 1, "alice", 100),
+            Err("Not the asset admin.")
+        );
+        assert_eq!(assets.mint("bob", 1, "alice", 100), Ok(()));
+        assert_eq!(assets.total_supply(&1), 100);
+
+        assert_eq!(
+            assets.transfer("alice", 1, "charlie", 150),
+            Err("Insufficient balance.")
+        );
+        assert_eq!(assets.transfer("alice", 1, "charlie", 40), Ok(()));
+        assert_eq!(assets.balance(&1, &"alice"), 60);
+        assert_eq!(assets.balance(&1, &"charlie"), 40);
+
+        // Queimar reduz o suprimento total
+        assert_eq!(assets.burn("bob", 1, "charlie", 10), Ok(()));
+        assert_eq!(assets.balance(&1, &"charlie"), 30);
+        assert_eq!(assets.total_supply(&1), 90);
+
+        // Ativos diferentes têm saldos independentes
+        assert_eq!(
+            assets.transfer("alice", 2, "charlie", 1),
+            Err("Unknown asset.")
+        );
+        assert!(matches!(
+            assets.events().last(),
+            Some(Event::Burned {
+                asset_id: 1,
+                owner: "charlie",
+                amount: 10
+            })
+        ));
+    }
+
+    #[test]
+    fn freeze_metadata_and_destroy() {
+        let mut runtime = TestRuntime::new();
+        assert_eq!(Assets::create(&mut runtime, "alice", 7, "alice"), Ok(()));
+        assert_eq!(Assets::create(&mut runtime, "bob", 8, "bob"), Ok(()));
+        let assets = &mut runtime.assets;
+        assert_eq!(assets.mint("alice", 7, "bob", 50), Ok(()));
+        assert_eq!(assets.mint("bob", 8, "bob", 20), Ok(()));
+
+        // Uma conta congelada não pode transferir, mas pode receber
+        assert_eq!(assets.freeze("alice", 7, "bob"), Ok(()));
+        assert_eq!(
+            assets.transfer("bob", 7, "alice", 10),
+            Err("Account is frozen.")
+        );
+        assert_eq!(assets.transfer("bob", 8, "alice", 10), Ok(()));
+        assert_eq!(assets.mint("alice", 7, "bob", 5), Ok(()));
+        assert_eq!(assets.thaw("alice", 7, "bob"), Ok(()));
+        assert_eq!(assets.transfer("bob", 7, "alice", 10), Ok(()));
+
+        assert_eq!(
+            assets.set_metadata("alice", 7, b"Stable Dollar".to_vec(), b"USD".to_vec(), 2),
+            Err("Metadata string too long.")
+        );
+        assert_eq!(
+            assets.set_metadata("bob", 7, b"Dollar".to_vec(), b"USD".to_vec(), 2),
+            Err("Not the asset owner.")
+        );
+        assert_eq!(
+            assets.set_metadata("alice", 7, b"Dollar".to_vec(), b"USD".to_vec(), 2),
+            Ok(())
+        );
+        assert_eq!(assets.metadata(&7).map(|m| m.decimals), Some(2));
+        assert_eq!(assets.freeze("alice", 7, "bob"), Ok(()));
+
+        // Destruir o ativo apaga apenas os dados dele e devolve o depósito
+        assert_eq!(
+            Assets::destroy(&mut runtime, "bob", 7),
+            Err("Not the asset owner.")
+        );
+        assert_eq!(Assets::destroy(&mut runtime, "alice", 7), Ok(()));
+        assert_eq!(runtime.balances.reserved_balance(&"alice"), 0);
+        let assets = &runtime.assets;
+        assert_eq!(assets.asset(&7), None);
+        assert_eq!(assets.metadata(&7), None);
+        assert_eq!(assets.balance(&7, &"bob"), 0);
+        assert!(!assets.is_frozen(&7, &"bob"));
+        assert_eq!(assets.balance(&8, &"bob"), 10);
+        assert_eq!(assets.balance(&8, &"alice"), 10);
+    }
+}
@@ -1,79 +1,1002 @@
+use crate::support::{
+    storage_map, storage_value, BalanceStatus, Currency, ExistenceRequirement, GetPallet, Hooks,
+    LockIdentifier, NamedReservableCurrency, NegativeImbalance, Origin, PositiveImbalance,
+    ReservableCurrency, ReserveIdentifier, Storage, WithdrawReasons,
+};
+use core::cmp::min;
+use core::fmt::Debug;
 use num::traits::{CheckedAdd, CheckedSub, Zero};
 use std::collections::BTreeMap;
 
+/// Um bloqueio sobre parte do saldo livre de uma conta.
+/// Os fundos bloqueados continuam na conta, mas não podem ser sacados pelos motivos indicados.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct BalanceLock<Balance> {
+    /// A quantia que deve permanecer no saldo livre.
+    pub amount: Balance,
+    /// Os tipos de saque impedidos por este bloqueio.
+    pub reasons: WithdrawReasons,
+}
+
+/// Os eventos emitidos pelo Módulo Balances.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Event<T: Config> {
+    /// `amount` foi transferido de `from` para `to`.
+    Transfer {
+        from: T::AccountId,
+        to: T::AccountId,
+        amount: T::Balance,
+    },
+    /// `account` ficou abaixo do depósito existencial e foi removida, e a poeira `amount` que
+    /// restava no seu saldo livre foi queimada.
+    DustLost {
+        account: T::AccountId,
+        amount: T::Balance,
+    },
+    /// `owner` autorizou `spender` a transferir até `amount` em seu nome.
+    Approval {
+        owner: T::AccountId,
+        spender: T::AccountId,
+        amount: T::Balance,
+    },
+}
+
 /// A característica de configuração do Módulo Balances.
 /// Contém os tipos básicos necessários para lidar com saldos.
 pub trait Config: crate::system::Config {
-    type Balance: Zero + CheckedSub + CheckedAdd + Copy + Ord;
+    type Balance: Zero + CheckedSub + CheckedAdd + Copy + Ord + Debug;
+    /// O saldo livre mínimo que uma conta precisa manter para continuar viva.
+    const EXISTENTIAL_DEPOSIT: Self::Balance;
 }
 
 /// Este é o Módulo de Saldos.
 /// É um módulo simples que monitora quanto saldo cada conta tem nesta máquina de estados.
-#[derive(Debug)]
+#[derive(Debug, Clone)]
 pub struct Pallet<T: Config> {
-    /// Um mapeamento simples de armazenamento de contas para seus saldos.
+    /// Um mapeamento simples de armazenamento de contas para seus saldos livres.
     balances: BTreeMap<T::AccountId, T::Balance>,
+    /// O saldo reservado (sem nome) de cada conta. Não pode ser transferido nem gasto.
+    reserved: BTreeMap<T::AccountId, T::Balance>,
+    /// As reservas nomeadas de cada conta, indexadas pelo identificador da reserva.
+    holds: BTreeMap<T::AccountId, BTreeMap<ReserveIdentifier, T::Balance>>,
+    /// Os bloqueios sobre o saldo livre de cada conta, indexados pelo identificador do bloqueio.
+    /// Os bloqueios se sobrepõem: apenas o maior deles limita o que pode ser gasto.
+    locks: BTreeMap<T::AccountId, BTreeMap<LockIdentifier, BalanceLock<T::Balance>>>,
+    /// As autorizações de gasto, indexadas por `(owner, spender)`.
+    allowances: BTreeMap<(T::AccountId, T::AccountId), T::Balance>,
+    /// A quantia total da moeda que existe em todas as contas.
+    total_issuance: T::Balance,
+    /// Os eventos emitidos por este pallet no bloco atual, em ordem.
+    events: Vec<Event<T>>,
 }
 
 #[macros::call]
 impl<T: Config> Pallet<T> {
     /// Transfere `amount` de uma conta para outra.
-    /// Esta função verifica se `caller` tem pelo menos `amount` de saldo para transferir
-    /// e impede que ocorram overflow/underflow matemáticos.
+    /// Esta função verifica se `caller` tem pelo menos `amount` de saldo para transferir,
+    /// respeitando os bloqueios da conta, e impede que ocorram overflow/underflow matemáticos.
     pub fn transfer(
         &mut self,
         caller: T::AccountId,
         to: T::AccountId,
         amount: T::Balance,
     ) -> crate::support::DispatchResult {
-        let caller_balance = self.balance(&caller);
-        let to_balance = self.balance(&to);
+        self.do_transfer(&caller, &to, amount, ExistenceRequirement::AllowDeath)
+    }
+
+    /// O mesmo que `transfer`, mas falha se `caller` ficar com menos do que o depósito
+    /// existencial.
+    pub fn transfer_keep_alive(
+        &mut self,
+        caller: T::AccountId,
+        to: T::AccountId,
+        amount: T::Balance,
+    ) -> crate::support::DispatchResult {
+        self.do_transfer(&caller, &to, amount, ExistenceRequirement::KeepAlive)
+    }
+
+    /// Transfere todo o saldo gastável de `caller` para `to`.
+    /// Fundos bloqueados e reservados permanecem em `caller`. Se `keep_alive` for verdadeiro,
+    /// `caller` também mantém o depósito existencial.
+    pub fn transfer_all(
+        &mut self,
+        caller: T::AccountId,
+        to: T::AccountId,
+        keep_alive: bool,
+    ) -> crate::support::DispatchResult {
+        let existence = if keep_alive {
+            ExistenceRequirement::KeepAlive
+        } else {
+            ExistenceRequirement::AllowDeath
+        };
+        let amount = self.reducible_balance(&caller, existence);
+        self.do_transfer(&caller, &to, amount, existence)
+    }
+
+    /// Autoriza `spender` a transferir até `amount` do saldo de `caller`.
+    /// Substitui qualquer autorização anterior dada ao mesmo `spender`.
+    pub fn approve(
+        &mut self,
+        caller: T::AccountId,
+        spender: T::AccountId,
+        amount: T::Balance,
+    ) -> crate::support::DispatchResult {
+        self.set_allowance(caller, spender, amount);
+        Ok(())
+    }
+
+    /// Aumenta em `amount` a autorização dada por `caller` a `spender`.
+    pub fn increase_allowance(
+        &mut self,
+        caller: T::AccountId,
+        spender: T::AccountId,
+        amount: T::Balance,
+    ) -> crate::support::DispatchResult {
+        let new_allowance = self
+            .allowance(&caller, &spender)
+            .checked_add(&amount)
+            .ok_or("Overflow")?;
+        self.set_allowance(caller, spender, new_allowance);
+        Ok(())
+    }
 
-        let new_caller_balance = caller_balance
+    /// Diminui em `amount` a autorização dada por `caller` a `spender`.
+    pub fn decrease_allowance(
+        &mut self,
+        caller: T::AccountId,
+        spender: T::AccountId,
+        amount: T::Balance,
+    ) -> crate::support::DispatchResult {
+        let new_allowance = self
+            .allowance(&caller, &spender)
             .checked_sub(&amount)
-            .ok_or("Insufficient balance")?;
-        let new_to_balance = to_balance.checked_add(&amount).ok_or("Overflow")?;
+            .ok_or("Decreased allowance below zero")?;
+        self.set_allowance(caller, spender, new_allowance);
+        Ok(())
+    }
 
-        self.balances.insert(caller, new_caller_balance);
-        self.balances.insert(to, new_to_balance);
+    /// Transfere `amount` de `owner` para `to` em nome de `owner`.
+    /// `caller` precisa ter recebido de `owner` uma autorização de pelo menos `amount`, que é
+    /// consumida pela transferência.
+    pub fn transfer_from(
+        &mut self,
+        caller: T::AccountId,
+        owner: T::AccountId,
+        to: T::AccountId,
+        amount: T::Balance,
+    ) -> crate::support::DispatchResult {
+        let allowance = self.allowance(&owner, &caller);
+        let new_allowance = allowance
+            .checked_sub(&amount)
+            .ok_or("Insufficient allowance")?;
+
+        self.do_transfer(&owner, &to, amount, ExistenceRequirement::AllowDeath)?;
+        if new_allowance.is_zero() {
+            self.allowances.remove(&(owner, caller));
+        } else {
+            self.allowances.insert((owner, caller), new_allowance);
+        }
 
         Ok(())
     }
+
+    /// Define o saldo livre de `who` como `amount`, ajustando a emissão total.
+    /// Apenas a origem privilegiada pode fazer esta chamada. Falha, sem alterar nada, se a nova
+    /// emissão total não couber no tipo do saldo.
+    pub fn force_set_balance(
+        &mut self,
+        origin: Origin<T::AccountId>,
+        who: T::AccountId,
+        amount: T::Balance,
+    ) -> crate::support::DispatchResult {
+        origin.ensure_root()?;
+        self.try_set_balance(&who, amount)
+    }
 }
 
 impl<T: Config> Pallet<T> {
     pub fn new() -> Self {
         Self {
             balances: BTreeMap::new(),
+            reserved: BTreeMap::new(),
+            holds: BTreeMap::new(),
+            locks: BTreeMap::new(),
+            allowances: BTreeMap::new(),
+            total_issuance: T::Balance::zero(),
+            events: Vec::new(),
         }
     }
 
-    /// Define o saldo de um utilizador.
+    /// Define o saldo de um utilizador, ajustando a emissão total.
+    /// Usada para os saldos iniciais; uma emissão total que não cabe no tipo do saldo é um erro de
+    /// configuração, e causa pânico.
     pub fn set_balance(&mut self, who: &T::AccountId, amount: T::Balance) {
+        self.try_set_balance(who, amount)
+            .expect("total issuance overflow");
+    }
+
+    /// Define o saldo de um utilizador, ajustando a emissão total, ou falha sem alterar nada se a
+    /// nova emissão total não couber no tipo do saldo.
+    pub fn try_set_balance(
+        &mut self,
+        who: &T::AccountId,
+        amount: T::Balance,
+    ) -> crate::support::DispatchResult {
+        let old = self.balance(who);
+        self.total_issuance = self
+            .total_issuance
+            .checked_sub(&old)
+            .and_then(|issuance| issuance.checked_add(&amount))
+            .ok_or("Overflow")?;
         self.balances.insert(who.clone(), amount);
+        Ok(())
+    }
+
+    /// Obtém a quantia total da moeda que existe em todas as contas.
+    pub fn total_issuance(&self) -> T::Balance {
+        self.total_issuance
+    }
+
+    /// Obtém o saldo total de um utilizador: o saldo livre mais o saldo reservado.
+    pub fn total_balance(&self, who: &T::AccountId) -> T::Balance {
+        let free = self.balance(who);
+        free.checked_add(&self.reserved_balance(who))
+            .unwrap_or(free)
+    }
+
+    /// Retira `amount` do saldo livre de `who` por `reason`, reduzindo o total emitido.
+    /// Respeita os bloqueios da conta e o requisito de existência.
+    pub fn withdraw(
+        &mut self,
+        who: &T::AccountId,
+        amount: T::Balance,
+        reason: WithdrawReasons,
+        existence: ExistenceRequirement,
+    ) -> crate::support::DispatchResult {
+        let new_balance = self
+            .balance(who)
+            .checked_sub(&amount)
+            .ok_or("Insufficient balance")?;
+        self.ensure_can_withdraw(who, reason, new_balance)?;
+        if existence == ExistenceRequirement::KeepAlive && new_balance < T::EXISTENTIAL_DEPOSIT {
+            return Err("Withdrawal would kill account");
+        }
+
+        self.balances.insert(who.clone(), new_balance);
+        self.total_issuance = self
+            .total_issuance
+            .checked_sub(&amount)
+            .unwrap_or(T::Balance::zero());
+        self.reap_dust(who);
+        Ok(())
+    }
+
+    /// Cria `amount` de novos fundos no saldo livre de `who`, aumentando o total emitido.
+    pub fn deposit(
+        &mut self,
+        who: &T::AccountId,
+        amount: T::Balance,
+    ) -> crate::support::DispatchResult {
+        let new_issuance = self.total_issuance.checked_add(&amount).ok_or("Overflow")?;
+        let new_balance = self.balance(who).checked_add(&amount).ok_or("Overflow")?;
+
+        self.balances.insert(who.clone(), new_balance);
+        self.total_issuance = new_issuance;
+        Ok(())
     }
 
     /// Obtém o saldo de um utilizador.
     pub fn balance(&self, who: &T::AccountId) -> T::Balance {
         *self.balances.get(who).unwrap_or(&T::Balance::zero())
     }
+
+    /// Obtém quanto `spender` ainda pode transferir em nome de `owner`.
+    pub fn allowance(&self, owner: &T::AccountId, spender: &T::AccountId) -> T::Balance {
+        *self
+            .allowances
+            .get(&(owner.clone(), spender.clone()))
+            .unwrap_or(&T::Balance::zero())
+    }
+
+    /// Obtém os eventos emitidos por este pallet no bloco atual, em ordem.
+    pub fn events(&self) -> &[Event<T>] {
+        &self.events
+    }
+
+    /// Registra um evento emitido por este pallet.
+    fn deposit_event(&mut self, event: Event<T>) {
+        self.events.push(event);
+    }
+
+    /// Move `amount` do saldo livre de `from` para o saldo livre de `to`, respeitando os
+    /// bloqueios de `from` e o requisito de existência, e emite um evento `Transfer`. O saldo de
+    /// `to` precisa chegar ao depósito existencial.
+    /// Uma transferência de zero não faz nada, e a poeira que restar em `from` é removida.
+    fn do_transfer(
+        &mut self,
+        from: &T::AccountId,
+        to: &T::AccountId,
+        amount: T::Balance,
+        existence: ExistenceRequirement,
+    ) -> crate::support::DispatchResult {
+        if amount.is_zero() {
+            return Ok(());
+        }
+
+        let from_balance = self.balance(from);
+        let to_balance = self.balance(to);
+
+        let new_from_balance = from_balance
+            .checked_sub(&amount)
+            .ok_or("Insufficient balance")?;
+        let new_to_balance = to_balance.checked_add(&amount).ok_or("Overflow")?;
+        self.ensure_can_withdraw(from, WithdrawReasons::Transfer, new_from_balance)?;
+        if existence == ExistenceRequirement::KeepAlive && new_from_balance < T::EXISTENTIAL_DEPOSIT
+        {
+            return Err("Transfer would kill account");
+        }
+        // Ninguém remove a poeira de quem recebe, então a transferência não pode criá-la.
+        if from != to && new_to_balance < T::EXISTENTIAL_DEPOSIT {
+            return Err("Transfer would leave the recipient below the existential deposit");
+        }
+
+        // Uma transferência para a própria conta não altera nenhum saldo.
+        if from != to {
+            self.balances.insert(from.clone(), new_from_balance);
+            self.balances.insert(to.clone(), new_to_balance);
+        }
+
+        self.deposit_event(Event::Transfer {
+            from: from.clone(),
+            to: to.clone(),
+            amount,
+        });
+        self.reap_dust(from);
+        Ok(())
+    }
+
+    /// Remove `who` se o seu saldo livre estiver abaixo do depósito existencial e nada mais
+    /// mantiver a conta viva, ou seja, se ela não tiver reservas nem bloqueios. A poeira que
+    /// restava é queimada, reduzindo o total emitido.
+    fn reap_dust(&mut self, who: &T::AccountId) {
+        let dust = self.balance(who);
+        if dust >= T::EXISTENTIAL_DEPOSIT
+            || !self.reserved_balance(who).is_zero()
+            || self.locks.contains_key(who)
+        {
+            return;
+        }
+
+        self.balances.remove(who);
+        if !dust.is_zero() {
+            self.total_issuance = self
+                .total_issuance
+                .checked_sub(&dust)
+                .unwrap_or(T::Balance::zero());
+            self.deposit_event(Event::DustLost {
+                account: who.clone(),
+                amount: dust,
+            });
+        }
+    }
+
+    /// Define a autorização de `owner` para `spender` e emite um evento `Approval`.
+    fn set_allowance(&mut self, owner: T::AccountId, spender: T::AccountId, amount: T::Balance) {
+        if amount.is_zero() {
+            self.allowances.remove(&(owner.clone(), spender.clone()));
+        } else {
+            self.allowances
+                .insert((owner.clone(), spender.clone()), amount);
+        }
+        self.deposit_event(Event::Approval {
+            owner,
+            spender,
+            amount,
+        });
+    }
+
+    /// Obtém a parte do saldo livre de um utilizador que pode ser transferida, ou seja, o saldo
+    /// livre menos o maior bloqueio que impede transferências.
+    pub fn spendable_balance(&self, who: &T::AccountId) -> T::Balance {
+        let locked = self.locked_balance(who, WithdrawReasons::Transfer);
+        self.balance(who)
+            .checked_sub(&locked)
+            .unwrap_or(T::Balance::zero())
+    }
+
+    /// Obtém quanto um utilizador pode transferir de uma só vez: o saldo gastável, limitado pelo
+    /// depósito existencial se a conta precisar continuar viva.
+    pub fn reducible_balance(
+        &self,
+        who: &T::AccountId,
+        existence: ExistenceRequirement,
+    ) -> T::Balance {
+        let spendable = self.spendable_balance(who);
+        match existence {
+            ExistenceRequirement::AllowDeath => spendable,
+            ExistenceRequirement::KeepAlive => {
+                let above_existential = self
+                    .balance(who)
+                    .checked_sub(&T::EXISTENTIAL_DEPOSIT)
+                    .unwrap_or(T::Balance::zero());
+                min(spendable, above_existential)
+            }
+        }
+    }
+
+    /// Obtém o maior bloqueio de um utilizador que impede saques feitos por `reason`.
+    pub fn locked_balance(&self, who: &T::AccountId, reason: WithdrawReasons) -> T::Balance {
+        self.locks
+            .get(who)
+            .into_iter()
+            .flat_map(|locks| locks.values())
+            .filter(|lock| lock.reasons.intersects(reason))
+            .map(|lock| lock.amount)
+            .fold(T::Balance::zero(), |max, amount| max.max(amount))
+    }
+
+    /// Obtém o bloqueio `id` de um utilizador, se existir.
+    pub fn lock(
+        &self,
+        id: &LockIdentifier,
+        who: &T::AccountId,
+    ) -> Option<&BalanceLock<T::Balance>> {
+        self.locks.get(who).and_then(|locks| locks.get(id))
+    }
+
+    /// Verifica se `who` pode ficar com `new_balance` de saldo livre depois de um saque feito por
+    /// `reason`, sem violar nenhum dos seus bloqueios.
+    pub fn ensure_can_withdraw(
+        &self,
+        who: &T::AccountId,
+        reason: WithdrawReasons,
+        new_balance: T::Balance,
+    ) -> crate::support::DispatchResult {
+        if new_balance < self.locked_balance(who, reason) {
+            return Err("Balance is locked");
+        }
+        Ok(())
+    }
+
+    /// Cria ou substitui o bloqueio `id` sobre o saldo de `who`.
+    /// Um bloqueio com `amount` zero é removido.
+    #[cfg(test)]
+    pub fn set_lock(
+        &mut self,
+        id: LockIdentifier,
+        who: &T::AccountId,
+        amount: T::Balance,
+        reasons: WithdrawReasons,
+    ) {
+        if amount.is_zero() {
+            self.remove_lock(id, who);
+            return;
+        }
+        self.locks
+            .entry(who.clone())
+            .or_default()
+            .insert(id, BalanceLock { amount, reasons });
+    }
+
+    /// Estende o bloqueio `id` sobre o saldo de `who`, ou o cria se não existir.
+    /// O bloqueio resultante fica com a maior das quantias e com a união dos motivos.
+    #[cfg(test)]
+    pub fn extend_lock(
+        &mut self,
+        id: LockIdentifier,
+        who: &T::AccountId,
+        amount: T::Balance,
+        reasons: WithdrawReasons,
+    ) {
+        let lock = match self.lock(&id, who) {
+            Some(lock) => BalanceLock {
+                amount: lock.amount.max(amount),
+                reasons: lock.reasons.union(reasons),
+            },
+            None => BalanceLock { amount, reasons },
+        };
+        self.set_lock(id, who, lock.amount, lock.reasons);
+    }
+
+    /// Remove o bloqueio `id` do saldo de `who`.
+    #[cfg(test)]
+    pub fn remove_lock(&mut self, id: LockIdentifier, who: &T::AccountId) {
+        if let Some(locks) = self.locks.get_mut(who) {
+            locks.remove(&id);
+            if locks.is_empty() {
+                self.locks.remove(who);
+            }
+        }
+    }
+
+    /// Obtém o saldo reservado total de um utilizador, incluindo todas as reservas nomeadas.
+    pub fn reserved_balance(&self, who: &T::AccountId) -> T::Balance {
+        self.holds
+            .get(who)
+            .into_iter()
+            .flat_map(|holds| holds.values())
+            .fold(self.reserved_of(None, who), |total, amount| {
+                total.checked_add(amount).unwrap_or(total)
+            })
+    }
+
+    /// Obtém o saldo retido por um utilizador sob a reserva nomeada `id`.
+    pub fn reserved_balance_named(&self, id: &ReserveIdentifier, who: &T::AccountId) -> T::Balance {
+        self.reserved_of(Some(id), who)
+    }
+
+    /// Move `amount` do saldo livre de `who` para o seu saldo reservado.
+    pub fn reserve(
+        &mut self,
+        who: &T::AccountId,
+        amount: T::Balance,
+    ) -> crate::support::DispatchResult {
+        self.do_reserve(None, who, amount)
+    }
+
+    /// Move até `amount` do saldo reservado de `who` de volta para o seu saldo livre.
+    /// Retorna a parte de `amount` que não pôde ser liberada.
+    pub fn unreserve(&mut self, who: &T::AccountId, amount: T::Balance) -> T::Balance {
+        self.do_unreserve(None, who, amount)
+    }
+
+    /// Destrói até `amount` do saldo reservado de `who`.
+    /// Retorna a parte de `amount` que não pôde ser cortada.
+    pub fn slash_reserved(&mut self, who: &T::AccountId, amount: T::Balance) -> T::Balance {
+        self.do_slash_reserved(None, who, amount)
+    }
+
+    /// Move até `amount` do saldo reservado de `slashed` para `beneficiary`, que recebe os fundos
+    /// como saldo livre ou reservado de acordo com `status`.
+    /// Retorna a parte de `amount` que não pôde ser movida.
+    pub fn repatriate_reserved(
+        &mut self,
+        slashed: &T::AccountId,
+        beneficiary: &T::AccountId,
+        amount: T::Balance,
+        status: BalanceStatus,
+    ) -> Result<T::Balance, &'static str> {
+        self.do_repatriate_reserved(None, slashed, beneficiary, amount, status)
+    }
+
+    /// O mesmo que `reserve`, mas retém os fundos sob a reserva nomeada `id`.
+    pub fn reserve_named(
+        &mut self,
+        id: &ReserveIdentifier,
+        who: &T::AccountId,
+        amount: T::Balance,
+    ) -> crate::support::DispatchResult {
+        self.do_reserve(Some(id), who, amount)
+    }
+
+    /// O mesmo que `unreserve`, mas libera apenas os fundos da reserva nomeada `id`.
+    pub fn unreserve_named(
+        &mut self,
+        id: &ReserveIdentifier,
+        who: &T::AccountId,
+        amount: T::Balance,
+    ) -> T::Balance {
+        self.do_unreserve(Some(id), who, amount)
+    }
+
+    /// O mesmo que `slash_reserved`, mas corta apenas os fundos da reserva nomeada `id`.
+    pub fn slash_reserved_named(
+        &mut self,
+        id: &ReserveIdentifier,
+        who: &T::AccountId,
+        amount: T::Balance,
+    ) -> T::Balance {
+        self.do_slash_reserved(Some(id), who, amount)
+    }
+
+    /// O mesmo que `repatriate_reserved`, mas move os fundos da reserva nomeada `id`.
+    /// Se `status` for `Reserved`, os fundos ficam retidos no beneficiário sob o mesmo `id`.
+    pub fn repatriate_reserved_named(
+        &mut self,
+        id: &ReserveIdentifier,
+        slashed: &T::AccountId,
+        beneficiary: &T::AccountId,
+        amount: T::Balance,
+        status: BalanceStatus,
+    ) -> Result<T::Balance, &'static str> {
+        self.do_repatriate_reserved(Some(id), slashed, beneficiary, amount, status)
+    }
+
+    /// Obtém o saldo reservado de `who`: a reserva sem nome se `id` for `None`, ou a reserva
+    /// nomeada `id` caso contrário.
+    fn reserved_of(&self, id: Option<&ReserveIdentifier>, who: &T::AccountId) -> T::Balance {
+        let amount = match id {
+            None => self.reserved.get(who),
+            Some(id) => self.holds.get(who).and_then(|holds| holds.get(id)),
+        };
+        *amount.unwrap_or(&T::Balance::zero())
+    }
+
+    /// Define o saldo reservado de `who` para a reserva indicada por `id`.
+    /// Entradas zeradas são removidas do armazenamento.
+    fn set_reserved(
+        &mut self,
+        id: Option<&ReserveIdentifier>,
+        who: &T::AccountId,
+        amount: T::Balance,
+    ) {
+        match id {
+            None if amount.is_zero() => {
+                self.reserved.remove(who);
+            }
+            None => {
+                self.reserved.insert(who.clone(), amount);
+            }
+            Some(id) => {
+                let holds = self.holds.entry(who.clone()).or_default();
+                if amount.is_zero() {
+                    holds.remove(id);
+                } else {
+                    holds.insert(*id, amount);
+                }
+                if holds.is_empty() {
+                    self.holds.remove(who);
+                }
+            }
+        }
+    }
+
+    fn do_reserve(
+        &mut self,
+        id: Option<&ReserveIdentifier>,
+        who: &T::AccountId,
+        amount: T::Balance,
+    ) -> crate::support::DispatchResult {
+        let new_free = self
+            .balance(who)
+            .checked_sub(&amount)
+            .ok_or("Insufficient balance")?;
+        let new_reserved = self
+            .reserved_of(id, who)
+            .checked_add(&amount)
+            .ok_or("Overflow")?;
+        self.ensure_can_withdraw(who, WithdrawReasons::Transfer, new_free)?;
+
+        self.balances.insert(who.clone(), new_free);
+        self.set_reserved(id, who, new_reserved);
+
+        Ok(())
+    }
+
+    fn do_unreserve(
+        &mut self,
+        id: Option<&ReserveIdentifier>,
+        who: &T::AccountId,
+        amount: T::Balance,
+    ) -> T::Balance {
+        let reserved = self.reserved_of(id, who);
+        let free = self.balance(who);
+        let actual = min(amount, reserved);
+        // O saldo livre só pode transbordar se algo muito errado aconteceu; nesse caso nada é
+        // liberado.
+        let Some(new_free) = free.checked_add(&actual) else {
+            return amount;
+        };
+
+        self.balances.insert(who.clone(), new_free);
+        self.set_reserved(id, who, reserved.checked_sub(&actual).unwrap_or(reserved));
+
+        amount.checked_sub(&actual).unwrap_or(amount)
+    }
+
+    fn do_slash_reserved(
+        &mut self,
+        id: Option<&ReserveIdentifier>,
+        who: &T::AccountId,
+        amount: T::Balance,
+    ) -> T::Balance {
+        let reserved = self.reserved_of(id, who);
+        let actual = min(amount, reserved);
+
+        self.set_reserved(id, who, reserved.checked_sub(&actual).unwrap_or(reserved));
+        self.total_issuance = self
+            .total_issuance
+            .checked_sub(&actual)
+            .unwrap_or(T::Balance::zero());
+
+        amount.checked_sub(&actual).unwrap_or(amount)
+    }
+
+    fn do_repatriate_reserved(
+        &mut self,
+        id: Option<&ReserveIdentifier>,
+        slashed: &T::AccountId,
+        beneficiary: &T::AccountId,
+        amount: T::Balance,
+        status: BalanceStatus,
+    ) -> Result<T::Balance, &'static str> {
+        if slashed == beneficiary {
+            return Ok(match status {
+                BalanceStatus::Free => self.do_unreserve(id, slashed, amount),
+                BalanceStatus::Reserved => {
+                    let actual = min(amount, self.reserved_of(id, slashed));
+                    amount.checked_sub(&actual).unwrap_or(amount)
+                }
+            });
+        }
+
+        let reserved = self.reserved_of(id, slashed);
+        let actual = min(amount, reserved);
+
+        match status {
+            BalanceStatus::Free => {
+                let new_free = self
+                    .balance(beneficiary)
+                    .checked_add(&actual)
+                    .ok_or("Overflow")?;
+                self.balances.insert(beneficiary.clone(), new_free);
+            }
+            BalanceStatus::Reserved => {
+                let new_reserved = self
+                    .reserved_of(id, beneficiary)
+                    .checked_add(&actual)
+                    .ok_or("Overflow")?;
+                self.set_reserved(id, beneficiary, new_reserved);
+            }
+        }
+        self.set_reserved(
+            id,
+            slashed,
+            reserved.checked_sub(&actual).unwrap_or(reserved),
+        );
+
+        Ok(amount.checked_sub(&actual).unwrap_or(amount))
+    }
+}
+
+impl<T: Config + GetPallet<Self>> Hooks<T> for Pallet<T> {
+    fn on_initialize(runtime: &mut T, _n: T::BlockNumber) {
+        let pallet: &mut Self = runtime.pallet_mut();
+        pallet.events.clear();
+    }
+}
+
+impl<T: Config> Storage for Pallet<T> {
+    fn storage(&self) -> Vec<(Vec<u8>, Vec<u8>)> {
+        let mut storage = vec![storage_value("total_issuance", &self.total_issuance)];
+        storage.extend(storage_map("balances", &self.balances));
+        storage.extend(storage_map("reserved", &self.reserved));
+        storage.extend(storage_map("holds", &self.holds));
+        storage.extend(storage_map("locks", &self.locks));
+        storage.extend(storage_map("allowances", &self.allowances));
+        storage
+    }
+}
+
+impl<T: Config + GetPallet<Pallet<T>>> Currency<T> for Pallet<T> {
+    type Balance = T::Balance;
+
+    fn total_issuance(runtime: &T) -> T::Balance {
+        runtime.pallet().total_issuance()
+    }
+
+    fn total_balance(runtime: &T, who: &T::AccountId) -> T::Balance {
+        runtime.pallet().total_balance(who)
+    }
+
+    fn free_balance(runtime: &T, who: &T::AccountId) -> T::Balance {
+        runtime.pallet().balance(who)
+    }
+
+    fn transfer(
+        runtime: &mut T,
+        from: &T::AccountId,
+        to: &T::AccountId,
+        amount: T::Balance,
+        existence: ExistenceRequirement,
+    ) -> crate::support::DispatchResult {
+        runtime
+            .pallet_mut()
+            .do_transfer(from, to, amount, existence)
+    }
+
+    fn withdraw(
+        runtime: &mut T,
+        who: &T::AccountId,
+        amount: T::Balance,
+        reason: WithdrawReasons,
+        existence: ExistenceRequirement,
+    ) -> Result<NegativeImbalance<T::Balance>, &'static str> {
+        runtime
+            .pallet_mut()
+            .withdraw(who, amount, reason, existence)?;
+        Ok(NegativeImbalance::new(amount))
+    }
+
+    fn deposit_creating(
+        runtime: &mut T,
+        who: &T::AccountId,
+        amount: T::Balance,
+    ) -> PositiveImbalance<T::Balance> {
+        match runtime.pallet_mut().deposit(who, amount) {
+            Ok(()) => PositiveImbalance::new(amount),
+            Err(_) => PositiveImbalance::new(T::Balance::zero()),
+        }
+    }
+}
+
+impl<T: Config + GetPallet<Pallet<T>>> ReservableCurrency<T> for Pallet<T> {
+    fn can_reserve(runtime: &T, who: &T::AccountId, amount: T::Balance) -> bool {
+        let pallet = runtime.pallet();
+        match pallet.balance(who).checked_sub(&amount) {
+            Some(new_balance) => pallet
+                .ensure_can_withdraw(who, WithdrawReasons::Transfer, new_balance)
+                .is_ok(),
+            None => false,
+        }
+    }
+
+    fn reserved_balance(runtime: &T, who: &T::AccountId) -> T::Balance {
+        runtime.pallet().reserved_balance(who)
+    }
+
+    fn reserve(
+        runtime: &mut T,
+        who: &T::AccountId,
+        amount: T::Balance,
+    ) -> crate::support::DispatchResult {
+        runtime.pallet_mut().reserve(who, amount)
+    }
+
+    fn unreserve(runtime: &mut T, who: &T::AccountId, amount: T::Balance) -> T::Balance {
+        runtime.pallet_mut().unreserve(who, amount)
+    }
+
+    fn slash_reserved(
+        runtime: &mut T,
+        who: &T::AccountId,
+        amount: T::Balance,
+    ) -> (NegativeImbalance<T::Balance>, T::Balance) {
+        let remaining = runtime.pallet_mut().slash_reserved(who, amount);
+        let slashed = amount.checked_sub(&remaining).unwrap_or(T::Balance::zero());
+        (NegativeImbalance::new(slashed), remaining)
+    }
+
+    fn repatriate_reserved(
+        runtime: &mut T,
+        slashed: &T::AccountId,
+        beneficiary: &T::AccountId,
+        amount: T::Balance,
+        status: BalanceStatus,
+    ) -> Result<T::Balance, &'static str> {
+        runtime
+            .pallet_mut()
+            .repatriate_reserved(slashed, beneficiary, amount, status)
+    }
+}
+
+impl<T: Config + GetPallet<Pallet<T>>> NamedReservableCurrency<T> for Pallet<T> {
+    fn reserved_balance_named(
+        runtime: &T,
+        id: &ReserveIdentifier,
+        who: &T::AccountId,
+    ) -> T::Balance {
+        runtime.pallet().reserved_balance_named(id, who)
+    }
+
+    fn reserve_named(
+        runtime: &mut T,
+        id: &ReserveIdentifier,
+        who: &T::AccountId,
+        amount: T::Balance,
+    ) -> crate::support::DispatchResult {
+        runtime.pallet_mut().reserve_named(id, who, amount)
+    }
+
+    fn unreserve_named(
+        runtime: &mut T,
+        id: &ReserveIdentifier,
+        who: &T::AccountId,
+        amount: T::Balance,
+    ) -> T::Balance {
+        runtime.pallet_mut().unreserve_named(id, who, amount)
+    }
+
+    fn slash_reserved_named(
+        runtime: &mut T,
+        id: &ReserveIdentifier,
+        who: &T::AccountId,
+        amount: T::Balance,
+    ) -> (NegativeImbalance<T::Balance>, T::Balance) {
+        let remaining = runtime.pallet_mut().slash_reserved_named(id, who, amount);
+        let slashed = amount.checked_sub(&remaining).unwrap_or(T::Balance::zero());
+        (NegativeImbalance::new(slashed), remaining)
+    }
+
+    fn repatriate_reserved_named(
+        runtime: &mut T,
+        id: &ReserveIdentifier,
+        slashed: &T::AccountId,
+        beneficiary: &T::AccountId,
+        amount: T::Balance,
+        status: BalanceStatus,
+    ) -> Result<T::Balance, &'static str> {
+        runtime
+            .pallet_mut()
+            .repatriate_reserved_named(id, slashed, beneficiary, amount, status)
+    }
+}
+
+#[cfg(test)]
+impl<T: Config + GetPallet<Pallet<T>>> crate::support::LockableCurrency<T> for Pallet<T> {
+    fn set_lock(
+        runtime: &mut T,
+        id: LockIdentifier,
+        who: &T::AccountId,
+        amount: T::Balance,
+        reasons: WithdrawReasons,
+    ) {
+        runtime.pallet_mut().set_lock(id, who, amount, reasons)
+    }
+
+    fn extend_lock(
+        runtime: &mut T,
+        id: LockIdentifier,
+        who: &T::AccountId,
+        amount: T::Balance,
+        reasons: WithdrawReasons,
+    ) {
+        runtime.pallet_mut().extend_lock(id, who, amount, reasons)
+    }
+
+    fn remove_lock(runtime: &mut T, id: LockIdentifier, who: &T::AccountId) {
+        runtime.pallet_mut().remove_lock(id, who)
+    }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::support::LockableCurrency;
     use crate::system;
 
+    #[derive(Debug, PartialEq, Eq)]
     struct TestConfig;
 
     impl Config for TestConfig {
         type Balance = u128;
+        const EXISTENTIAL_DEPOSIT: u128 = 5;
     }
 
     impl system::Config for TestConfig {
         type AccountId = String;
         type BlockNumber = u32;
         type Nonce = u32;
+        type Hash = crate::hashing::H256;
+        type Hashing = crate::hashing::Sha256;
+    }
+
+    /// Um runtime mínimo, que contém apenas este pallet, para usá-lo através das traits de moeda.
+    struct TestRuntime {
+        balances: Pallet<TestRuntime>,
+    }
+
+    impl Config for TestRuntime {
+        type Balance = u128;
+        const EXISTENTIAL_DEPOSIT: u128 = 5;
+    }
+
+    impl system::Config for TestRuntime {
+        type AccountId = String;
+        type BlockNumber = u32;
+        type Nonce = u32;
+        type Hash = crate::hashing::H256;
+        type Hashing = crate::hashing::Sha256;
+    }
+
+    impl GetPallet<Pallet<TestRuntime>> for TestRuntime {
+        fn pallet(&self) -> &Pallet<TestRuntime> {
+            &self.balances
+        }
+
+        fn pallet_mut(&mut self) -> &mut Pallet<TestRuntime> {
+            &mut self.balances
+        }
     }
 
     #[test]
@@ -111,4 +1034,405 @@ mod tests {
         assert_eq!(balances.balance(&"dev0".to_string()), 70); // 100 - 30
         assert_eq!(balances.balance(&"dev1".to_string()), 80); // 50 + 30
     }
+
+    #[test]
+    fn transfer_below_existential_deposit() {
+        let mut balances = Pallet::<TestConfig>::new();
+        let dev0 = "dev0".to_string();
+        let dev1 = "dev1".to_string();
+        balances.set_balance(&dev0, 100);
+
+        // Com o depósito existencial 5, uma conta nova precisa receber pelo menos 5
+        assert_eq!(
+            balances.transfer(dev0.clone(), dev1.clone(), 3),
+            Err("Transfer would leave the recipient below the existential deposit")
+        );
+        assert_eq!(balances.balance(&dev0), 100);
+        assert_eq!(balances.transfer(dev0.clone(), dev1.clone(), 5), Ok(()));
+
+        // Uma conta que já existe pode receber qualquer quantia
+        assert_eq!(balances.transfer(dev0.clone(), dev1.clone(), 1), Ok(()));
+        assert_eq!(balances.balance(&dev1), 6);
+        assert_eq!(balances.total_issuance(), 100);
+    }
+
+    #[test]
+    fn force_set_balance() {
+        let mut balances = Pallet::<TestConfig>::new();
+        let dev0 = "dev0".to_string();
+        let dev1 = "dev1".to_string();
+
+        balances.set_balance(&dev0, 100);
+
+        // Apenas a origem privilegiada pode definir saldos, e a emissão total acompanha
+        let res = balances.force_set_balance(Origin::Signed(dev0.clone()), dev1.clone(), 50);
+        assert_eq!(res, Err("Bad origin: expected root."));
+        let res = balances.force_set_balance(Origin::Root, dev1.clone(), 50);
+        assert_eq!(res, Ok(()));
+        assert_eq!(balances.total_issuance(), 150);
+
+        // Um saldo que faria a emissão total passar do limite é rejeitado sem alterar nada
+        let res = balances.force_set_balance(Origin::Root, dev1.clone(), u128::MAX);
+        assert_eq!(res, Err("Overflow"));
+        assert_eq!(balances.balance(&dev1), 50);
+        assert_eq!(balances.total_issuance(), 150);
+    }
+
+    #[test]
+    fn reserve_and_unreserve() {
+        let mut balances = Pallet::<TestConfig>::new();
+        let dev0 = "dev0".to_string();
+
+        balances.set_balance(&dev0, 100);
+
+        // Não é possível reservar mais do que o saldo livre
+        assert_eq!(balances.reserve(&dev0, 150), Err("Insufficient balance"));
+
+        assert_eq!(balances.reserve(&dev0, 60), Ok(()));
+        assert_eq!(balances.balance(&dev0), 40);
+        assert_eq!(balances.reserved_balance(&dev0), 60);
+
+        // O saldo reservado não pode ser transferido
+        assert_eq!(
+            balances.transfer(dev0.clone(), "dev1".to_string(), 50),
+            Err("Insufficient balance")
+        );
+
+        // Liberar mais do que está reservado devolve o que faltou
+        assert_eq!(balances.unreserve(&dev0, 80), 20);
+        assert_eq!(balances.balance(&dev0), 100);
+        assert_eq!(balances.reserved_balance(&dev0), 0);
+    }
+
+    #[test]
+    fn slash_and_repatriate_reserved() {
+        let mut balances = Pallet::<TestConfig>::new();
+        let dev0 = "dev0".to_string();
+        let dev1 = "dev1".to_string();
+
+        balances.set_balance(&dev0, 100);
+        assert_eq!(balances.reserve(&dev0, 90), Ok(()));
+
+        // O corte destrói os fundos reservados
+        assert_eq!(balances.slash_reserved(&dev0, 30), 0);
+        assert_eq!(balances.reserved_balance(&dev0), 60);
+        assert_eq!(balances.balance(&dev0), 10);
+
+        // Repatria parte para o saldo livre e parte para o saldo reservado de dev1
+        assert_eq!(
+            balances.repatriate_reserved(&dev0, &dev1, 20, BalanceStatus::Free),
+            Ok(0)
+        );
+        assert_eq!(
+            balances.repatriate_reserved(&dev0, &dev1, 50, BalanceStatus::Reserved),
+            Ok(10)
+        );
+        assert_eq!(balances.reserved_balance(&dev0), 0);
+        assert_eq!(balances.balance(&dev1), 20);
+        assert_eq!(balances.reserved_balance(&dev1), 40);
+    }
+
+    #[test]
+    fn named_reserves() {
+        let mut balances = Pallet::<TestConfig>::new();
+        let dev0 = "dev0".to_string();
+        let dev1 = "dev1".to_string();
+        const POE: ReserveIdentifier = *b"poe/dep_";
+        const ESCROW: ReserveIdentifier = *b"escrow__";
+
+        balances.set_balance(&dev0, 100);
+        assert_eq!(balances.reserve_named(&POE, &dev0, 30), Ok(()));
+        assert_eq!(balances.reserve_named(&ESCROW, &dev0, 20), Ok(()));
+        assert_eq!(balances.reserve(&dev0, 10), Ok(()));
+
+        assert_eq!(balances.reserved_balance_named(&POE, &dev0), 30);
+        assert_eq!(balances.reserved_balance_named(&ESCROW, &dev0), 20);
+        assert_eq!(balances.reserved_balance(&dev0), 60);
+
+        // Cada reserva nomeada só libera os seus próprios fundos
+        assert_eq!(balances.unreserve_named(&POE, &dev0, 50), 20);
+        assert_eq!(balances.reserved_balance_named(&POE, &dev0), 0);
+        assert_eq!(balances.reserved_balance_named(&ESCROW, &dev0), 20);
+        assert_eq!(balances.balance(&dev0), 70);
+
+        assert_eq!(balances.slash_reserved_named(&ESCROW, &dev0, 5), 0);
+        assert_eq!(
+            balances.repatriate_reserved_named(&ESCROW, &dev0, &dev1, 15, BalanceStatus::Reserved),
+            Ok(0)
+        );
+        assert_eq!(balances.reserved_balance_named(&ESCROW, &dev1), 15);
+        assert_eq!(balances.reserved_balance(&dev0), 10);
+    }
+
+    #[test]
+    fn locks_restrict_spending() {
+        let mut balances = Pallet::<TestConfig>::new();
+        let dev0 = "dev0".to_string();
+        let dev1 = "dev1".to_string();
+        const VESTING: LockIdentifier = *b"vesting_";
+        const STAKING: LockIdentifier = *b"staking_";
+
+        balances.set_balance(&dev0, 100);
+        balances.set_lock(VESTING, &dev0, 50, WithdrawReasons::Transfer);
+        balances.set_lock(STAKING, &dev0, 30, WithdrawReasons::All);
+
+        // Apenas o maior bloqueio conta
+        assert_eq!(balances.spendable_balance(&dev0), 50);
+        assert_eq!(balances.locked_balance(&dev0, WithdrawReasons::Fees), 30);
+
+        assert_eq!(
+            balances.transfer(dev0.clone(), dev1.clone(), 60),
+            Err("Balance is locked")
+        );
+        assert_eq!(balances.reserve(&dev0, 60), Err("Balance is locked"));
+        assert_eq!(balances.transfer(dev0.clone(), dev1.clone(), 50), Ok(()));
+        assert_eq!(balances.spendable_balance(&dev0), 0);
+
+        // Estender um bloqueio mantém a maior quantia e combina os motivos
+        balances.extend_lock(VESTING, &dev0, 20, WithdrawReasons::Fees);
+        assert_eq!(
+            balances.lock(&VESTING, &dev0),
+            Some(&BalanceLock {
+                amount: 50,
+                reasons: WithdrawReasons::All
+            })
+        );
+
+        balances.remove_lock(VESTING, &dev0);
+        assert_eq!(balances.spendable_balance(&dev0), 20);
+        balances.set_lock(STAKING, &dev0, 0, WithdrawReasons::All);
+        assert_eq!(balances.spendable_balance(&dev0), 50);
+    }
+
+    #[test]
+    fn approve_and_transfer_from() {
+        let mut balances = Pallet::<TestConfig>::new();
+        let dev0 = "dev0".to_string();
+        let dev1 = "dev1".to_string();
+        let azuki = "azuki".to_string();
+
+        balances.set_balance(&dev0, 100);
+
+        // Sem autorização, azuki não pode gastar em nome de dev0
+        assert_eq!(
+            balances.transfer_from(azuki.clone(), dev0.clone(), dev1.clone(), 10),
+            Err("Insufficient allowance")
+        );
+
+        assert_eq!(balances.approve(dev0.clone(), azuki.clone(), 30), Ok(()));
+        assert_eq!(
+            balances.increase_allowance(dev0.clone(), azuki.clone(), 20),
+            Ok(())
+        );
+        assert_eq!(
+            balances.decrease_allowance(dev0.clone(), azuki.clone(), 10),
+            Ok(())
+        );
+        assert_eq!(
+            balances.decrease_allowance(dev0.clone(), azuki.clone(), 50),
+            Err("Decreased allowance below zero")
+        );
+        assert_eq!(balances.allowance(&dev0, &azuki), 40);
+
+        assert_eq!(
+            balances.transfer_from(azuki.clone(), dev0.clone(), dev1.clone(), 25),
+            Ok(())
+        );
+        assert_eq!(balances.allowance(&dev0, &azuki), 15);
+        assert_eq!(balances.balance(&dev0), 75);
+        assert_eq!(balances.balance(&dev1), 25);
+        assert_eq!(
+            balances.transfer_from(azuki.clone(), dev0.clone(), dev1.clone(), 20),
+            Err("Insufficient allowance")
+        );
+
+        assert_eq!(
+            balances.events().first(),
+            Some(&Event::Approval {
+                owner: dev0.clone(),
+                spender: azuki.clone(),
+                amount: 30
+            })
+        );
+        assert_eq!(
+            balances.events().last(),
+            Some(&Event::Transfer {
+                from: dev0,
+                to: dev1,
+                amount: 25
+            })
+        );
+    }
+
+    #[test]
+    fn events_cleared_each_block() {
+        let mut runtime = TestRuntime {
+            balances: Pallet::new(),
+        };
+        let dev0 = "dev0".to_string();
+        runtime.balances.set_balance(&dev0, 100);
+        let res = runtime
+            .balances
+            .approve(dev0.clone(), "azuki".to_string(), 30);
+        assert_eq!(res, Ok(()));
+        assert_eq!(runtime.balances.events().len(), 1);
+
+        // O início do bloco descarta os eventos do bloco anterior
+        Pallet::on_initialize(&mut runtime, 1);
+        assert!(runtime.balances.events().is_empty());
+    }
+
+    #[test]
+    fn transfer_keep_alive_and_transfer_all() {
+        let mut balances = Pallet::<TestConfig>::new();
+        let dev0 = "dev0".to_string();
+        let dev1 = "dev1".to_string();
+        let azuki = "azuki".to_string();
+        const VESTING: LockIdentifier = *b"vesting_";
+
+        balances.set_balance(&dev0, 100);
+
+        // dev0 ficaria com menos do que o depósito existencial
+        assert_eq!(
+            balances.transfer_keep_alive(dev0.clone(), dev1.clone(), 98),
+            Err("Transfer would kill account")
+        );
+        assert_eq!(
+            balances.transfer_keep_alive(dev0.clone(), dev1.clone(), 40),
+            Ok(())
+        );
+
+        // Reservas e bloqueios ficam na conta
+        assert_eq!(balances.reserve(&dev0, 10), Ok(()));
+        balances.set_lock(VESTING, &dev0, 20, WithdrawReasons::Transfer);
+        assert_eq!(
+            balances.transfer_all(dev0.clone(), azuki.clone(), true),
+            Ok(())
+        );
+        assert_eq!(balances.balance(&dev0), 20);
+        assert_eq!(balances.balance(&azuki), 30);
+
+        balances.remove_lock(VESTING, &dev0);
+        assert_eq!(
+            balances.transfer_all(dev0.clone(), azuki.clone(), true),
+            Ok(())
+        );
+        assert_eq!(balances.balance(&dev0), 5);
+        assert_eq!(
+            balances.transfer_all(dev0.clone(), azuki.clone(), false),
+            Ok(())
+        );
+        assert_eq!(balances.balance(&dev0), 0);
+        assert_eq!(balances.balance(&azuki), 50);
+        assert_eq!(balances.reserved_balance(&dev0), 10);
+
+        // Sem nada para transferir, nenhuma transferência é feita nem emitida
+        let events = balances.events().len();
+        assert_eq!(
+            balances.transfer_all(dev0.clone(), azuki.clone(), false),
+            Ok(())
+        );
+        assert_eq!(balances.events().len(), events);
+
+        // Sem reservas nem bloqueios, a poeira abaixo do depósito existencial é queimada
+        let issuance = balances.total_issuance();
+        assert_eq!(balances.transfer(dev1.clone(), azuki.clone(), 37), Ok(()));
+        assert_eq!(balances.balance(&dev1), 0);
+        assert_eq!(balances.total_issuance(), issuance - 3);
+        assert_eq!(
+            balances.events().last(),
+            Some(&Event::DustLost {
+                account: dev1.clone(),
+                amount: 3,
+            })
+        );
+    }
+
+    #[test]
+    fn currency_traits() {
+        // Outros pallets só enxergam o pallet de saldos através das traits de moeda.
+        type Balances = Pallet<TestRuntime>;
+        let mut runtime = TestRuntime {
+            balances: Pallet::new(),
+        };
+        let dev0 = "dev0".to_string();
+        let dev1 = "dev1".to_string();
+        const POE: ReserveIdentifier = *b"poe/dep_";
+
+        let minted = <Balances as Currency<_>>::deposit_creating(&mut runtime, &dev0, 100);
+        assert_eq!(minted.peek(), 100);
+        assert_eq!(<Balances as Currency<_>>::total_issuance(&runtime), 100);
+
+        assert_eq!(
+            <Balances as Currency<_>>::transfer(
+                &mut runtime,
+                &dev0,
+                &dev1,
+                96,
+                ExistenceRequirement::KeepAlive
+            ),
+            Err("Transfer would kill account")
+        );
+        assert!(<Balances as ReservableCurrency<_>>::can_reserve(
+            &runtime, &dev0, 30
+        ));
+        assert_eq!(
+            <Balances as NamedReservableCurrency<_>>::reserve_named(&mut runtime, &POE, &dev0, 30),
+            Ok(())
+        );
+        assert_eq!(<Balances as Currency<_>>::free_balance(&runtime, &dev0), 70);
+        assert_eq!(
+            <Balances as Currency<_>>::total_balance(&runtime, &dev0),
+            100
+        );
+
+        // Retirar e cortar fundos reduz o total emitido
+        let fee = <Balances as Currency<_>>::withdraw(
+            &mut runtime,
+            &dev0,
+            20,
+            WithdrawReasons::Fees,
+            ExistenceRequirement::KeepAlive,
+        )
+        .unwrap();
+        let (slashed, remaining) = <Balances as NamedReservableCurrency<_>>::slash_reserved_named(
+            &mut runtime,
+            &POE,
+            &dev0,
+            50,
+        );
+        assert_eq!((slashed.peek(), remaining), (30, 20));
+        assert_eq!(<Balances as Currency<_>>::total_issuance(&runtime), 50);
+
+        // Os fundos retirados podem ser depositados em outra conta
+        let res =
+            <Balances as Currency<_>>::resolve_creating(&mut runtime, &dev1, fee.merge(slashed));
+        assert_eq!(res, Ok(()));
+        assert_eq!(<Balances as Currency<_>>::free_balance(&runtime, &dev1), 50);
+        assert_eq!(<Balances as Currency<_>>::total_issuance(&runtime), 100);
+
+        // Se o depósito não for possível, os fundos são devolvidos em vez de queimados
+        let fee = <Balances as Currency<_>>::withdraw(
+            &mut runtime,
+            &dev0,
+            10,
+            WithdrawReasons::Fees,
+            ExistenceRequirement::KeepAlive,
+        )
+        .unwrap();
+        runtime.balances.total_issuance = u128::MAX;
+        let res = <Balances as Currency<_>>::resolve_creating(&mut runtime, &dev1, fee);
+        assert_eq!(res, Err(NegativeImbalance::new(10)));
+        assert_eq!(<Balances as Currency<_>>::free_balance(&runtime, &dev1), 50);
+
+        // Os bloqueios também são feitos através da trait
+        const VESTING: LockIdentifier = *b"vesting_";
+        let reasons = WithdrawReasons::Transfer;
+        <Balances as LockableCurrency<_>>::set_lock(&mut runtime, VESTING, &dev1, 40, reasons);
+        let reasons = WithdrawReasons::Fees;
+        <Balances as LockableCurrency<_>>::extend_lock(&mut runtime, VESTING, &dev1, 45, reasons);
+        assert_eq!(runtime.balances.spendable_balance(&dev1), 5);
+        <Balances as LockableCurrency<_>>::remove_lock(&mut runtime, VESTING, &dev1);
+        assert_eq!(runtime.balances.spendable_balance(&dev1), 50);
+    }
 }
@@ -0,0 +1,423 @@
+use crate::support::{self, Hasher};
+use crate::trie::ReadProof;
+use crate::{
+    hashing, light_client, merkle, proof_of_existence, timestamp, types, Runtime, RuntimeCall,
+};
+use std::time::{SystemTime, UNIX_EPOCH};
+use std::{fs, io, path::Path};
+
+/// Como usar os comandos de linha de comando.
+pub const USAGE: &str = "uso: rust-state-machine \
+[claim <conta> <arquivo> [descrição] | batch <conta> <arquivo>...]";
+
+/// Calcula o hash do conteúdo de um arquivo local.
+pub fn hash_file(path: &Path) -> io::Result<types::Hash> {
+    Ok(types::Hashing::hash(&fs::read(path)?))
+}
+
+/// Monta o extrínseco que reivindica, em nome de `caller`, o hash `claim` de um arquivo, calculado
+/// com `hash_file`, com uma `description` do conteúdo. Apenas o hash vai para a cadeia; o conteúdo
+/// do arquivo nunca sai da máquina local.
+pub fn create_claim_extrinsic(
+    caller: types::AccountId,
+    claim: types::Hash,
+    description: Vec<u8>,
+) -> types::Extrinsic {
+    support::Extrinsic::Signed {
+        caller,
+        call: RuntimeCall::proof_of_existence(proof_of_existence::Call::create_claim {
+            claim,
+            description,
+            expires_at: None,
+            attestation_threshold: None,
+        }),
+    }
+}
+
+/// Monta o extrínseco que reivindica, em nome de `caller`, todos os documentos de hashes `leaves`
+/// de uma só vez, através da raiz da árvore de Merkle sobre eles.
+/// Retorna `None` se não houver documentos.
+pub fn create_batch_claim_extrinsic(
+    caller: types::AccountId,
+    leaves: &[types::Hash],
+) -> Option<types::Extrinsic> {
+    Some(support::Extrinsic::Signed {
+        caller,
+        call: RuntimeCall::proof_of_existence(proof_of_existence::Call::create_batch_claim {
+            root: merkle::merkle_root::<types::Hashing>(leaves)?,
+            batch_size: leaves.len() as u32,
+            description: Vec::new(),
+            expires_at: None,
+            attestation_threshold: None,
+        }),
+    })
+}
+
+/// Obtém o horário atual da máquina local, em milissegundos desde a época Unix.
+pub fn wall_clock() -> types::Moment {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map_or(0, |elapsed| elapsed.as_millis() as types::Moment)
+}
+
+/// Monta o inerente que define o horário do bloco como `now`, exigido em todo bloco.
+pub fn timestamp_inherent(now: types::Moment) -> types::Extrinsic {
+    support::Extrinsic::Inherent {
+        call: RuntimeCall::timestamp(timestamp::Call::set { now }),
+    }
+}
+
+/// Calcula a chave de armazenamento, no runtime, da reivindicação do conteúdo `claim`.
+pub fn claim_storage_key(claim: &types::Hash) -> Vec<u8> {
+    let key = support::storage_map_key("claims", claim);
+    [b"proof_of_existence::".as_slice(), &key].concat()
+}
+
+/// Obtém o valor armazenado da reivindicação atual do conteúdo `claim`, se houver.
+pub fn claim_storage_value(runtime: &Runtime, claim: &types::Hash) -> Option<Vec<u8>> {
+    let claim = runtime.proof_of_existence.get_claim(claim)?;
+    Some(format!("{:?}", claim).into_bytes())
+}
+
+/// Gera a prova de leitura da reivindicação do conteúdo `claim` no estado depois do bloco
+/// `block_number`. Retorna `None` se o estado do bloco não estiver guardado.
+pub fn prove_claim(
+    runtime: &Runtime,
+    block_number: types::BlockNumber,
+    claim: &types::Hash,
+) -> Option<ReadProof<types::Hash>> {
+    runtime
+        .system
+        .read_proof(block_number, &claim_storage_key(claim))
+}
+
+/// Cria um runtime novo em que `caller` tem saldo suficiente para o depósito de uma reivindicação.
+fn new_runtime_for(caller: &types::AccountId) -> Runtime {
+    let mut runtime = Runtime::new();
+    let deposit = <Runtime as proof_of_existence::Config>::CLAIM_DEPOSIT;
+    runtime.balances.set_balance(caller, deposit);
+    runtime
+}
+
+/// Executa o comando `claim <conta> <arquivo> [descrição]`: inclui o extrínseco que reivindica o
+/// arquivo no primeiro bloco de um novo runtime, com o horário atual, mostra quem é o proprietário
+/// do hash e quando ele o reivindicou, e verifica a prova de leitura da reivindicação contra a
+/// raiz do estado do bloco, como faria um cliente leve. Falha se o arquivo não ficar reivindicado
+/// por `caller`.
+pub fn claim(caller: types::AccountId, path: &Path, description: &str) -> Result<(), String> {
+    let hash = hash_file(path).map_err(|e| format!("{}: {}", path.display(), e))?;
+    let extrinsic = create_claim_extrinsic(caller.clone(), hash, description.as_bytes().to_vec());
+    println!("sha256 {} {}", hashing::to_hex(&hash), path.display());
+
+    let mut runtime = new_runtime_for(&caller);
+    let block = types::Block {
+        header: types::Header {
+            block_number: 1,
+            state_root: None,
+        },
+        extrinsics: vec![timestamp_inherent(wall_clock()), extrinsic],
+    };
+    runtime.execute_block(block)?;
+
+    // O bloco é válido mesmo quando o extrínseco falha, então o comando só tem sucesso se o
+    // conteúdo ficou reivindicado por `caller`.
+    let claim = runtime
+        .proof_of_existence
+        .get_claim(&hash)
+        .ok_or("conteúdo não reivindicado")?;
+    println!(
+        "reivindicado por {} no bloco {}, em {} ms desde a época Unix",
+        claim.owner,
+        claim.created_at,
+        claim.timestamp.unwrap_or_default()
+    );
+    if claim.owner != caller {
+        return Err(format!("conteúdo reivindicado por {}", claim.owner));
+    }
+
+    let header = types::Header {
+        block_number: 1,
+        state_root: runtime.system.state_root(1),
+    };
+    let proof = prove_claim(&runtime, 1, &hash).ok_or("estado do bloco não guardado")?;
+    light_client::verify_storage::<types::Hashing, _>(
+        &header,
+        &claim_storage_key(&hash),
+        claim_storage_value(&runtime, &hash).as_deref(),
+        &proof,
+    )?;
+    println!(
+        "prova de leitura verificada contra a raiz do estado {}",
+        hashing::to_hex(&header.state_root.unwrap_or_default())
+    );
+    Ok(())
+}
+
+/// Executa o comando `batch <conta> <arquivo>...`: reivindica todos os arquivos com uma única
+/// reivindicação em lote no primeiro bloco de um novo runtime, e então verifica a prova de
+/// inclusão de cada arquivo sob a raiz reivindicada. Falha se a raiz não ficar reivindicada.
+pub fn batch_claim(caller: types::AccountId, paths: &[String]) -> Result<(), String> {
+    let mut runtime = new_runtime_for(&caller);
+    batch_claim_in(&mut runtime, caller, paths)
+}
+
+/// Executa o comando `batch` sobre `runtime`, a partir do primeiro bloco.
+fn batch_claim_in(
+    runtime: &mut Runtime,
+    caller: types::AccountId,
+    paths: &[String],
+) -> Result<(), String> {
+    let leaves = paths
+        .iter()
+        .map(|path| hash_file(path.as_ref()).map_err(|e| format!("{}: {}", path, e)))
+        .collect::<Result<Vec<_>, _>>()?;
+    let extrinsic =
+        create_batch_claim_extrinsic(caller.clone(), &leaves).ok_or("nenhum arquivo informado")?;
+    let root = merkle::merkle_root::<types::Hashing>(&leaves).ok_or("nenhum arquivo informado")?;
+    println!(
+        "raiz {} ({} arquivos)",
+        hashing::to_hex(&root),
+        leaves.len()
+    );
+
+    let block = types::Block {
+        header: types::Header {
+            block_number: 1,
+            state_root: None,
+        },
+        extrinsics: vec![timestamp_inherent(wall_clock()), extrinsic],
+    };
+    runtime.execute_block(block)?;
+    if runtime.proof_of_existence.get_claim(&root).is_none() {
+        return Err("lote não reivindicado".to_string());
+    }
+
+    for (i, (path, leaf)) in paths.iter().zip(&leaves).enumerate() {
+        let included = merkle::merkle_proof::<types::Hashing>(&leaves, i).is_some_and(|proof| {
+            runtime
+                .proof_of_existence
+                .verify_batch_inclusion(&root, leaf, &proof)
+        });
+        let status = if included {
+            "incluído"
+        } else {
+            "não incluído"
+        };
+        println!("{} {}", status, path);
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn batch_claim_inclusion() {
+        let caller = "alice".to_string();
+        let leaves: Vec<_> = ["a", "b", "c"]
+            .iter()
+            .map(|doc| types::Hashing::hash(doc.as_bytes()))
+            .collect();
+        let root = merkle::merkle_root::<types::Hashing>(&leaves).unwrap();
+        assert!(create_batch_claim_extrinsic(caller.clone(), &[]).is_none());
+
+        let mut runtime = new_runtime_for(&caller);
+        let extrinsic = create_batch_claim_extrinsic(caller.clone(), &leaves).unwrap();
+        let block = types::Block {
+            header: types::Header {
+                block_number: 1,
+                state_root: None,
+            },
+            extrinsics: vec![timestamp_inherent(1_000), extrinsic],
+        };
+        assert_eq!(runtime.execute_block(block), Ok(()));
+        let claim = runtime.proof_of_existence.get_claim(&root).unwrap();
+        assert_eq!(claim.batch_size, Some(3));
+
+        let poe = &runtime.proof_of_existence;
+        for (i, leaf) in leaves.iter().enumerate() {
+            let proof = merkle::merkle_proof::<types::Hashing>(&leaves, i).unwrap();
+            assert!(poe.verify_batch_inclusion(&root, leaf, &proof));
+        }
+
+        // Um documento de fora do lote, ou uma raiz que não foi reivindicada, não são aceitos
+        let proof = merkle::merkle_proof::<types::Hashing>(&leaves, 0).unwrap();
+        let other = types::Hashing::hash(b"d");
+        assert!(!poe.verify_batch_inclusion(&root, &other, &proof));
+        let other_root = merkle::merkle_root::<types::Hashing>(&leaves[..2]).unwrap();
+        let proof = merkle::merkle_proof::<types::Hashing>(&leaves[..2], 0).unwrap();
+        assert!(!poe.verify_batch_inclusion(&other_root, &leaves[0], &proof));
+
+        // Nem um documento numa posição além do número de documentos informado para o lote
+        let mut leaves = leaves;
+        leaves.push(types::Hashing::hash(b"d"));
+        let root = merkle::merkle_root::<types::Hashing>(&leaves).unwrap();
+        runtime.balances.set_balance(&caller, 100);
+        let res = proof_of_existence::Pallet::create_batch_claim(
+            &mut runtime,
+            caller,
+            root,
+            3,
+            Vec::new(),
+            None,
+            None,
+        );
+        assert_eq!(res, Ok(()));
+        let proof = merkle::merkle_proof::<types::Hashing>(&leaves, 3).unwrap();
+        assert!(merkle::verify_proof::<types::Hashing>(
+            &root, &leaves[3], &proof
+        ));
+        let poe = &runtime.proof_of_existence;
+        assert!(!poe.verify_batch_inclusion(&root, &leaves[3], &proof));
+    }
+
+    #[test]
+    fn state_root_in_header() {
+        let caller = "alice".to_string();
+        let leaves = [types::Hashing::hash(b"a")];
+
+        // Executa o mesmo bloco em dois runtimes: a raiz do estado é a mesma
+        let block = |state_root| types::Block {
+            header: types::Header {
+                block_number: 1,
+                state_root,
+            },
+            extrinsics: vec![
+                timestamp_inherent(1_000),
+                create_batch_claim_extrinsic(caller.clone(), &leaves).unwrap(),
+            ],
+        };
+        let mut runtime = new_runtime_for(&caller);
+        assert_eq!(runtime.execute_block(block(None)), Ok(()));
+        let root = runtime.system.state_root(1).unwrap();
+        let mut other = new_runtime_for(&caller);
+        assert_eq!(other.execute_block(block(Some(root))), Ok(()));
+
+        // Um estado diferente tem outra raiz, e o bloco é rejeitado
+        let mut diverged = new_runtime_for(&caller);
+        diverged.balances.set_balance(&"bob".to_string(), 1);
+        let res = diverged.execute_block(block(Some(root)));
+        assert_eq!(res, Err("state root does not match the header"));
+
+        // E desfeito por inteiro: nenhuma raiz é registrada e o mesmo bloco pode ser executado de
+        // novo
+        assert_eq!(diverged.system.state_root(1), None);
+        assert_eq!(diverged.system.block_number(), 0);
+        assert_eq!(diverged.proof_of_existence.claim_count(&caller), 0);
+        assert_eq!(diverged.execute_block(block(None)), Ok(()));
+        assert_ne!(diverged.system.state_root(1), Some(root));
+    }
+
+    #[test]
+    fn claim_read_proof() {
+        let caller = "alice".to_string();
+        let leaves = [types::Hashing::hash(b"a")];
+        let root = merkle::merkle_root::<types::Hashing>(&leaves).unwrap();
+        let other = types::Hashing::hash(b"b");
+
+        let mut runtime = new_runtime_for(&caller);
+        let block = types::Block {
+            header: types::Header {
+                block_number: 1,
+                state_root: None,
+            },
+            extrinsics: vec![
+                timestamp_inherent(1_000),
+                create_batch_claim_extrinsic(caller.clone(), &leaves).unwrap(),
+            ],
+        };
+        assert_eq!(runtime.execute_block(block), Ok(()));
+        let header = types::Header {
+            block_number: 1,
+            state_root: runtime.system.state_root(1),
+        };
+        let verify = |key: &[u8], value: Option<&[u8]>, proof: &ReadProof<types::Hash>| {
+            light_client::verify_storage::<types::Hashing, _>(&header, key, value, proof)
+        };
+
+        // O cliente leve aceita o valor da reivindicação, e não aceita outro proprietário
+        let proof = prove_claim(&runtime, 1, &root).unwrap();
+        let value = claim_storage_value(&runtime, &root).unwrap();
+        let key = claim_storage_key(&root);
+        assert_eq!(verify(&key, Some(&value), &proof), Ok(()));
+        let forged = String::from_utf8(value.clone())
+            .unwrap()
+            .replace("alice", "bob");
+        let res = verify(&key, Some(forged.as_bytes()), &proof);
+        assert_eq!(res, Err("invalid storage proof"));
+
+        // Também é possível provar que um conteúdo não foi reivindicado
+        let proof = prove_claim(&runtime, 1, &other).unwrap();
+        assert_eq!(claim_storage_value(&runtime, &other), None);
+        assert_eq!(verify(&claim_storage_key(&other), None, &proof), Ok(()));
+        assert_eq!(verify(&key, None, &proof), Err("invalid storage proof"));
+
+        // A prova continua válida contra o bloco 1 depois de outros blocos
+        let block = types::Block {
+            header: types::Header {
+                block_number: 2,
+                state_root: None,
+            },
+            extrinsics: vec![
+                timestamp_inherent(7_000),
+                support::Extrinsic::Signed {
+                    caller: caller.clone(),
+                    call: RuntimeCall::proof_of_existence(proof_of_existence::Call::revoke_claim {
+                        claim: root,
+                    }),
+                },
+            ],
+        };
+        assert_eq!(runtime.execute_block(block), Ok(()));
+        assert_eq!(claim_storage_value(&runtime, &root), None);
+        let old_proof = prove_claim(&runtime, 1, &root).unwrap();
+        assert_eq!(verify(&key, Some(&value), &old_proof), Ok(()));
+        let new_proof = prove_claim(&runtime, 2, &root).unwrap();
+        assert_eq!(
+            verify(&key, Some(&value), &new_proof),
+            Err("invalid storage proof")
+        );
+        assert!(prove_claim(&runtime, 3, &root).is_none());
+
+        // Sem raiz do estado no cabeçalho, não há o que verificar
+        let header = types::Header {
+            block_number: 1,
+            state_root: None,
+        };
+        let res = light_client::verify_storage::<types::Hashing, _>(&header, &key, None, &proof);
+        assert_eq!(res, Err("header has no state root"));
+    }
+
+    #[test]
+    fn claim_fails_when_not_claimed() {
+        let path = std::env::temp_dir().join("rust-state-machine-claim-not-claimed.txt");
+        fs::write(&path, b"conteudo").unwrap();
+
+        // Com uma descrição longa demais, o bloco é válido, mas o conteúdo não é reivindicado
+        let description = "a".repeat(129);
+        let res = claim("alice".to_string(), &path, &description);
+        assert_eq!(res, Err("conteúdo não reivindicado".to_string()));
+        assert_eq!(claim("alice".to_string(), &path, "contrato"), Ok(()));
+        fs::remove_file(path).unwrap();
+    }
+
+    #[test]
+    fn batch_claim_fails_when_not_claimed() {
+        let dir = std::env::temp_dir();
+        let paths = ["a", "b"].map(|name| {
+            let path = dir.join(format!("rust-state-machine-batch-{}.txt", name));
+            fs::write(&path, name).unwrap();
+            path.to_string_lossy().into_owned()
+        });
+
+        // Sem saldo para o depósito, o bloco é válido, mas a raiz não é reivindicada
+        let mut runtime = Runtime::new();
+        let res = batch_claim_in(&mut runtime, "alice".to_string(), &paths);
+        assert_eq!(res, Err("lote não reivindicado".to_string()));
+        assert_eq!(batch_claim("alice".to_string(), &paths), Ok(()));
+        for path in paths {
+            fs::remove_file(path).unwrap();
+        }
+    }
+}
@@ -0,0 +1,854 @@
+use crate::support::{
+    storage_map, storage_value, BalanceOf, BalanceStatus, DispatchResult, GetPallet, Hasher, Hooks,
+    NamedReservableCurrency, Origin, ReserveIdentifier, Storage,
+};
+use crate::system;
+use num::traits::{CheckedAdd, CheckedSub, Zero};
+use std::collections::BTreeMap;
+
+/// O identificador da reserva que guarda os depósitos das identidades e as taxas dos julgamentos.
+pub const IDENTITY_RESERVE_ID: ReserveIdentifier = *b"identity";
+
+/// O tipo de saldo dos depósitos e das taxas deste pallet.
+pub type DepositOf<T> = BalanceOf<<T as Config>::Currency, T>;
+
+/// O índice de um registrador, na ordem em que foram adicionados.
+pub type RegistrarIndex = u32;
+
+/// Os eventos emitidos pelo Módulo de Identidade.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Event<T: Config> {
+    /// `who` definiu ou alterou a sua identidade.
+    IdentitySet { who: T::AccountId },
+    /// `who` apagou a sua identidade, e `deposit` foi devolvido.
+    IdentityCleared {
+        who: T::AccountId,
+        deposit: DepositOf<T>,
+    },
+    /// `who` pediu o julgamento do registrador `registrar_index`.
+    JudgementRequested {
+        who: T::AccountId,
+        registrar_index: RegistrarIndex,
+    },
+    /// `who` cancelou o pedido de julgamento ao registrador `registrar_index`.
+    JudgementUnrequested {
+        who: T::AccountId,
+        registrar_index: RegistrarIndex,
+    },
+    /// O registrador `registrar_index` julgou a identidade de `target`.
+    JudgementGiven {
+        target: T::AccountId,
+        registrar_index: RegistrarIndex,
+    },
+    /// Um registrador foi adicionado com o índice `registrar_index`.
+    RegistrarAdded { registrar_index: RegistrarIndex },
+    /// `sub` foi ligada como subconta de `main`, que reservou `deposit`.
+    SubIdentityAdded {
+        sub: T::AccountId,
+        main: T::AccountId,
+        deposit: DepositOf<T>,
+    },
+    /// `main` desligou a subconta `sub`, e `deposit` foi devolvido.
+    SubIdentityRemoved {
+        sub: T::AccountId,
+        main: T::AccountId,
+        deposit: DepositOf<T>,
+    },
+    /// A subconta `sub` deixou `main`, e recebeu `deposit` de `main`.
+    SubIdentityRevoked {
+        sub: T::AccountId,
+        main: T::AccountId,
+        deposit: DepositOf<T>,
+    },
+}
+
+/// As informações de uma identidade. Os campos vazios não foram informados.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct IdentityInfo {
+    /// O nome de exibição da conta.
+    pub display: Vec<u8>,
+    /// O nome legal da pessoa ou organização.
+    pub legal: Vec<u8>,
+    /// O endereço de email.
+    pub email: Vec<u8>,
+    /// O endereço do site.
+    pub web: Vec<u8>,
+    /// Outros campos, como pares de nome e valor. Cada um exige um depósito adicional.
+    pub additional: Vec<(Vec<u8>, Vec<u8>)>,
+}
+
+/// O julgamento de um registrador sobre uma identidade.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Judgement<Balance> {
+    /// O registrador não sabe nada sobre a identidade.
+    Unknown,
+    /// O julgamento foi pedido, e a taxa do registrador está reservada até que ele o dê.
+    FeePaid(Balance),
+    /// As informações parecem razoáveis, mas não foram verificadas a fundo.
+    Reasonable,
+    /// As informações foram verificadas e estão corretas.
+    KnownGood,
+    /// As informações estavam corretas, mas não estão mais.
+    OutOfDate,
+    /// As informações são de baixa qualidade ou imprecisas.
+    LowQuality,
+    /// As informações são falsas de propósito.
+    Erroneous,
+}
+
+impl<Balance> Judgement<Balance> {
+    /// Um julgamento fixo não é desfeito quando a identidade muda: o pedido pago continua de pé,
+    /// e uma identidade falsa continua marcada.
+    pub fn is_sticky(&self) -> bool {
+        matches!(self, Judgement::FeePaid(_) | Judgement::Erroneous)
+    }
+}
+
+/// A identidade registrada de uma conta.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Registration<Balance> {
+    /// Os julgamentos dos registradores, em ordem de índice do registrador.
+    pub judgements: Vec<(RegistrarIndex, Judgement<Balance>)>,
+    /// O depósito reservado pela identidade, sem contar as taxas dos julgamentos pedidos.
+    pub deposit: Balance,
+    /// As informações da identidade.
+    pub info: IdentityInfo,
+}
+
+/// Um registrador, que julga identidades em troca de uma taxa.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RegistrarInfo<AccountId, Balance> {
+    /// A conta do registrador.
+    pub account: AccountId,
+    /// A taxa cobrada por julgamento.
+    pub fee: Balance,
+}
+
+/// A característica de configuração do Módulo de Identidade.
+pub trait Config: system::Config + Sized + GetPallet<Pallet<Self>> {
+    /// A moeda da qual são reservados os depósitos e as taxas.
+    type Currency: NamedReservableCurrency<Self>;
+    /// O depósito reservado por uma identidade.
+    const BASIC_DEPOSIT: DepositOf<Self>;
+    /// O depósito adicional reservado para cada campo de `additional`.
+    const FIELD_DEPOSIT: DepositOf<Self>;
+    /// O depósito reservado para cada subconta.
+    const SUB_ACCOUNT_DEPOSIT: DepositOf<Self>;
+    /// O tamanho máximo, em bytes, de cada campo de uma identidade e do nome de uma subconta.
+    const MAX_FIELD_LENGTH: u32;
+    /// O número máximo de campos em `additional`.
+    const MAX_ADDITIONAL_FIELDS: u32;
+    /// O número máximo de subcontas de uma conta.
+    const MAX_SUB_ACCOUNTS: u32;
+    /// O número máximo de registradores.
+    const MAX_REGISTRARS: u32;
+}
+
+/// Este é o Módulo de Identidade.
+/// As contas registram informações sobre quem são, como um nome de exibição, um email e um site,
+/// reservando um depósito. Registradores julgam essas informações em troca de uma taxa, e uma
+/// conta pode ligar outras contas a ela como subcontas.
+#[derive(Debug, Clone)]
+pub struct Pallet<T: Config> {
+    /// A identidade de cada conta.
+    identities: BTreeMap<T::AccountId, Registration<DepositOf<T>>>,
+    /// A conta principal de cada subconta, e o nome da subconta.
+    super_of: BTreeMap<T::AccountId, (T::AccountId, Vec<u8>)>,
+    /// O depósito total e as subcontas de cada conta principal.
+    #[allow(clippy::type_complexity)]
+    subs_of: BTreeMap<T::AccountId, (DepositOf<T>, Vec<T::AccountId>)>,
+    /// Os registradores, em ordem de índice.
+    registrars: Vec<RegistrarInfo<T::AccountId, DepositOf<T>>>,
+    /// Os eventos emitidos por este pallet no bloco atual, em ordem.
+    events: Vec<Event<T>>,
+}
+
+#[macros::call]
+impl<T: Config> Pallet<T> {
+    /// Adiciona `account` como registrador, sem taxa. Apenas a origem privilegiada pode fazer esta
+    /// chamada.
+    pub fn add_registrar(
+        &mut self,
+        origin: Origin<T::AccountId>,
+        account: T::AccountId,
+    ) -> DispatchResult {
+        origin.ensure_root()?;
+        if self.registrars.len() >= T::MAX_REGISTRARS as usize {
+            return Err("Too many registrars.");
+        }
+        self.registrars.push(RegistrarInfo {
+            account,
+            fee: Zero::zero(),
+        });
+        let registrar_index = self.registrars.len() as RegistrarIndex - 1;
+        self.events.push(Event::RegistrarAdded { registrar_index });
+        Ok(())
+    }
+
+    /// Define a identidade de `caller` como `info`, reservando o depósito `BASIC_DEPOSIT` mais
+    /// `FIELD_DEPOSIT` para cada campo adicional. Se `caller` já tiver uma identidade, o depósito
+    /// é ajustado e apenas os julgamentos fixos são mantidos.
+    pub fn set_identity(
+        runtime: &mut T,
+        caller: T::AccountId,
+        info: IdentityInfo,
+    ) -> DispatchResult {
+        if info.additional.len() > T::MAX_ADDITIONAL_FIELDS as usize {
+            return Err("Too many additional fields.");
+        }
+        let fields = [&info.display, &info.legal, &info.email, &info.web];
+        let additional = info.additional.iter().flat_map(|(key, value)| [key, value]);
+        if fields
+            .into_iter()
+            .chain(additional)
+            .any(|field| field.len() > T::MAX_FIELD_LENGTH as usize)
+        {
+            return Err("Identity field too long.");
+        }
+        let deposit = info
+            .additional
+            .iter()
+            .try_fold(T::BASIC_DEPOSIT, |deposit, _| {
+                deposit.checked_add(&T::FIELD_DEPOSIT)
+            })
+            .ok_or("Overflow")?;
+
+        let pallet: &Self = runtime.pallet();
+        let (old_deposit, judgements) = match pallet.identities.get(&caller) {
+            Some(registration) => (
+                registration.deposit,
+                registration
+                    .judgements
+                    .iter()
+                    .filter(|(_, judgement)| judgement.is_sticky())
+                    .cloned()
+                    .collect(),
+            ),
+            None => (Zero::zero(), Vec::new()),
+        };
+        if let Some(extra) = deposit.checked_sub(&old_deposit) {
+            T::Currency::reserve_named(runtime, &IDENTITY_RESERVE_ID, &caller, extra)?;
+        } else if let Some(refund) = old_deposit.checked_sub(&deposit) {
+            T::Currency::unreserve_named(runtime, &IDENTITY_RESERVE_ID, &caller, refund);
+        }
+
+        let pallet: &mut Self = runtime.pallet_mut();
+        let registration = Registration {
+            judgements,
+            deposit,
+            info,
+        };
+        pallet.identities.insert(caller.clone(), registration);
+        pallet.events.push(Event::IdentitySet { who: caller });
+        Ok(())
+    }
+
+    /// Apaga a identidade de `caller` e desliga todas as suas subcontas, devolvendo os depósitos e
+    /// as taxas dos julgamentos ainda não dados.
+    pub fn clear_identity(runtime: &mut T, caller: T::AccountId) -> DispatchResult {
+        let pallet: &mut Self = runtime.pallet_mut();
+        let registration = pallet.identities.get(&caller).ok_or("No identity found.")?;
+        let subs_deposit = pallet
+            .subs_of
+            .get(&caller)
+            .map_or_else(Zero::zero, |(deposit, _)| *deposit);
+        let fees = registration
+            .judgements
+            .iter()
+            .filter_map(|(_, judgement)| match judgement {
+                Judgement::FeePaid(fee) => Some(*fee),
+                _ => None,
+            });
+        let deposit = fees
+            .chain([subs_deposit])
+            .try_fold(registration.deposit, |deposit, amount| {
+                deposit.checked_add(&amount)
+            })
+            .ok_or("Overflow")?;
+
+        pallet.identities.remove(&caller);
+        if let Some((_, subs)) = pallet.subs_of.remove(&caller) {
+            for sub in &subs {
+                pallet.super_of.remove(sub);
+            }
+        }
+        T::Currency::unreserve_named(runtime, &IDENTITY_RESERVE_ID, &caller, deposit);
+
+        let pallet: &mut Self = runtime.pallet_mut();
+        pallet.events.push(Event::IdentityCleared {
+            who: caller,
+            deposit,
+        });
+        Ok(())
+    }
+
+    /// Pede o julgamento da identidade de `caller` ao registrador `registrar_index`, reservando
+    /// a taxa do registrador, desde que ela não passe de `max_fee`.
+    pub fn request_judgement(
+        runtime: &mut T,
+        caller: T::AccountId,
+        registrar_index: RegistrarIndex,
+        max_fee: DepositOf<T>,
+    ) -> DispatchResult {
+        let pallet: &Self = runtime.pallet();
+        let fee = pallet.registrar(registrar_index)?.fee;
+        if fee > max_fee {
+            return Err("Fee exceeds the maximum.");
+        }
+        let registration = pallet.identities.get(&caller).ok_or("No identity found.")?;
+        let position = registration
+            .judgements
+            .binary_search_by_key(&registrar_index, |(index, _)| *index);
+        if let Ok(i) = position {
+            if registration.judgements[i].1.is_sticky() {
+                return Err("The judgement cannot be requested again.");
+            }
+        }
+
+        T::Currency::reserve_named(runtime, &IDENTITY_RESERVE_ID, &caller, fee)?;
+        let pallet: &mut Self = runtime.pallet_mut();
+        let registration = pallet
+            .identities
+            .get_mut(&caller)
+            .ok_or("No identity found.")?;
+        let judgement = (registrar_index, Judgement::FeePaid(fee));
+        match position {
+            Ok(i) => registration.judgements[i] = judgement,
+            Err(i) => registration.judgements.insert(i, judgement),
+        }
+        pallet.events.push(Event::JudgementRequested {
+            who: caller,
+            registrar_index,
+        });
+        Ok(())
+    }
+
+    /// Cancela o pedido de julgamento de `caller` ao registrador `registrar_index`, devolvendo a
+    /// taxa reservada.
+    pub fn cancel_request(
+        runtime: &mut T,
+        caller: T::AccountId,
+        registrar_index: RegistrarIndex,
+    ) -> DispatchResult {
+        let pallet: &mut Self = runtime.pallet_mut();
+        let registration = pallet
+            .identities
+            .get_mut(&caller)
+            .ok_or("No identity found.")?;
+        let i = registration
+            .judgements
+            .binary_search_by_key(&registrar_index, |(index, _)| *index)
+            .map_err(|_| "No pending judgement request.")?;
+        let Judgement::FeePaid(fee) = registration.judgements[i].1 else {
+            return Err("No pending judgement request.");
+        };
+        registration.judgements.remove(i);
+
+        T::Currency::unreserve_named(runtime, &IDENTITY_RESERVE_ID, &caller, fee);
+        let pallet: &mut Self = runtime.pallet_mut();
+        pallet.events.push(Event::JudgementUnrequested {
+            who: caller,
+            registrar_index,
+        });
+        Ok(())
+    }
+
+    /// Altera a taxa do registrador `registrar_index` para `fee`. Apenas o próprio registrador
+    /// pode fazer esta chamada.
+    pub fn set_fee(
+        &mut self,
+        caller: T::AccountId,
+        registrar_index: RegistrarIndex,
+        fee: DepositOf<T>,
+    ) -> DispatchResult {
+        let registrar = self
+            .registrars
+            .get_mut(registrar_index as usize)
+            .ok_or("Invalid registrar.")?;
+        if registrar.account != caller {
+            return Err("Only the registrar can make this call.");
+        }
+        registrar.fee = fee;
+        Ok(())
+    }
+
+    /// Dá o julgamento `judgement` sobre a identidade de `target`, como o registrador
+    /// `registrar_index`. `identity` precisa ser o hash da identidade julgada, obtido com
+    /// `identity_hash`, para que a identidade não mude sem que o registrador perceba. O julgamento
+    /// precisa ter sido pedido a este registrador, e a taxa reservada é paga a ele.
+    pub fn provide_judgement(
+        runtime: &mut T,
+        caller: T::AccountId,
+        registrar_index: RegistrarIndex,
+        target: T::AccountId,
+        judgement: Judgement<DepositOf<T>>,
+        identity: T::Hash,
+    ) -> DispatchResult {
+        let pallet: &Self = runtime.pallet();
+        if pallet.registrar(registrar_index)?.account != caller {
+            return Err("Only the registrar can make this call.");
+        }
+        if matches!(judgement, Judgement::FeePaid(_)) {
+            return Err("Invalid judgement.");
+        }
+        let registration = pallet.identities.get(&target).ok_or("No identity found.")?;
+        if Self::identity_hash(&registration.info) != identity {
+            return Err("The identity has changed.");
+        }
+        let i = registration
+            .judgements
+            .binary_search_by_key(&registrar_index, |(index, _)| *index)
+            .map_err(|_| "No pending judgement request.")?;
+        let Judgement::FeePaid(fee) = registration.judgements[i].1 else {
+            return Err("No pending judgement request.");
+        };
+
+        T::Currency::repatriate_reserved_named(
+            runtime,
+            &IDENTITY_RESERVE_ID,
+            &target,
+            &caller,
+            fee,
+            BalanceStatus::Free,
+        )?;
+        let pallet: &mut Self = runtime.pallet_mut();
+        let registration = pallet
+            .identities
+            .get_mut(&target)
+            .ok_or("No identity found.")?;
+        registration.judgements[i] = (registrar_index, judgement);
+        pallet.events.push(Event::JudgementGiven {
+            target,
+            registrar_index,
+        });
+        Ok(())
+    }
+
+    /// Liga `sub` como subconta de `caller`, com o nome `name`, reservando `SUB_ACCOUNT_DEPOSIT`.
+    /// `caller` precisa ter uma identidade, e `sub` não pode ser subconta de outra conta.
+    pub fn add_sub(
+        runtime: &mut T,
+        caller: T::AccountId,
+        sub: T::AccountId,
+        name: Vec<u8>,
+    ) -> DispatchResult {
+        if name.len() > T::MAX_FIELD_LENGTH as usize {
+            return Err("Identity field too long.");
+        }
+        let pallet: &Self = runtime.pallet();
+        if !pallet.identities.contains_key(&caller) {
+            return Err("No identity found.");
+        }
+        if sub == caller || pallet.super_of.contains_key(&sub) {
+            return Err("The account is already linked.");
+        }
+        if pallet.subs_of(&caller).len() >= T::MAX_SUB_ACCOUNTS as usize {
+            return Err("Too many sub-accounts.");
+        }
+
+        let deposit = T::SUB_ACCOUNT_DEPOSIT;
+        T::Currency::reserve_named(runtime, &IDENTITY_RESERVE_ID, &caller, deposit)?;
+        let pallet: &mut Self = runtime.pallet_mut();
+        let (total, subs) = pallet
+            .subs_of
+            .entry(caller.clone())
+            .or_insert_with(|| (Zero::zero(), Vec::new()));
+        *total = total.checked_add(&deposit).ok_or("Overflow")?;
+        subs.push(sub.clone());
+        pallet.super_of.insert(sub.clone(), (caller.clone(), name));
+        pallet.events.push(Event::SubIdentityAdded {
+            sub,
+            main: caller,
+            deposit,
+        });
+        Ok(())
+    }
+
+    /// Desliga a subconta `sub` de `caller`, devolvendo o seu depósito.
+    pub fn remove_sub(runtime: &mut T, caller: T::AccountId, sub: T::AccountId) -> DispatchResult {
+        let deposit = Self::unlink_sub(runtime, &caller, &sub)?;
+        T::Currency::unreserve_named(runtime, &IDENTITY_RESERVE_ID, &caller, deposit);
+        Self::deposit_event(
+            runtime,
+            Event::SubIdentityRemoved {
+                sub,
+                main: caller,
+                deposit,
+            },
+        );
+        Ok(())
+    }
+
+    /// Deixa de ser subconta da sua conta principal. `caller` recebe o depósito da subconta, como
+    /// compensação pela ligação que não pediu.
+    pub fn quit_sub(runtime: &mut T, caller: T::AccountId) -> DispatchResult {
+        let pallet: &Self = runtime.pallet();
+        let (main, _) = pallet
+            .super_of
+            .get(&caller)
+            .cloned()
+            .ok_or("Not a sub-account.")?;
+        let deposit = Self::unlink_sub(runtime, &main, &caller)?;
+        T::Currency::repatriate_reserved_named(
+            runtime,
+            &IDENTITY_RESERVE_ID,
+            &main,
+            &caller,
+            deposit,
+            BalanceStatus::Free,
+        )?;
+        Self::deposit_event(
+            runtime,
+            Event::SubIdentityRevoked {
+                sub: caller,
+                main,
+                deposit,
+            },
+        );
+        Ok(())
+    }
+}
+
+impl<T: Config> Pallet<T> {
+    /// Cria uma nova instância do Módulo de Identidade.
+    pub fn new() -> Self {
+        Self {
+            identities: BTreeMap::new(),
+            super_of: BTreeMap::new(),
+            subs_of: BTreeMap::new(),
+            registrars: Vec::new(),
+            events: Vec::new(),
+        }
+    }
+
+    /// Obtém a identidade de `who`, se houver.
+    pub fn identity(&self, who: &T::AccountId) -> Option<&Registration<DepositOf<T>>> {
+        self.identities.get(who)
+    }
+
+    /// Obtém a conta principal de `sub` e o nome da subconta, se `sub` for uma subconta.
+    pub fn super_of(&self, sub: &T::AccountId) -> Option<&(T::AccountId, Vec<u8>)> {
+        self.super_of.get(sub)
+    }
+
+    /// Obtém as subcontas de `who`.
+    pub fn subs_of(&self, who: &T::AccountId) -> &[T::AccountId] {
+        self.subs_of.get(who).map_or(&[], |(_, subs)| subs)
+    }
+
+    /// Obtém os registradores, em ordem de índice.
+    pub fn registrars(&self) -> &[RegistrarInfo<T::AccountId, DepositOf<T>>] {
+        &self.registrars
+    }
+
+    /// Obtém o nome de exibição de `who`. O nome de uma subconta é o nome da conta principal
+    /// seguido do nome da subconta, como em `Azuki/cofre`.
+    pub fn display_name(&self, who: &T::AccountId) -> Option<Vec<u8>> {
+        if let Some(registration) = self.identities.get(who) {
+            return Some(registration.info.display.clone());
+        }
+        let (main, name) = self.super_of.get(who)?;
+        let display = &self.identities.get(main)?.info.display;
+        Some([display.as_slice(), b"/", name].concat())
+    }
+
+    /// Calcula o hash das informações de uma identidade, como usado em `provide_judgement`.
+    pub fn identity_hash(info: &IdentityInfo) -> T::Hash {
+        T::Hashing::hash(format!("{:?}", info).as_bytes())
+    }
+
+    /// Obtém os eventos emitidos por este pallet no bloco atual, em ordem.
+    pub fn events(&self) -> &[Event<T>] {
+        &self.events
+    }
+
+    /// Registra um evento emitido por este pallet.
+    fn deposit_event(runtime: &mut T, event: Event<T>) {
+        let pallet: &mut Self = runtime.pallet_mut();
+        pallet.events.push(event);
+    }
+
+    /// Obtém o registrador `registrar_index`.
+    fn registrar(
+        &self,
+        registrar_index: RegistrarIndex,
+    ) -> Result<&RegistrarInfo<T::AccountId, DepositOf<T>>, &'static str> {
+        self.registrars
+            .get(registrar_index as usize)
+            .ok_or("Invalid registrar.")
+    }
+
+    /// Desliga a subconta `sub` de `main`, e retorna o depósito que ela reservava, que continua
+    /// reservado de `main`.
+    fn unlink_sub(
+        runtime: &mut T,
+        main: &T::AccountId,
+        sub: &T::AccountId,
+    ) -> Result<DepositOf<T>, &'static str> {
+        let pallet: &mut Self = runtime.pallet_mut();
+        if pallet.super_of.get(sub).map(|(account, _)| account) != Some(main) {
+            return Err("Not a sub-account of the caller.");
+        }
+        pallet.super_of.remove(sub);
+        let deposit = T::SUB_ACCOUNT_DEPOSIT;
+        if let Some((total, subs)) = pallet.subs_of.get_mut(main) {
+            subs.retain(|account| account != sub);
+            *total = total.checked_sub(&deposit).unwrap_or_else(Zero::zero);
+            if subs.is_empty() {
+                pallet.subs_of.remove(main);
+            }
+        }
+        Ok(deposit)
+    }
+}
+
+impl<T: Config> Hooks<T> for Pallet<T> {
+    fn on_initialize(runtime: &mut T, _n: T::BlockNumber) {
+        let pallet: &mut Self = runtime.pallet_mut();
+        pallet.events.clear();
+    }
+}
+
+impl<T: Config> Storage for Pallet<T> {
+    fn storage(&self) -> Vec<(Vec<u8>, Vec<u8>)> {
+        let mut storage: Vec<_> = storage_map("identities", &self.identities).collect();
+        storage.extend(storage_map("super_of", &self.super_of));
+        storage.extend(storage_map("subs_of", &self.subs_of));
+        storage.push(storage_value("registrars", &self.registrars));
+        storage
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::tests::new_runtime;
+    use crate::Runtime;
+
+    type Identity = Pallet<Runtime>;
+
+    fn info(display: &str) -> IdentityInfo {
+        IdentityInfo {
+            display: display.as_bytes().to_vec(),
+            email: b"contato@azuki.dev".to_vec(),
+            web: b"https://azuki.dev".to_vec(),
+            ..Default::default()
+        }
+    }
+
+    /// Alice com a identidade `info("Alice")`, e bob como o registrador 0, com a taxa 5.
+    fn with_registrar() -> (Runtime, [String; 3]) {
+        let (mut runtime, [alice, bob, charlie]) = new_runtime(["alice", "bob", "charlie"], 100);
+        let res = Identity::set_identity(&mut runtime, alice.clone(), info("Alice"));
+        assert_eq!(res, Ok(()));
+        let res = runtime.identity.add_registrar(Origin::Root, bob.clone());
+        assert_eq!(res, Ok(()));
+        assert_eq!(runtime.identity.set_fee(bob.clone(), 0, 5), Ok(()));
+        (runtime, [alice, bob, charlie])
+    }
+
+    #[test]
+    fn identity_deposit() {
+        let (mut runtime, [alice, bob]) = new_runtime(["alice", "bob"], 100);
+
+        // A identidade reserva o depósito básico mais um depósito por campo adicional
+        let mut alice_info = info("Alice");
+        alice_info.additional = vec![(b"github".to_vec(), b"alice".to_vec())];
+        let res = Identity::set_identity(&mut runtime, alice.clone(), alice_info);
+        assert_eq!(res, Ok(()));
+        assert_eq!(runtime.balances.reserved_balance(&alice), 12);
+        let too_long = info(&"a".repeat(65));
+        let res = Identity::set_identity(&mut runtime, bob, too_long);
+        assert_eq!(res, Err("Identity field too long."));
+    }
+
+    #[test]
+    fn registrars() {
+        let (mut runtime, [alice, bob]) = new_runtime(["alice", "bob"], 100);
+
+        // Apenas a origem privilegiada adiciona registradores, que definem a própria taxa
+        let res = runtime
+            .identity
+            .add_registrar(Origin::Signed(bob.clone()), bob.clone());
+        assert_eq!(res, Err("Bad origin: expected root."));
+        assert_eq!(
+            runtime.identity.add_registrar(Origin::Root, bob.clone()),
+            Ok(())
+        );
+        assert_eq!(
+            runtime.identity.set_fee(alice, 0, 5),
+            Err("Only the registrar can make this call.")
+        );
+        assert_eq!(runtime.identity.set_fee(bob, 0, 5), Ok(()));
+    }
+
+    #[test]
+    fn paid_judgement() {
+        let (mut runtime, [alice, bob, _]) = with_registrar();
+
+        // O pedido de julgamento reserva a taxa, que é paga ao registrador com o julgamento
+        let res = Identity::request_judgement(&mut runtime, alice.clone(), 0, 4);
+        assert_eq!(res, Err("Fee exceeds the maximum."));
+        let res = Identity::request_judgement(&mut runtime, alice.clone(), 0, 5);
+        assert_eq!(res, Ok(()));
+        assert_eq!(runtime.balances.reserved_balance(&alice), 15);
+        let stale = Identity::identity_hash(&info("Bob"));
+        let res = Identity::provide_judgement(
+            &mut runtime,
+            bob.clone(),
+            0,
+            alice.clone(),
+            Judgement::KnownGood,
+            stale,
+        );
+        assert_eq!(res, Err("The identity has changed."));
+        let hash = Identity::identity_hash(&info("Alice"));
+        let res = Identity::provide_judgement(
+            &mut runtime,
+            bob.clone(),
+            0,
+            alice.clone(),
+            Judgement::KnownGood,
+            hash,
+        );
+        assert_eq!(res, Ok(()));
+        assert_eq!(runtime.balances.balance(&bob), 105);
+        assert_eq!(runtime.balances.reserved_balance(&alice), 10);
+        let registration = runtime.identity.identity(&alice).unwrap();
+        assert_eq!(registration.judgements, [(0, Judgement::KnownGood)]);
+
+        // Alterar a identidade ajusta o depósito e descarta os julgamentos que não são fixos
+        let mut alice_info = info("Alice");
+        alice_info.additional = vec![(b"github".to_vec(), b"alice".to_vec())];
+        let res = Identity::set_identity(&mut runtime, alice.clone(), alice_info);
+        assert_eq!(res, Ok(()));
+        assert_eq!(runtime.balances.reserved_balance(&alice), 12);
+        assert!(runtime
+            .identity
+            .identity(&alice)
+            .unwrap()
+            .judgements
+            .is_empty());
+    }
+
+    #[test]
+    fn judgement_requires_request() {
+        let (mut runtime, [alice, bob, charlie]) = with_registrar();
+        let hash = Identity::identity_hash(&info("Alice"));
+        let res = runtime
+            .identity
+            .add_registrar(Origin::Root, charlie.clone());
+        assert_eq!(res, Ok(()));
+
+        // Um registrador não julga uma identidade que não pediu o seu julgamento, nem mesmo com um
+        // julgamento fixo
+        let res = Identity::provide_judgement(
+            &mut runtime,
+            bob.clone(),
+            0,
+            alice.clone(),
+            Judgement::Erroneous,
+            hash,
+        );
+        assert_eq!(res, Err("No pending judgement request."));
+
+        // O pedido a um registrador não permite que outro julgue
+        let res = Identity::request_judgement(&mut runtime, alice.clone(), 1, 0);
+        assert_eq!(res, Ok(()));
+        let res = Identity::provide_judgement(
+            &mut runtime,
+            bob.clone(),
+            0,
+            alice.clone(),
+            Judgement::Erroneous,
+            hash,
+        );
+        assert_eq!(res, Err("No pending judgement request."));
+        let res = Identity::provide_judgement(
+            &mut runtime,
+            charlie.clone(),
+            1,
+            alice.clone(),
+            Judgement::Reasonable,
+            hash,
+        );
+        assert_eq!(res, Ok(()));
+
+        // Um julgamento dado não pode ser dado de novo sem um novo pedido
+        let res = Identity::provide_judgement(
+            &mut runtime,
+            charlie,
+            1,
+            alice.clone(),
+            Judgement::Erroneous,
+            hash,
+        );
+        assert_eq!(res, Err("No pending judgement request."));
+        let registration = runtime.identity.identity(&alice).unwrap();
+        assert_eq!(registration.judgements, [(1, Judgement::Reasonable)]);
+    }
+
+    #[test]
+    fn sub_accounts() {
+        let (mut runtime, [alice, bob, charlie]) = with_registrar();
+
+        // Subcontas aparecem com o nome da conta principal, e podem deixá-la com o depósito
+        let res = Identity::add_sub(
+            &mut runtime,
+            alice.clone(),
+            charlie.clone(),
+            b"cofre".to_vec(),
+        );
+        assert_eq!(res, Ok(()));
+        assert_eq!(runtime.balances.reserved_balance(&alice), 12);
+        assert_eq!(
+            runtime.identity.display_name(&charlie),
+            Some(b"Alice/cofre".to_vec())
+        );
+        let res = Identity::add_sub(&mut runtime, bob, charlie.clone(), Vec::new());
+        assert_eq!(res, Err("No identity found."));
+        assert_eq!(Identity::quit_sub(&mut runtime, charlie.clone()), Ok(()));
+        assert_eq!(runtime.balances.balance(&charlie), 102);
+        assert_eq!(runtime.identity.subs_of(&alice), &[] as &[String]);
+    }
+
+    #[test]
+    fn clear_identity() {
+        let (mut runtime, [alice, _, charlie]) = with_registrar();
+
+        // Apagar a identidade devolve os depósitos e as taxas pendentes
+        let res = Identity::add_sub(&mut runtime, alice.clone(), charlie.clone(), Vec::new());
+        assert_eq!(res, Ok(()));
+        let res = Identity::request_judgement(&mut runtime, alice.clone(), 0, 5);
+        assert_eq!(res, Ok(()));
+        assert_eq!(runtime.balances.reserved_balance(&alice), 17);
+        assert_eq!(
+            Identity::clear_identity(&mut runtime, alice.clone()),
+            Ok(())
+        );
+        assert_eq!(runtime.balances.reserved_balance(&alice), 0);
+        assert_eq!(runtime.identity.super_of(&charlie), None);
+        assert!(matches!(
+            runtime.identity.events().last(),
+            Some(Event::IdentityCleared { deposit: 17, .. })
+        ));
+    }
+
+    #[test]
+    fn clear_identity_overflow() {
+        let (mut runtime, [alice, _, charlie]) = with_registrar();
+        let res = Identity::add_sub(&mut runtime, alice.clone(), charlie.clone(), Vec::new());
+        assert_eq!(res, Ok(()));
+
+        // Se o total a devolver não couber no tipo do saldo, nada é apagado
+        runtime.identity.identities.get_mut(&alice).unwrap().deposit = u128::MAX;
+        let res = Identity::clear_identity(&mut runtime, alice.clone());
+        assert_eq!(res, Err("Overflow"));
+        assert!(runtime.identity.identity(&alice).is_some());
+        assert_eq!(runtime.identity.subs_of(&alice), ["charlie"]);
+        assert_eq!(
+            runtime.identity.super_of(&charlie).map(|(who, _)| who),
+            Some(&alice)
+        );
+    }
+}
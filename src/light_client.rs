@@ -0,0 +1,21 @@
+use crate::support::{Hasher, Header};
+use crate::trie::{self, ReadProof};
+
+/// Verifica, sem executar nenhum bloco, que a chave de armazenamento `key` tinha o valor `value`
+/// depois do bloco de `header`, ou que ela não existia quando `value` é `None`. Basta confiar no
+/// cabeçalho; a prova vem de um nó que guarda o estado.
+pub fn verify_storage<H: Hasher, BlockNumber>(
+    header: &Header<BlockNumber, H::Output>,
+    key: &[u8],
+    value: Option<&[u8]>,
+    proof: &ReadProof<H::Output>,
+) -> Result<(), &'static str> {
+    let root = header
+        .state_root
+        .as_ref()
+        .ok_or("header has no state root")?;
+    if !trie::verify_read_proof::<H>(root, key, value, proof) {
+        return Err("invalid storage proof");
+    }
+    Ok(())
+}
@@ -1,29 +1,62 @@
+mod assets;
 mod balances;
+mod cli;
+mod hashing;
+mod identity;
+mod light_client;
+mod merkle;
+mod multisig;
+mod nfts;
 mod proof_of_existence;
+mod proxy;
+mod scheduler;
+mod sudo;
 mod support;
 mod system;
+#[cfg(test)]
+mod tests;
+mod timestamp;
+mod trie;
+mod utility;
 
-use crate::support::Dispatch;
+use crate::support::{Dispatch, Hasher};
 
 mod types {
     pub type AccountId = String;
     pub type Balance = u128;
     pub type BlockNumber = u32;
     pub type Nonce = u32;
+    pub type Hash = crate::hashing::H256;
+    pub type Hashing = crate::hashing::Sha256;
     pub type Extrinsic = crate::support::Extrinsic<AccountId, crate::RuntimeCall>;
-    pub type Header = crate::support::Header<BlockNumber>;
+    pub type Header = crate::support::Header<BlockNumber, Hash>;
     pub type Block = crate::support::Block<Header, Extrinsic>;
-    pub type Content = &'static str;
+    pub type Content = Hash;
+    pub type AssetId = u32;
+    pub type Moment = u64;
+    pub type CollectionId = u32;
+    pub type ItemId = u32;
 }
 
 // Este é o nosso Runtime principal.
 // Acumula todos os diferentes pallets que queremos utilizar.
-#[derive(Debug)]
+#[derive(Debug, Clone)]
 #[macros::runtime]
 pub struct Runtime {
     system: system::Pallet<Self>,
+    #[inherent]
+    timestamp: timestamp::Pallet<Self>,
     balances: balances::Pallet<Self>,
+    assets: assets::Pallet<Self>,
     proof_of_existence: proof_of_existence::Pallet<Self>,
+    utility: utility::Pallet<Self>,
+    sudo: sudo::Pallet<Self>,
+    multisig: multisig::Pallet<Self>,
+    #[validate_unsigned]
+    proxy: proxy::Pallet<Self>,
+    scheduler: scheduler::Pallet<Self>,
+    identity: identity::Pallet<Self>,
+    nfts: nfts::Pallet<Self>,
 }
 
 // Implementação da característica `system::Config` para o `Runtime`.
@@ -31,18 +64,151 @@ impl system::Config for Runtime {
     type AccountId = types::AccountId;
     type BlockNumber = types::BlockNumber;
     type Nonce = types::Nonce;
+    type Hash = types::Hash;
+    type Hashing = types::Hashing;
+}
+
+impl timestamp::Config for Runtime {
+    type Moment = types::Moment;
+    // Os horários são dados em milissegundos, com blocos a cada seis segundos.
+    const MINIMUM_PERIOD: types::Moment = 6_000;
 }
 
 // Implementação da característica `balances::Config` para o `Runtime`.
 impl balances::Config for Runtime {
     type Balance = types::Balance;
+    const EXISTENTIAL_DEPOSIT: types::Balance = 1;
+}
+
+impl assets::Config for Runtime {
+    type AssetId = types::AssetId;
+    type Balance = types::Balance;
+    type Currency = balances::Pallet<Self>;
+    const ASSET_DEPOSIT: types::Balance = 5;
+    const STRING_LIMIT: u32 = 32;
 }
 
 impl proof_of_existence::Config for Runtime {
     type Content = types::Content;
+    type Time = timestamp::Pallet<Self>;
+    const MAX_DESCRIPTION_LENGTH: u32 = 128;
+    type Currency = balances::Pallet<Self>;
+    const MAX_CLAIMS_PER_ACCOUNT: u32 = 100;
+    const CLAIM_DEPOSIT: types::Balance = 5;
+    const MAX_ATTESTERS: u32 = 16;
+}
+
+impl utility::Config for Runtime {
+    type RuntimeCall = RuntimeCall;
+    const MAX_BATCH_SIZE: u32 = 32;
+    const MAX_BATCH_DEPTH: u32 = 4;
+}
+
+impl sudo::Config for Runtime {
+    type RuntimeCall = RuntimeCall;
+}
+
+impl multisig::Config for Runtime {
+    type RuntimeCall = RuntimeCall;
+    type Currency = balances::Pallet<Self>;
+    const MULTISIG_DEPOSIT: types::Balance = 10;
+    const MAX_SIGNATORIES: u32 = 16;
+
+    fn account_from_hash(hash: &types::Hash) -> types::AccountId {
+        hashing::to_hex(hash)
+    }
+}
+
+/// Os tipos de proxy do runtime, e as chamadas que cada um pode fazer em nome da conta real.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ProxyType {
+    /// Qualquer chamada.
+    Any,
+    /// Apenas transferências de saldo.
+    Transfer,
+    /// Apenas chamadas do pallet de prova de existência.
+    ProofOfExistence,
+}
+
+impl proxy::InstanceFilter<RuntimeCall> for ProxyType {
+    fn filter(&self, call: &RuntimeCall) -> bool {
+        match self {
+            ProxyType::Any => true,
+            ProxyType::Transfer => matches!(
+                call,
+                RuntimeCall::balances(
+                    balances::Call::transfer { .. }
+                        | balances::Call::transfer_keep_alive { .. }
+                        | balances::Call::transfer_all { .. }
+                )
+            ),
+            ProxyType::ProofOfExistence => matches!(call, RuntimeCall::proof_of_existence(_)),
+        }
+    }
+}
+
+impl proxy::Config for Runtime {
+    type RuntimeCall = RuntimeCall;
+    type ProxyType = ProxyType;
+    const MAX_PROXIES: u32 = 32;
+    const MAX_PENDING: u32 = 32;
+}
+
+impl scheduler::Config for Runtime {
+    type RuntimeCall = RuntimeCall;
+    type Currency = balances::Pallet<Self>;
+    const SCHEDULE_DEPOSIT: types::Balance = 5;
+    const MAX_SCHEDULED_PER_BLOCK: u32 = 16;
+}
+
+impl identity::Config for Runtime {
+    type Currency = balances::Pallet<Self>;
+    const BASIC_DEPOSIT: types::Balance = 10;
+    const FIELD_DEPOSIT: types::Balance = 2;
+    const SUB_ACCOUNT_DEPOSIT: types::Balance = 2;
+    const MAX_FIELD_LENGTH: u32 = 64;
+    const MAX_ADDITIONAL_FIELDS: u32 = 8;
+    const MAX_SUB_ACCOUNTS: u32 = 16;
+    const MAX_REGISTRARS: u32 = 8;
+}
+
+impl nfts::Config for Runtime {
+    type CollectionId = types::CollectionId;
+    type ItemId = types::ItemId;
+    type Currency = balances::Pallet<Self>;
+    const COLLECTION_DEPOSIT: types::Balance = 10;
+    const ITEM_DEPOSIT: types::Balance = 1;
+    const STRING_LIMIT: u32 = 64;
+    const MAX_ATTRIBUTES: u32 = 2;
 }
 
 fn main() {
+    // Com `claim <conta> <arquivo> [descrição]`, reivindica um arquivo local, e com
+    // `batch <conta> <arquivo>...`, vários arquivos de uma vez, em vez de executar o exemplo.
+    let args: Vec<String> = std::env::args().skip(1).collect();
+    match args.as_slice() {
+        [] => {}
+        [command, caller, path, rest @ ..] if command == "claim" && rest.len() <= 1 => {
+            let description = rest.first().map(String::as_str).unwrap_or("");
+            if let Err(e) = cli::claim(caller.clone(), path.as_ref(), description) {
+                eprintln!("{}", e);
+                std::process::exit(1);
+            }
+            return;
+        }
+        [command, caller, paths @ ..] if command == "batch" && !paths.is_empty() => {
+            if let Err(e) = cli::batch_claim(caller.clone(), paths) {
+                eprintln!("{}", e);
+                std::process::exit(1);
+            }
+            return;
+        }
+        _ => {
+            eprintln!("{}", cli::USAGE);
+            std::process::exit(1);
+        }
+    }
+
     let mut runtime = Runtime::new();
 
     // Variáveis para as contas
@@ -52,21 +218,81 @@ fn main() {
 
     // Usando as variáveis
     runtime.balances.set_balance(&dev0, 100);
+    runtime.balances.set_balance(&azuki, 10);
+    runtime.sudo.initialize_key(dev0.clone());
+
+    // Uma transferência que dev1 anuncia como proxy de dev0, mas que nunca chega a fazer.
+    let refund: RuntimeCall = balances::Call::transfer {
+        to: dev1.clone(),
+        amount: 5,
+    }
+    .into();
 
     let block_1 = types::Block {
-        header: support::Header { block_number: 1 },
+        header: support::Header {
+            block_number: 1,
+            state_root: None,
+        },
         extrinsics: vec![
-            support::Extrinsic {
+            support::Extrinsic::Inherent {
+                call: RuntimeCall::timestamp(timestamp::Call::set {
+                    now: 1_700_000_000_000,
+                }),
+            },
+            support::Extrinsic::Signed {
                 caller: dev0.clone(),
                 call: RuntimeCall::balances(balances::Call::transfer {
-                    to: dev1,
+                    to: dev1.clone(),
                     amount: 20,
                 }),
             },
-            support::Extrinsic {
+            support::Extrinsic::Signed {
                 caller: dev0.clone(),
                 call: RuntimeCall::proof_of_existence(proof_of_existence::Call::create_claim {
-                    claim: "oi",
+                    claim: types::Hashing::hash(b"oi"),
+                    description: "saudação".as_bytes().to_vec(),
+                    expires_at: None,
+                    attestation_threshold: None,
+                }),
+            },
+            support::Extrinsic::Signed {
+                caller: dev0.clone(),
+                call: RuntimeCall::identity(identity::Call::set_identity {
+                    info: identity::IdentityInfo {
+                        display: b"Dev Zero".to_vec(),
+                        web: b"https://dev0.example".to_vec(),
+                        ..Default::default()
+                    },
+                }),
+            },
+            support::Extrinsic::Signed {
+                caller: azuki.clone(),
+                call: RuntimeCall::assets(assets::Call::create {
+                    asset_id: 1,
+                    admin: azuki.clone(),
+                }),
+            },
+            support::Extrinsic::Signed {
+                caller: azuki.clone(),
+                call: RuntimeCall::assets(assets::Call::mint {
+                    asset_id: 1,
+                    beneficiary: dev0.clone(),
+                    amount: 1_000,
+                }),
+            },
+            support::Extrinsic::Signed {
+                caller: dev0.clone(),
+                call: RuntimeCall::proxy(proxy::Call::add_proxy {
+                    delegate: dev1.clone(),
+                    proxy_type: ProxyType::Transfer,
+                    delay: 1,
+                }),
+            },
+            support::Extrinsic::Signed {
+                caller: dev1.clone(),
+                call: RuntimeCall::proxy(proxy::Call::announce {
+                    real: dev0.clone(),
+                    call_hash: support::call_hash::<types::Hashing>(&refund),
                 }),
             },
         ],
@@ -74,37 +300,103 @@ fn main() {
 
     // Bloco 2
     let block_2 = types::Block {
-        header: types::Header { block_number: 2 },
+        header: types::Header {
+            block_number: 2,
+            state_root: None,
+        },
         extrinsics: vec![
-            support::Extrinsic {
+            support::Extrinsic::Inherent {
+                call: RuntimeCall::timestamp(timestamp::Call::set {
+                    now: 1_700_000_006_000,
+                }),
+            },
+            support::Extrinsic::Signed {
                 caller: azuki.clone(),
                 call: RuntimeCall::proof_of_existence(proof_of_existence::Call::create_claim {
-                    claim: "tchau",
+                    claim: types::Hashing::hash(b"tchau"),
+                    description: b"despedida".to_vec(),
+                    expires_at: None,
+                    attestation_threshold: None,
                 }),
             },
-            support::Extrinsic {
+            support::Extrinsic::Signed {
                 caller: dev0.clone(),
                 call: RuntimeCall::proof_of_existence(proof_of_existence::Call::revoke_claim {
-                    claim: "oi",
+                    claim: types::Hashing::hash(b"oi"),
                 }),
             },
+            support::Extrinsic::Signed {
+                caller: dev0.clone(),
+                call: RuntimeCall::utility(utility::Call::batch_all {
+                    calls: vec![
+                        balances::Call::transfer {
+                            to: azuki.clone(),
+                            amount: 10,
+                        }
+                        .into(),
+                        proof_of_existence::Call::create_claim {
+                            claim: types::Hashing::hash(b"lote"),
+                            description: Vec::new(),
+                            expires_at: None,
+                            attestation_threshold: None,
+                        }
+                        .into(),
+                    ],
+                }),
+            },
+            support::Extrinsic::Signed {
+                caller: dev0.clone(),
+                call: RuntimeCall::proxy(proxy::Call::remove_proxies {}),
+            },
         ],
     };
 
     // Bloco 3
     let block_3 = types::Block {
-        header: types::Header { block_number: 3 },
+        header: types::Header {
+            block_number: 3,
+            state_root: None,
+        },
         extrinsics: vec![
-            support::Extrinsic {
+            support::Extrinsic::Inherent {
+                call: RuntimeCall::timestamp(timestamp::Call::set {
+                    now: 1_700_000_012_000,
+                }),
+            },
+            support::Extrinsic::Signed {
                 caller: dev0.clone(),
                 call: RuntimeCall::proof_of_existence(proof_of_existence::Call::revoke_claim {
-                    claim: "Hello, world!",
+                    claim: types::Hashing::hash(b"Hello, world!"),
                 }),
             },
-            support::Extrinsic {
+            support::Extrinsic::Signed {
                 caller: dev0.clone(),
                 call: RuntimeCall::proof_of_existence(proof_of_existence::Call::create_claim {
-                    claim: "Hello, world!",
+                    claim: types::Hashing::hash(b"Hello, world!"),
+                    description: b"exemplo".to_vec(),
+                    expires_at: None,
+                    attestation_threshold: None,
+                }),
+            },
+            support::Extrinsic::Signed {
+                caller: dev0.clone(),
+                call: RuntimeCall::sudo(sudo::Call::sudo {
+                    call: Box::new(
+                        balances::Call::force_set_balance {
+                            who: azuki.clone(),
+                            amount: 50,
+                        }
+                        .into(),
+                    ),
+                }),
+            },
+            // dev1 não é mais proxy de dev0, então qualquer um pode remover o anúncio dele sem
+            // assinar a transação.
+            support::Extrinsic::Unsigned {
+                call: RuntimeCall::proxy(proxy::Call::remove_stale_announcement {
+                    delegate: dev1,
+                    real: dev0.clone(),
+                    call_hash: support::call_hash::<types::Hashing>(&refund),
                 }),
             },
         ],
@@ -115,4 +407,13 @@ fn main() {
     runtime.execute_block(block_3).expect("invalid block 3");
 
     println!("{:?}", runtime);
+    for block_number in 1..=3 {
+        if let Some(root) = runtime.system.state_root(block_number) {
+            println!(
+                "raiz do estado no bloco {}: {}",
+                block_number,
+                hashing::to_hex(&root)
+            );
+        }
+    }
 }
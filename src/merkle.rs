@@ -0,0 +1,178 @@
+use crate::support::Hasher;
+
+/// Um passo de uma prova de inclusão: o hash irmão e o lado em que ele fica.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ProofNode<Hash> {
+    /// O irmão fica à esquerda do nó que está sendo provado.
+    Left(Hash),
+    /// O irmão fica à direita do nó que está sendo provado.
+    Right(Hash),
+}
+
+/// A prova de que uma folha está incluída numa árvore de Merkle: os irmãos do caminho entre a
+/// folha e a raiz, começando pela folha.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct MerkleProof<Hash> {
+    pub path: Vec<ProofNode<Hash>>,
+}
+
+/// Calcula o hash de uma folha. O prefixo distingue folhas de nós internos, de forma que um nó
+/// interno não possa ser apresentado como se fosse uma folha.
+pub fn hash_leaf<H: Hasher>(leaf: &H::Output) -> H::Output {
+    let mut data = vec![0u8];
+    data.extend_from_slice(leaf.as_ref());
+    H::hash(&data)
+}
+
+/// Calcula o hash de um nó interno a partir dos seus dois filhos.
+pub fn hash_node<H: Hasher>(left: &H::Output, right: &H::Output) -> H::Output {
+    let mut data = vec![1u8];
+    data.extend_from_slice(left.as_ref());
+    data.extend_from_slice(right.as_ref());
+    H::hash(&data)
+}
+
+/// Calcula o próximo nível da árvore. Um nó sem par sobe para o próximo nível sem ser alterado.
+fn next_level<H: Hasher>(level: &[H::Output]) -> Vec<H::Output> {
+    level
+        .chunks(2)
+        .map(|pair| match pair {
+            [left, right] => hash_node::<H>(left, right),
+            [single] => *single,
+            _ => unreachable!("chunks(2) has one or two elements"),
+        })
+        .collect()
+}
+
+/// Calcula a raiz da árvore de Merkle sobre `leaves`, na ordem dada.
+/// Retorna `None` se não houver folhas.
+pub fn merkle_root<H: Hasher>(leaves: &[H::Output]) -> Option<H::Output> {
+    let mut level: Vec<H::Output> = leaves.iter().map(hash_leaf::<H>).collect();
+    while level.len() > 1 {
+        level = next_level::<H>(&level);
+    }
+    level.first().copied()
+}
+
+/// Gera a prova de inclusão da folha de posição `index` em `leaves`.
+/// Retorna `None` se `index` estiver fora dos limites.
+pub fn merkle_proof<H: Hasher>(
+    leaves: &[H::Output],
+    index: usize,
+) -> Option<MerkleProof<H::Output>> {
+    if index >= leaves.len() {
+        return None;
+    }
+
+    let mut path = Vec::new();
+    let mut level: Vec<H::Output> = leaves.iter().map(hash_leaf::<H>).collect();
+    let mut index = index;
+    while level.len() > 1 {
+        let sibling = index ^ 1;
+        if let Some(hash) = level.get(sibling) {
+            path.push(if sibling < index {
+                ProofNode::Left(*hash)
+            } else {
+                ProofNode::Right(*hash)
+            });
+        }
+        level = next_level::<H>(&level);
+        index /= 2;
+    }
+    Some(MerkleProof { path })
+}
+
+/// Verifica se `leaf` está incluída na árvore de raiz `root` segundo `proof`.
+pub fn verify_proof<H: Hasher>(
+    root: &H::Output,
+    leaf: &H::Output,
+    proof: &MerkleProof<H::Output>,
+) -> bool {
+    let computed = proof
+        .path
+        .iter()
+        .fold(hash_leaf::<H>(leaf), |node, step| match step {
+            ProofNode::Left(sibling) => hash_node::<H>(sibling, &node),
+            ProofNode::Right(sibling) => hash_node::<H>(&node, sibling),
+        });
+    &computed == root
+}
+
+/// Obtém a posição da folha provada por `proof` numa árvore de `leaf_count` folhas, ou `None` se
+/// `proof` não tiver o formato de uma prova dessa árvore.
+pub fn leaf_index<Hash>(proof: &MerkleProof<Hash>, leaf_count: usize) -> Option<usize> {
+    if leaf_count == 0 {
+        return None;
+    }
+    let mut sizes = vec![leaf_count];
+    while let Some(&size) = sizes.last().filter(|size| **size > 1) {
+        sizes.push(size.div_ceil(2));
+    }
+
+    // Desce da raiz até a folha, consumindo a prova do fim para o começo. Só os nós que têm irmão
+    // no seu nível têm um passo na prova.
+    let mut steps = proof.path.iter().rev();
+    let mut index = 0;
+    for &size in sizes.iter().rev().skip(1) {
+        index *= 2;
+        if index + 1 < size {
+            if let ProofNode::Left(_) = steps.next()? {
+                index += 1;
+            }
+        }
+    }
+    if steps.next().is_some() {
+        return None;
+    }
+    Some(index)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::hashing::Sha256;
+
+    fn leaves(count: u8) -> Vec<[u8; 32]> {
+        (0..count).map(|i| Sha256::hash(&[i])).collect()
+    }
+
+    #[test]
+    fn root_and_proofs() {
+        assert_eq!(merkle_root::<Sha256>(&[]), None);
+
+        // Uma única folha é a própria raiz, com uma prova vazia
+        let one = leaves(1);
+        let root = merkle_root::<Sha256>(&one).unwrap();
+        assert_eq!(root, hash_leaf::<Sha256>(&one[0]));
+        let proof = merkle_proof::<Sha256>(&one, 0).unwrap();
+        assert!(proof.path.is_empty());
+        assert!(verify_proof::<Sha256>(&root, &one[0], &proof));
+        assert_eq!(leaf_index(&proof, 1), Some(0));
+        assert_eq!(leaf_index(&proof, 0), None);
+
+        // Todas as folhas de árvores de vários tamanhos, inclusive ímpares, podem ser provadas
+        for count in 2..=9 {
+            let leaves = leaves(count);
+            let root = merkle_root::<Sha256>(&leaves).unwrap();
+            for (i, leaf) in leaves.iter().enumerate() {
+                let proof = merkle_proof::<Sha256>(&leaves, i).unwrap();
+                assert!(verify_proof::<Sha256>(&root, leaf, &proof));
+                // A prova revela a posição da folha, e só serve para uma árvore desse tamanho
+                assert_eq!(leaf_index(&proof, leaves.len()), Some(i));
+                assert_eq!(leaf_index(&proof, 2 * leaves.len()), None);
+                // A prova de uma folha não serve para outra
+                let other = &leaves[(i + 1) % leaves.len()];
+                assert!(!verify_proof::<Sha256>(&root, other, &proof));
+            }
+            assert_eq!(merkle_proof::<Sha256>(&leaves, leaves.len()), None);
+        }
+
+        // A ordem das folhas faz parte da raiz
+        let mut swapped = leaves(3);
+        swapped.swap(0, 1);
+        assert_ne!(
+            merkle_root::<Sha256>(&swapped),
+            merkle_root::<Sha256>(&leaves(3))
+        );
+    }
+}
@@ -0,0 +1,409 @@
+use crate::support::{
+    call_hash, storage_map, BalanceOf, Dispatch, DispatchResult, GetPallet, Hasher, Hooks,
+    NamedReservableCurrency, Origin, ReserveIdentifier, Storage,
+};
+use crate::system;
+use core::fmt::Debug;
+use std::collections::BTreeMap;
+
+/// O identificador da reserva que guarda os depósitos das operações pendentes.
+pub const MULTISIG_RESERVE_ID: ReserveIdentifier = *b"multisig";
+
+/// O tipo de saldo dos depósitos das operações pendentes.
+pub type DepositOf<T> = BalanceOf<<T as Config>::Currency, T>;
+
+/// Os eventos emitidos pelo Módulo Multisig.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Event<T: Config> {
+    /// `approving` abriu uma operação para a chamada de hash `call_hash` da conta `multisig`.
+    NewMultisig {
+        approving: T::AccountId,
+        multisig: T::AccountId,
+        call_hash: T::Hash,
+    },
+    /// `approving` aprovou a operação pendente.
+    MultisigApproval {
+        approving: T::AccountId,
+        multisig: T::AccountId,
+        call_hash: T::Hash,
+    },
+    /// A aprovação de `approving` completou o limiar, e a chamada foi despachada pela conta
+    /// `multisig` com o resultado `result`.
+    MultisigExecuted {
+        approving: T::AccountId,
+        multisig: T::AccountId,
+        call_hash: T::Hash,
+        result: DispatchResult,
+    },
+    /// `cancelling` cancelou a operação pendente.
+    MultisigCancelled {
+        cancelling: T::AccountId,
+        multisig: T::AccountId,
+        call_hash: T::Hash,
+    },
+}
+
+/// Uma operação pendente: uma chamada da conta multisig que ainda não tem aprovações suficientes.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Multisig<AccountId, BlockNumber, Balance> {
+    /// O bloco em que a operação foi aberta.
+    pub when: BlockNumber,
+    /// A conta que abriu a operação e pagou o depósito.
+    pub depositor: AccountId,
+    /// O depósito reservado de `depositor` enquanto a operação estiver pendente.
+    pub deposit: Balance,
+    /// Os signatários que já aprovaram a operação, em ordem.
+    pub approvals: Vec<AccountId>,
+}
+
+/// A característica de configuração do Módulo Multisig.
+pub trait Config:
+    system::Config
+    + Sized
+    + Dispatch<Caller = Origin<Self::AccountId>, Call = Self::RuntimeCall>
+    + GetPallet<Pallet<Self>>
+    + GetPallet<system::Pallet<Self>>
+{
+    /// O tipo das chamadas do runtime, que podem ser de qualquer pallet.
+    type RuntimeCall: Debug;
+    /// A moeda da qual é reservado o depósito de cada operação pendente.
+    type Currency: NamedReservableCurrency<Self>;
+    /// O depósito reservado de quem abre uma operação, enquanto ela estiver pendente.
+    const MULTISIG_DEPOSIT: DepositOf<Self>;
+    /// O número máximo de signatários de uma conta multisig.
+    const MAX_SIGNATORIES: u32;
+
+    /// Converte o hash que identifica uma conta multisig na própria conta. Essas contas não
+    /// pertencem a nenhuma chave: só podem ser usadas através deste pallet.
+    fn account_from_hash(hash: &Self::Hash) -> Self::AccountId;
+}
+
+/// Este é o Módulo Multisig.
+/// Um conjunto de signatários e um limiar determinam uma conta multisig, que faz uma chamada
+/// quando pelo menos `threshold` signatários a aprovam.
+#[derive(Debug, Clone)]
+pub struct Pallet<T: Config> {
+    /// As operações pendentes, indexadas pela conta multisig e pelo hash da chamada.
+    #[allow(clippy::type_complexity)]
+    multisigs:
+        BTreeMap<(T::AccountId, T::Hash), Multisig<T::AccountId, T::BlockNumber, DepositOf<T>>>,
+    /// Os eventos emitidos por este pallet no bloco atual, em ordem.
+    events: Vec<Event<T>>,
+}
+
+#[allow(clippy::boxed_local)]
+#[macros::call]
+impl<T: Config> Pallet<T> {
+    /// Aprova `call` em nome da conta multisig de `caller` e `other_signatories`, com o limiar
+    /// `threshold`. Se a aprovação completar o limiar, `call` é despachada pela conta multisig e
+    /// o resultado é registrado no evento `MultisigExecuted`. Caso contrário, a aprovação é
+    /// registrada, e quem abre a operação paga o depósito `MULTISIG_DEPOSIT`.
+    /// `other_signatories` precisa estar em ordem, sem repetições e sem `caller`.
+    pub fn as_multi(
+        runtime: &mut T,
+        caller: T::AccountId,
+        threshold: u32,
+        other_signatories: Vec<T::AccountId>,
+        call: Box<T::RuntimeCall>,
+    ) -> DispatchResult {
+        let multisig = Self::ensure_signatory(&caller, threshold, other_signatories)?;
+        let call_hash = call_hash::<T::Hashing>(&call);
+        let key = (multisig.clone(), call_hash);
+
+        let pallet: &mut Self = runtime.pallet_mut();
+        let approvals = match pallet.multisigs.get(&key) {
+            Some(op) if op.approvals.contains(&caller) => op.approvals.len(),
+            Some(op) => op.approvals.len() + 1,
+            None => 1,
+        };
+        if approvals < threshold as usize {
+            return Self::approve(runtime, caller, multisig, call_hash);
+        }
+
+        if let Some(op) = pallet.multisigs.remove(&key) {
+            T::Currency::unreserve_named(runtime, &MULTISIG_RESERVE_ID, &op.depositor, op.deposit);
+        }
+        let result = runtime.dispatch(Origin::Signed(multisig.clone()), *call);
+        Self::deposit_event(
+            runtime,
+            Event::MultisigExecuted {
+                approving: caller,
+                multisig,
+                call_hash,
+                result,
+            },
+        );
+        Ok(())
+    }
+
+    /// Aprova a chamada de hash `call_hash` em nome da conta multisig, como `as_multi`, mas sem
+    /// despachá-la: quando o limiar for atingido, a chamada ainda precisa ser enviada por um
+    /// signatário com `as_multi`.
+    pub fn approve_as_multi(
+        runtime: &mut T,
+        caller: T::AccountId,
+        threshold: u32,
+        other_signatories: Vec<T::AccountId>,
+        call_hash: T::Hash,
+    ) -> DispatchResult {
+        let multisig = Self::ensure_signatory(&caller, threshold, other_signatories)?;
+        Self::approve(runtime, caller, multisig, call_hash)
+    }
+
+    /// Cancela a operação pendente da chamada de hash `call_hash` e devolve o depósito. Apenas
+    /// quem abriu a operação pode cancelá-la.
+    pub fn cancel_as_multi(
+        runtime: &mut T,
+        caller: T::AccountId,
+        threshold: u32,
+        other_signatories: Vec<T::AccountId>,
+        call_hash: T::Hash,
+    ) -> DispatchResult {
+        let multisig = Self::ensure_signatory(&caller, threshold, other_signatories)?;
+        let key = (multisig.clone(), call_hash);
+
+        let pallet: &mut Self = runtime.pallet_mut();
+        let op = pallet
+            .multisigs
+            .get(&key)
+            .ok_or("No pending operation for this call.")?;
+        if op.depositor != caller {
+            return Err("Only the depositor can cancel the operation.");
+        }
+        let deposit = op.deposit;
+        pallet.multisigs.remove(&key);
+
+        T::Currency::unreserve_named(runtime, &MULTISIG_RESERVE_ID, &caller, deposit);
+        Self::deposit_event(
+            runtime,
+            Event::MultisigCancelled {
+                cancelling: caller,
+                multisig,
+                call_hash,
+            },
+        );
+        Ok(())
+    }
+}
+
+impl<T: Config> Pallet<T> {
+    /// Cria uma nova instância do Módulo Multisig.
+    pub fn new() -> Self {
+        Self {
+            multisigs: BTreeMap::new(),
+            events: Vec::new(),
+        }
+    }
+
+    /// Deriva a conta multisig de `signatories`, em qualquer ordem, com o limiar `threshold`.
+    /// A mesma combinação sempre resulta na mesma conta.
+    pub fn multi_account_id(signatories: &[T::AccountId], threshold: u32) -> T::AccountId {
+        let mut signatories = signatories.to_vec();
+        signatories.sort();
+        let data = format!("multisig:{:?}:{}", signatories, threshold);
+        T::account_from_hash(&T::Hashing::hash(data.as_bytes()))
+    }
+
+    /// Obtém a operação pendente da conta `multisig` para a chamada de hash `call_hash`, se houver.
+    pub fn multisig(
+        &self,
+        multisig: &T::AccountId,
+        call_hash: &T::Hash,
+    ) -> Option<&Multisig<T::AccountId, T::BlockNumber, DepositOf<T>>> {
+        self.multisigs.get(&(multisig.clone(), *call_hash))
+    }
+
+    /// Obtém os eventos emitidos por este pallet no bloco atual, em ordem.
+    pub fn events(&self) -> &[Event<T>] {
+        &self.events
+    }
+
+    /// Registra um evento emitido por este pallet.
+    fn deposit_event(runtime: &mut T, event: Event<T>) {
+        let pallet: &mut Self = runtime.pallet_mut();
+        pallet.events.push(event);
+    }
+
+    /// Valida os signatários e o limiar, e retorna a conta multisig de `caller` e
+    /// `other_signatories`.
+    fn ensure_signatory(
+        caller: &T::AccountId,
+        threshold: u32,
+        mut other_signatories: Vec<T::AccountId>,
+    ) -> Result<T::AccountId, &'static str> {
+        if threshold < 2 {
+            return Err("Threshold must be at least two.");
+        }
+        if !other_signatories.windows(2).all(|pair| pair[0] < pair[1]) {
+            return Err("Signatories must be sorted and unique.");
+        }
+        if other_signatories.contains(caller) {
+            return Err("The caller must not be among the other signatories.");
+        }
+        other_signatories.push(caller.clone());
+        if other_signatories.len() > T::MAX_SIGNATORIES as usize {
+            return Err("Too many signatories.");
+        }
+        if threshold as usize > other_signatories.len() {
+            return Err("Threshold exceeds the number of signatories.");
+        }
+        Ok(Self::multi_account_id(&other_signatories, threshold))
+    }
+
+    /// Registra a aprovação de `caller` para a chamada de hash `call_hash` da conta `multisig`,
+    /// abrindo a operação, e reservando o depósito, se ela ainda não existir.
+    fn approve(
+        runtime: &mut T,
+        caller: T::AccountId,
+        multisig: T::AccountId,
+        call_hash: T::Hash,
+    ) -> DispatchResult {
+        let key = (multisig.clone(), call_hash);
+        let pallet: &mut Self = runtime.pallet_mut();
+        if let Some(op) = pallet.multisigs.get_mut(&key) {
+            if op.approvals.contains(&caller) {
+                return Err("Already approved.");
+            }
+            let index = op.approvals.partition_point(|who| who < &caller);
+            op.approvals.insert(index, caller.clone());
+            Self::deposit_event(
+                runtime,
+                Event::MultisigApproval {
+                    approving: caller,
+                    multisig,
+                    call_hash,
+                },
+            );
+            return Ok(());
+        }
+
+        T::Currency::reserve_named(runtime, &MULTISIG_RESERVE_ID, &caller, T::MULTISIG_DEPOSIT)?;
+        let system: &system::Pallet<T> = runtime.pallet();
+        let op = Multisig {
+            when: system.block_number(),
+            depositor: caller.clone(),
+            deposit: T::MULTISIG_DEPOSIT,
+            approvals: vec![caller.clone()],
+        };
+        let pallet: &mut Self = runtime.pallet_mut();
+        pallet.multisigs.insert(key, op);
+        Self::deposit_event(
+            runtime,
+            Event::NewMultisig {
+                approving: caller,
+                multisig,
+                call_hash,
+            },
+        );
+        Ok(())
+    }
+}
+
+impl<T: Config> Hooks<T> for Pallet<T> {
+    fn on_initialize(runtime: &mut T, _n: T::BlockNumber) {
+        let pallet: &mut Self = runtime.pallet_mut();
+        pallet.events.clear();
+    }
+}
+
+impl<T: Config> Storage for Pallet<T> {
+    fn storage(&self) -> Vec<(Vec<u8>, Vec<u8>)> {
+        storage_map("multisigs", &self.multisigs).collect()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::tests::{new_runtime, transfer};
+    use crate::{support, types, Runtime};
+
+    type Multi = Pallet<Runtime>;
+
+    /// Os signatários, e a conta multisig 2 de 3 deles, que já tem saldo.
+    fn with_treasury() -> (Runtime, [String; 3], String) {
+        let (mut runtime, accounts) = new_runtime(["alice", "bob", "charlie"], 100);
+        let treasury = Multi::multi_account_id(&accounts, 2);
+        runtime.balances.set_balance(&treasury, 1_000);
+        (runtime, accounts, treasury)
+    }
+
+    #[test]
+    fn multi_account_id() {
+        let [alice, bob, charlie] = ["alice", "bob", "charlie"].map(String::from);
+
+        // A conta multisig não depende da ordem dos signatários, mas depende do limiar
+        let signatories = [alice.clone(), bob.clone(), charlie.clone()];
+        let treasury = Multi::multi_account_id(&signatories, 2);
+        let reversed = [charlie, bob, alice];
+        assert_eq!(Multi::multi_account_id(&reversed, 2), treasury);
+        assert_ne!(Multi::multi_account_id(&signatories, 3), treasury);
+    }
+
+    #[test]
+    fn invalid_signatories_and_threshold() {
+        let (mut runtime, [alice, bob, charlie], _) = with_treasury();
+
+        let others = vec![charlie.clone(), bob.clone()];
+        let res = Multi::as_multi(&mut runtime, alice.clone(), 2, others, transfer("dave", 1));
+        assert_eq!(res, Err("Signatories must be sorted and unique."));
+        let others = vec![bob.clone(), charlie.clone()];
+        let res = Multi::as_multi(&mut runtime, alice.clone(), 1, others, transfer("dave", 1));
+        assert_eq!(res, Err("Threshold must be at least two."));
+        let others = vec![bob, charlie];
+        let res = Multi::as_multi(&mut runtime, alice, 4, others, transfer("dave", 1));
+        assert_eq!(res, Err("Threshold exceeds the number of signatories."));
+    }
+
+    #[test]
+    fn two_of_three() {
+        let (mut runtime, [alice, bob, charlie], treasury) = with_treasury();
+
+        // A primeira aprovação abre a operação e reserva o depósito
+        let call_hash = support::call_hash::<types::Hashing>(&transfer("dave", 100));
+        let others = vec![bob.clone(), charlie.clone()];
+        let res = Multi::approve_as_multi(&mut runtime, alice.clone(), 2, others, call_hash);
+        assert_eq!(res, Ok(()));
+        assert_eq!(runtime.balances.reserved_balance(&alice), 10);
+        let op = runtime.multisig.multisig(&treasury, &call_hash).unwrap();
+        assert_eq!(op.approvals, ["alice"]);
+        let others = vec![bob.clone(), charlie.clone()];
+        let res = Multi::approve_as_multi(&mut runtime, alice.clone(), 2, others, call_hash);
+        assert_eq!(res, Err("Already approved."));
+
+        // A segunda aprovação atinge o limiar e despacha a chamada pela conta multisig
+        let others = vec![alice.clone(), bob];
+        let res = Multi::as_multi(&mut runtime, charlie, 2, others, transfer("dave", 100));
+        assert_eq!(res, Ok(()));
+        assert_eq!(runtime.balances.balance(&treasury), 900);
+        assert_eq!(runtime.balances.balance(&"dave".to_string()), 100);
+        assert_eq!(runtime.balances.reserved_balance(&alice), 0);
+        assert_eq!(runtime.multisig.multisig(&treasury, &call_hash), None);
+        assert!(matches!(
+            runtime.multisig.events(),
+            [
+                Event::NewMultisig { .. },
+                Event::MultisigExecuted { result: Ok(()), .. }
+            ]
+        ));
+    }
+
+    #[test]
+    fn only_depositor_cancels() {
+        let (mut runtime, [alice, bob, charlie], treasury) = with_treasury();
+
+        let call_hash = support::call_hash::<types::Hashing>(&transfer("dave", 1));
+        let others = vec![alice.clone(), charlie.clone()];
+        let res = Multi::as_multi(&mut runtime, bob.clone(), 2, others, transfer("dave", 1));
+        assert_eq!(res, Ok(()));
+        assert_eq!(runtime.balances.reserved_balance(&bob), 10);
+        let others = vec![bob.clone(), charlie.clone()];
+        let res = Multi::cancel_as_multi(&mut runtime, alice.clone(), 2, others, call_hash);
+        assert_eq!(res, Err("Only the depositor can cancel the operation."));
+        let others = vec![alice, charlie];
+        let res = Multi::cancel_as_multi(&mut runtime, bob.clone(), 2, others, call_hash);
+        assert_eq!(res, Ok(()));
+        assert_eq!(runtime.balances.reserved_balance(&bob), 0);
+        assert_eq!(runtime.multisig.multisig(&treasury, &call_hash), None);
+    }
+}
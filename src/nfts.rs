@@ -0,0 +1,849 @@
+use crate::support::{
+    storage_map, BalanceOf, DispatchResult, GetPallet, Hooks, NamedReservableCurrency,
+    ReserveIdentifier, Storage,
+};
+use crate::system;
+use core::fmt::Debug;
+use std::collections::BTreeMap;
+
+/// O identificador da reserva que guarda os depósitos das coleções e dos itens.
+pub const NFTS_RESERVE_ID: ReserveIdentifier = *b"nfts_dep";
+
+/// O tipo de saldo dos depósitos das coleções e dos itens.
+pub type DepositOf<T> = BalanceOf<<T as Config>::Currency, T>;
+
+/// Os eventos emitidos pelo Módulo de NFTs.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Event<T: Config> {
+    /// A coleção `collection` foi criada por `owner`, com `admin` como administrador.
+    Created {
+        collection: T::CollectionId,
+        owner: T::AccountId,
+        admin: T::AccountId,
+    },
+    /// A coleção foi destruída.
+    Destroyed { collection: T::CollectionId },
+    /// O administrador da coleção passou a ser `admin`.
+    AdminChanged {
+        collection: T::CollectionId,
+        admin: T::AccountId,
+    },
+    /// A coleção passou a ter no máximo `max_supply` itens.
+    MaxSupplySet {
+        collection: T::CollectionId,
+        max_supply: u32,
+    },
+    /// O item `item` da coleção foi emitido para `owner`.
+    Issued {
+        collection: T::CollectionId,
+        item: T::ItemId,
+        owner: T::AccountId,
+    },
+    /// O item foi transferido de `from` para `to`.
+    Transferred {
+        collection: T::CollectionId,
+        item: T::ItemId,
+        from: T::AccountId,
+        to: T::AccountId,
+    },
+    /// O item de `owner` foi queimado.
+    Burned {
+        collection: T::CollectionId,
+        item: T::ItemId,
+        owner: T::AccountId,
+    },
+    /// `owner` autorizou `delegate` a transferir o item.
+    TransferApproved {
+        collection: T::CollectionId,
+        item: T::ItemId,
+        owner: T::AccountId,
+        delegate: T::AccountId,
+    },
+    /// `owner` retirou a autorização de `delegate` para transferir o item.
+    ApprovalCancelled {
+        collection: T::CollectionId,
+        item: T::ItemId,
+        owner: T::AccountId,
+        delegate: T::AccountId,
+    },
+    /// Os metadados do item passaram a ser `data`.
+    ItemMetadataSet {
+        collection: T::CollectionId,
+        item: T::ItemId,
+        data: Vec<u8>,
+    },
+    /// Os metadados do item foram apagados.
+    ItemMetadataCleared {
+        collection: T::CollectionId,
+        item: T::ItemId,
+    },
+    /// O atributo `key` do item passou a ser `value`.
+    AttributeSet {
+        collection: T::CollectionId,
+        item: T::ItemId,
+        key: Vec<u8>,
+        value: Vec<u8>,
+    },
+    /// O atributo `key` do item foi apagado.
+    AttributeCleared {
+        collection: T::CollectionId,
+        item: T::ItemId,
+        key: Vec<u8>,
+    },
+}
+
+/// Os dados de uma coleção.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CollectionDetails<AccountId, Balance> {
+    /// A conta que criou a coleção. Paga os depósitos, e pode destruir a coleção e alterar o
+    /// administrador e o limite de itens.
+    pub owner: AccountId,
+    /// A conta que, além do proprietário, pode emitir itens e definir os seus metadados e
+    /// atributos.
+    pub admin: AccountId,
+    /// O número de itens da coleção que existem.
+    pub items: u32,
+    /// O número máximo de itens da coleção, se houver.
+    pub max_supply: Option<u32>,
+    /// O depósito reservado do proprietário pela coleção, sem contar os dos itens.
+    pub deposit: Balance,
+}
+
+/// Os dados de um item.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ItemDetails<AccountId, Balance> {
+    /// A conta que possui o item.
+    pub owner: AccountId,
+    /// A conta autorizada pelo proprietário a transferir o item, se houver.
+    pub approved: Option<AccountId>,
+    /// O depósito reservado do proprietário da coleção pelo item.
+    pub deposit: Balance,
+}
+
+/// A característica de configuração do Módulo de NFTs.
+pub trait Config: system::Config + Sized + GetPallet<Pallet<Self>> {
+    /// O identificador de uma coleção.
+    type CollectionId: Debug + Ord + Clone;
+    /// O identificador de um item dentro da sua coleção.
+    type ItemId: Debug + Ord + Clone;
+    /// A moeda da qual são reservados os depósitos.
+    type Currency: NamedReservableCurrency<Self>;
+    /// O depósito reservado de quem cria uma coleção, enquanto ela existir.
+    const COLLECTION_DEPOSIT: DepositOf<Self>;
+    /// O depósito reservado do proprietário da coleção por item, enquanto o item existir.
+    const ITEM_DEPOSIT: DepositOf<Self>;
+    /// O tamanho máximo, em bytes, dos metadados de um item e das chaves e valores dos atributos.
+    const STRING_LIMIT: u32;
+    /// O número máximo de atributos de um item.
+    const MAX_ATTRIBUTES: u32;
+}
+
+/// Este é o Módulo de NFTs.
+/// As contas criam coleções de tokens não fungíveis, em que cada item é único, tem um único
+/// proprietário e pode ser transferido, e carrega metadados e atributos definidos pela coleção.
+#[derive(Debug, Clone)]
+pub struct Pallet<T: Config> {
+    /// Os dados de cada coleção que existe.
+    collections: BTreeMap<T::CollectionId, CollectionDetails<T::AccountId, DepositOf<T>>>,
+    /// Os dados de cada item que existe, indexados por `(collection, item)`.
+    #[allow(clippy::type_complexity)]
+    items: BTreeMap<(T::CollectionId, T::ItemId), ItemDetails<T::AccountId, DepositOf<T>>>,
+    /// Os metadados de cada item, indexados por `(collection, item)`.
+    item_metadata: BTreeMap<(T::CollectionId, T::ItemId), Vec<u8>>,
+    /// Os atributos de cada item, indexados por `(collection, item, key)`.
+    #[allow(clippy::type_complexity)]
+    attributes: BTreeMap<(T::CollectionId, T::ItemId, Vec<u8>), Vec<u8>>,
+    /// Os eventos emitidos por este pallet no bloco atual, em ordem.
+    events: Vec<Event<T>>,
+}
+
+#[macros::call]
+impl<T: Config> Pallet<T> {
+    /// Cria a coleção `collection`, tendo `caller` como proprietário e `admin` como
+    /// administrador, e reserva `COLLECTION_DEPOSIT` de `caller`.
+    pub fn create(
+        runtime: &mut T,
+        caller: T::AccountId,
+        collection: T::CollectionId,
+        admin: T::AccountId,
+    ) -> DispatchResult {
+        let pallet: &Self = runtime.pallet();
+        if pallet.collections.contains_key(&collection) {
+            return Err("Collection already exists.");
+        }
+
+        let deposit = T::COLLECTION_DEPOSIT;
+        T::Currency::reserve_named(runtime, &NFTS_RESERVE_ID, &caller, deposit)?;
+        let pallet: &mut Self = runtime.pallet_mut();
+        let details = CollectionDetails {
+            owner: caller.clone(),
+            admin: admin.clone(),
+            items: 0,
+            max_supply: None,
+            deposit,
+        };
+        pallet.collections.insert(collection.clone(), details);
+        pallet.events.push(Event::Created {
+            collection,
+            owner: caller,
+            admin,
+        });
+        Ok(())
+    }
+
+    /// Destrói a coleção e devolve o seu depósito. Apenas o proprietário da coleção pode
+    /// destruí-la, e só depois que todos os seus itens forem queimados.
+    pub fn destroy(
+        runtime: &mut T,
+        caller: T::AccountId,
+        collection: T::CollectionId,
+    ) -> DispatchResult {
+        let pallet: &mut Self = runtime.pallet_mut();
+        let details = pallet.ensure_owner(&caller, &collection)?;
+        if details.items > 0 {
+            return Err("The collection still has items.");
+        }
+        let deposit = details.deposit;
+        pallet.collections.remove(&collection);
+        pallet.events.push(Event::Destroyed { collection });
+
+        T::Currency::unreserve_named(runtime, &NFTS_RESERVE_ID, &caller, deposit);
+        Ok(())
+    }
+
+    /// Passa a administração da coleção para `admin`. Apenas o proprietário da coleção pode
+    /// fazer esta chamada.
+    pub fn set_admin(
+        &mut self,
+        caller: T::AccountId,
+        collection: T::CollectionId,
+        admin: T::AccountId,
+    ) -> DispatchResult {
+        self.ensure_owner(&caller, &collection)?;
+        if let Some(details) = self.collections.get_mut(&collection) {
+            details.admin = admin.clone();
+        }
+        self.events.push(Event::AdminChanged { collection, admin });
+        Ok(())
+    }
+
+    /// Limita a coleção a `max_supply` itens, que não pode ser menos do que os itens que já
+    /// existem. Apenas o proprietário da coleção pode fazer esta chamada.
+    pub fn set_max_supply(
+        &mut self,
+        caller: T::AccountId,
+        collection: T::CollectionId,
+        max_supply: u32,
+    ) -> DispatchResult {
+        let details = self.ensure_owner(&caller, &collection)?;
+        if max_supply < details.items {
+            return Err("Max supply is below the current supply.");
+        }
+        if let Some(details) = self.collections.get_mut(&collection) {
+            details.max_supply = Some(max_supply);
+        }
+        self.events.push(Event::MaxSupplySet {
+            collection,
+            max_supply,
+        });
+        Ok(())
+    }
+
+    /// Emite o item `item` da coleção para `owner`, reservando `ITEM_DEPOSIT` do proprietário da
+    /// coleção. Apenas o proprietário ou o administrador da coleção podem emitir itens.
+    pub fn mint(
+        runtime: &mut T,
+        caller: T::AccountId,
+        collection: T::CollectionId,
+        item: T::ItemId,
+        owner: T::AccountId,
+    ) -> DispatchResult {
+        let pallet: &Self = runtime.pallet();
+        let details = pallet.ensure_manager(&caller, &collection)?;
+        if details
+            .max_supply
+            .is_some_and(|max_supply| details.items >= max_supply)
+        {
+            return Err("The collection has reached its max supply.");
+        }
+        let key = (collection.clone(), item.clone());
+        if pallet.items.contains_key(&key) {
+            return Err("Item already exists.");
+        }
+
+        let deposit = T::ITEM_DEPOSIT;
+        let collection_owner = details.owner.clone();
+        T::Currency::reserve_named(runtime, &NFTS_RESERVE_ID, &collection_owner, deposit)?;
+        let pallet: &mut Self = runtime.pallet_mut();
+        if let Some(details) = pallet.collections.get_mut(&collection) {
+            details.items += 1;
+        }
+        let details = ItemDetails {
+            owner: owner.clone(),
+            approved: None,
+            deposit,
+        };
+        pallet.items.insert(key, details);
+        pallet.events.push(Event::Issued {
+            collection,
+            item,
+            owner,
+        });
+        Ok(())
+    }
+
+    /// Queima o item, apagando os seus metadados e atributos, e devolve o depósito ao
+    /// proprietário da coleção. Apenas o proprietário do item pode queimá-lo: quem administra a
+    /// coleção não pode tirar um item de quem o possui.
+    pub fn burn(
+        runtime: &mut T,
+        caller: T::AccountId,
+        collection: T::CollectionId,
+        item: T::ItemId,
+    ) -> DispatchResult {
+        let pallet: &mut Self = runtime.pallet_mut();
+        let details = pallet.ensure_item(&collection, &item)?;
+        if details.owner != caller {
+            return Err("No permission.");
+        }
+        let key = (collection.clone(), item.clone());
+        let Some(details) = pallet.items.remove(&key) else {
+            return Err("Unknown item.");
+        };
+        pallet.item_metadata.remove(&key);
+        let attributes: Vec<Vec<u8>> = pallet
+            .attributes_of(&collection, &item)
+            .map(|(key, _)| key.to_vec())
+            .collect();
+        for attribute in attributes {
+            pallet
+                .attributes
+                .remove(&(collection.clone(), item.clone(), attribute));
+        }
+        let collection_details = pallet
+            .collections
+            .get_mut(&collection)
+            .ok_or("Unknown collection.")?;
+        collection_details.items -= 1;
+        let collection_owner = collection_details.owner.clone();
+        pallet.events.push(Event::Burned {
+            collection,
+            item,
+            owner: details.owner,
+        });
+
+        T::Currency::unreserve_named(
+            runtime,
+            &NFTS_RESERVE_ID,
+            &collection_owner,
+            details.deposit,
+        );
+        Ok(())
+    }
+
+    /// Transfere o item para `dest`. Apenas o proprietário do item, ou a conta autorizada por
+    /// ele, podem transferi-lo, e a autorização deixa de valer depois da transferência.
+    pub fn transfer(
+        &mut self,
+        caller: T::AccountId,
+        collection: T::CollectionId,
+        item: T::ItemId,
+        dest: T::AccountId,
+    ) -> DispatchResult {
+        let details = self.ensure_item(&collection, &item)?;
+        if details.owner != caller && details.approved.as_ref() != Some(&caller) {
+            return Err("No permission.");
+        }
+        let key = (collection.clone(), item.clone());
+        let Some(details) = self.items.get_mut(&key) else {
+            return Err("Unknown item.");
+        };
+        let from = core::mem::replace(&mut details.owner, dest.clone());
+        details.approved = None;
+        self.events.push(Event::Transferred {
+            collection,
+            item,
+            from,
+            to: dest,
+        });
+        Ok(())
+    }
+
+    /// Autoriza `delegate` a transferir o item, substituindo qualquer autorização anterior.
+    /// Apenas o proprietário do item pode fazer esta chamada.
+    pub fn approve_transfer(
+        &mut self,
+        caller: T::AccountId,
+        collection: T::CollectionId,
+        item: T::ItemId,
+        delegate: T::AccountId,
+    ) -> DispatchResult {
+        let details = self.ensure_item_owner(&caller, &collection, &item)?;
+        details.approved = Some(delegate.clone());
+        self.events.push(Event::TransferApproved {
+            collection,
+            item,
+            owner: caller,
+            delegate,
+        });
+        Ok(())
+    }
+
+    /// Retira a autorização de transferência do item. Apenas o proprietário do item pode fazer
+    /// esta chamada.
+    pub fn cancel_approval(
+        &mut self,
+        caller: T::AccountId,
+        collection: T::CollectionId,
+        item: T::ItemId,
+    ) -> DispatchResult {
+        let details = self.ensure_item_owner(&caller, &collection, &item)?;
+        let delegate = details.approved.take().ok_or("No approval to cancel.")?;
+        self.events.push(Event::ApprovalCancelled {
+            collection,
+            item,
+            owner: caller,
+            delegate,
+        });
+        Ok(())
+    }
+
+    /// Define os metadados do item como `data`. Apenas o proprietário ou o administrador da
+    /// coleção podem fazer esta chamada.
+    pub fn set_metadata(
+        &mut self,
+        caller: T::AccountId,
+        collection: T::CollectionId,
+        item: T::ItemId,
+        data: Vec<u8>,
+    ) -> DispatchResult {
+        self.ensure_manager(&caller, &collection)?;
+        self.ensure_item(&collection, &item)?;
+        if data.len() > T::STRING_LIMIT as usize {
+            return Err("Metadata too long.");
+        }
+        let key = (collection.clone(), item.clone());
+        self.item_metadata.insert(key, data.clone());
+        self.events.push(Event::ItemMetadataSet {
+            collection,
+            item,
+            data,
+        });
+        Ok(())
+    }
+
+    /// Apaga os metadados do item. Apenas o proprietário ou o administrador da coleção podem
+    /// fazer esta chamada.
+    pub fn clear_metadata(
+        &mut self,
+        caller: T::AccountId,
+        collection: T::CollectionId,
+        item: T::ItemId,
+    ) -> DispatchResult {
+        self.ensure_manager(&caller, &collection)?;
+        let key = (collection.clone(), item.clone());
+        if self.item_metadata.remove(&key).is_none() {
+            return Err("No metadata to clear.");
+        }
+        self.events
+            .push(Event::ItemMetadataCleared { collection, item });
+        Ok(())
+    }
+
+    /// Define o atributo `key` do item como `value`. Apenas o proprietário ou o administrador da
+    /// coleção podem fazer esta chamada.
+    pub fn set_attribute(
+        &mut self,
+        caller: T::AccountId,
+        collection: T::CollectionId,
+        item: T::ItemId,
+        key: Vec<u8>,
+        value: Vec<u8>,
+    ) -> DispatchResult {
+        self.ensure_manager(&caller, &collection)?;
+        self.ensure_item(&collection, &item)?;
+        let limit = T::STRING_LIMIT as usize;
+        if key.len() > limit || value.len() > limit {
+            return Err("Attribute too long.");
+        }
+        let attribute = (collection.clone(), item.clone(), key.clone());
+        if !self.attributes.contains_key(&attribute)
+            && self.attributes_of(&collection, &item).count() >= T::MAX_ATTRIBUTES as usize
+        {
+            return Err("Too many attributes.");
+        }
+        self.attributes.insert(attribute, value.clone());
+        self.events.push(Event::AttributeSet {
+            collection,
+            item,
+            key,
+            value,
+        });
+        Ok(())
+    }
+
+    /// Apaga o atributo `key` do item. Apenas o proprietário ou o administrador da coleção
+    /// podem fazer esta chamada.
+    pub fn clear_attribute(
+        &mut self,
+        caller: T::AccountId,
+        collection: T::CollectionId,
+        item: T::ItemId,
+        key: Vec<u8>,
+    ) -> DispatchResult {
+        self.ensure_manager(&caller, &collection)?;
+        let attribute = (collection.clone(), item.clone(), key.clone());
+        if self.attributes.remove(&attribute).is_none() {
+            return Err("No attribute to clear.");
+        }
+        self.events.push(Event::AttributeCleared {
+            collection,
+            item,
+            key,
+        });
+        Ok(())
+    }
+}
+
+impl<T: Config> Pallet<T> {
+    /// Cria uma nova instância do Módulo de NFTs.
+    pub fn new() -> Self {
+        Self {
+            collections: BTreeMap::new(),
+            items: BTreeMap::new(),
+            item_metadata: BTreeMap::new(),
+            attributes: BTreeMap::new(),
+            events: Vec::new(),
+        }
+    }
+
+    /// Obtém os dados de uma coleção, se ela existir.
+    pub fn collection(
+        &self,
+        collection: &T::CollectionId,
+    ) -> Option<&CollectionDetails<T::AccountId, DepositOf<T>>> {
+        self.collections.get(collection)
+    }
+
+    /// Obtém os dados de um item, se ele existir.
+    pub fn item(
+        &self,
+        collection: &T::CollectionId,
+        item: &T::ItemId,
+    ) -> Option<&ItemDetails<T::AccountId, DepositOf<T>>> {
+        self.items.get(&(collection.clone(), item.clone()))
+    }
+
+    /// Obtém o proprietário de um item, se ele existir.
+    pub fn owner(&self, collection: &T::CollectionId, item: &T::ItemId) -> Option<&T::AccountId> {
+        self.item(collection, item).map(|details| &details.owner)
+    }
+
+    /// Obtém os metadados de um item, se tiverem sido definidos.
+    pub fn metadata(&self, collection: &T::CollectionId, item: &T::ItemId) -> Option<&[u8]> {
+        self.item_metadata
+            .get(&(collection.clone(), item.clone()))
+            .map(Vec::as_slice)
+    }
+
+    /// Obtém o atributo `key` de um item, se tiver sido definido.
+    pub fn attribute(
+        &self,
+        collection: &T::CollectionId,
+        item: &T::ItemId,
+        key: &[u8],
+    ) -> Option<&[u8]> {
+        self.attributes
+            .get(&(collection.clone(), item.clone(), key.to_vec()))
+            .map(Vec::as_slice)
+    }
+
+    /// Obtém os atributos de um item, como pares de chave e valor, em ordem de chave. Os
+    /// atributos de um item são vizinhos no mapa, a partir da chave vazia, então apenas eles são
+    /// percorridos.
+    pub fn attributes_of<'a>(
+        &'a self,
+        collection: &'a T::CollectionId,
+        item: &'a T::ItemId,
+    ) -> impl Iterator<Item = (&'a [u8], &'a [u8])> + 'a {
+        self.attributes
+            .range((collection.clone(), item.clone(), Vec::new())..)
+            .take_while(move |((c, i, _), _)| c == collection && i == item)
+            .map(|((_, _, key), value)| (key.as_slice(), value.as_slice()))
+    }
+
+    /// Obtém os eventos emitidos por este pallet no bloco atual, em ordem.
+    pub fn events(&self) -> &[Event<T>] {
+        &self.events
+    }
+
+    /// Obtém os dados da coleção, ou um erro se ela não existir.
+    fn ensure_collection(
+        &self,
+        collection: &T::CollectionId,
+    ) -> Result<&CollectionDetails<T::AccountId, DepositOf<T>>, &'static str> {
+        self.collections
+            .get(collection)
+            .ok_or("Unknown collection.")
+    }
+
+    /// Garante que `who` é o proprietário da coleção.
+    fn ensure_owner(
+        &self,
+        who: &T::AccountId,
+        collection: &T::CollectionId,
+    ) -> Result<&CollectionDetails<T::AccountId, DepositOf<T>>, &'static str> {
+        let details = self.ensure_collection(collection)?;
+        if &details.owner != who {
+            return Err("No permission.");
+        }
+        Ok(details)
+    }
+
+    /// Garante que `who` é o proprietário ou o administrador da coleção.
+    fn ensure_manager(
+        &self,
+        who: &T::AccountId,
+        collection: &T::CollectionId,
+    ) -> Result<&CollectionDetails<T::AccountId, DepositOf<T>>, &'static str> {
+        let details = self.ensure_collection(collection)?;
+        if &details.owner != who && &details.admin != who {
+            return Err("No permission.");
+        }
+        Ok(details)
+    }
+
+    /// Obtém os dados do item, ou um erro se ele não existir.
+    fn ensure_item(
+        &self,
+        collection: &T::CollectionId,
+        item: &T::ItemId,
+    ) -> Result<&ItemDetails<T::AccountId, DepositOf<T>>, &'static str> {
+        self.item(collection, item).ok_or("Unknown item.")
+    }
+
+    /// Garante que `who` é o proprietário do item, e obtém os dados do item para alterá-los.
+    fn ensure_item_owner(
+        &mut self,
+        who: &T::AccountId,
+        collection: &T::CollectionId,
+        item: &T::ItemId,
+    ) -> Result<&mut ItemDetails<T::AccountId, DepositOf<T>>, &'static str> {
+        let details = self
+            .items
+            .get_mut(&(collection.clone(), item.clone()))
+            .ok_or("Unknown item.")?;
+        if &details.owner != who {
+            return Err("No permission.");
+        }
+        Ok(details)
+    }
+}
+
+impl<T: Config> Hooks<T> for Pallet<T> {
+    fn on_initialize(runtime: &mut T, _n: T::BlockNumber) {
+        let pallet: &mut Self = runtime.pallet_mut();
+        pallet.events.clear();
+    }
+}
+
+impl<T: Config> Storage for Pallet<T> {
+    fn storage(&self) -> Vec<(Vec<u8>, Vec<u8>)> {
+        let mut storage: Vec<_> = storage_map("collections", &self.collections).collect();
+        storage.extend(storage_map("items", &self.items));
+        storage.extend(storage_map("item_metadata", &self.item_metadata));
+        storage.extend(storage_map("attributes", &self.attributes));
+        storage
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::tests::new_runtime;
+    use crate::Runtime;
+
+    type Nfts = Pallet<Runtime>;
+
+    /// A coleção 1 de alice, administrada por bob, com o item 7 de charlie.
+    fn with_item() -> (Runtime, [String; 3]) {
+        let (mut runtime, [alice, bob, charlie]) = new_runtime(["alice", "bob", "charlie"], 100);
+        let res = Nfts::create(&mut runtime, alice.clone(), 1, bob.clone());
+        assert_eq!(res, Ok(()));
+        let res = Nfts::mint(&mut runtime, bob.clone(), 1, 7, charlie.clone());
+        assert_eq!(res, Ok(()));
+        (runtime, [alice, bob, charlie])
+    }
+
+    #[test]
+    fn create_and_mint() {
+        let (mut runtime, [alice, bob, charlie]) = new_runtime(["alice", "bob", "charlie"], 100);
+
+        // A coleção reserva o depósito de quem a cria, e cada item o de mais um item
+        assert_eq!(
+            Nfts::create(&mut runtime, alice.clone(), 1, bob.clone()),
+            Ok(())
+        );
+        let res = Nfts::create(&mut runtime, bob.clone(), 1, bob.clone());
+        assert_eq!(res, Err("Collection already exists."));
+        assert_eq!(runtime.balances.reserved_balance(&alice), 10);
+        let res = Nfts::mint(&mut runtime, charlie.clone(), 1, 7, charlie.clone());
+        assert_eq!(res, Err("No permission."));
+        let res = Nfts::mint(&mut runtime, bob.clone(), 1, 7, charlie.clone());
+        assert_eq!(res, Ok(()));
+        let res = Nfts::mint(&mut runtime, alice.clone(), 1, 7, bob.clone());
+        assert_eq!(res, Err("Item already exists."));
+        assert_eq!(runtime.nfts.owner(&1, &7), Some(&charlie));
+        assert_eq!(runtime.balances.reserved_balance(&alice), 11);
+        assert_eq!(runtime.balances.reserved_balance(&bob), 0);
+    }
+
+    #[test]
+    fn max_supply() {
+        let (mut runtime, [alice, bob, _]) = with_item();
+
+        // O limite de itens da coleção
+        assert_eq!(
+            runtime.nfts.set_max_supply(bob.clone(), 1, 2),
+            Err("No permission.")
+        );
+        assert_eq!(runtime.nfts.set_max_supply(alice.clone(), 1, 2), Ok(()));
+        let res = Nfts::mint(&mut runtime, alice.clone(), 1, 8, alice.clone());
+        assert_eq!(res, Ok(()));
+        let res = Nfts::mint(&mut runtime, alice.clone(), 1, 9, alice.clone());
+        assert_eq!(res, Err("The collection has reached its max supply."));
+        assert_eq!(
+            runtime.nfts.set_max_supply(alice.clone(), 1, 1),
+            Err("Max supply is below the current supply.")
+        );
+    }
+
+    #[test]
+    fn metadata_and_attributes() {
+        let (mut runtime, [_, bob, charlie]) = with_item();
+
+        // Metadados e atributos são definidos pela coleção
+        let data = b"certificado de conclusao".to_vec();
+        let res = runtime
+            .nfts
+            .set_metadata(charlie.clone(), 1, 7, data.clone());
+        assert_eq!(res, Err("No permission."));
+        assert_eq!(
+            runtime.nfts.set_metadata(bob.clone(), 1, 7, data.clone()),
+            Ok(())
+        );
+        assert_eq!(runtime.nfts.metadata(&1, &7), Some(data.as_slice()));
+        let res =
+            runtime
+                .nfts
+                .set_attribute(bob.clone(), 1, 7, b"curso".to_vec(), b"rust".to_vec());
+        assert_eq!(res, Ok(()));
+        assert_eq!(
+            runtime.nfts.attribute(&1, &7, b"curso"),
+            Some(b"rust".as_slice())
+        );
+        let res = runtime
+            .nfts
+            .set_attribute(bob.clone(), 1, 7, b"nota".to_vec(), b"10".to_vec());
+        assert_eq!(res, Ok(()));
+        let res = runtime
+            .nfts
+            .set_attribute(bob.clone(), 1, 7, b"ano".to_vec(), b"2024".to_vec());
+        assert_eq!(res, Err("Too many attributes."));
+        assert_eq!(runtime.nfts.attributes_of(&1, &7).count(), 2);
+    }
+
+    #[test]
+    fn transfer_and_approval() {
+        let (mut runtime, [_, bob, charlie]) = with_item();
+
+        // Apenas o proprietário do item, ou quem ele autorizar, pode transferi-lo
+        assert_eq!(
+            runtime.nfts.transfer(bob.clone(), 1, 7, bob.clone()),
+            Err("No permission.")
+        );
+        assert_eq!(
+            runtime
+                .nfts
+                .approve_transfer(charlie.clone(), 1, 7, bob.clone()),
+            Ok(())
+        );
+        assert_eq!(
+            runtime.nfts.transfer(bob.clone(), 1, 7, bob.clone()),
+            Ok(())
+        );
+        assert_eq!(runtime.nfts.owner(&1, &7), Some(&bob));
+        assert_eq!(runtime.nfts.item(&1, &7).unwrap().approved, None);
+        assert_eq!(
+            runtime.nfts.cancel_approval(bob.clone(), 1, 7),
+            Err("No approval to cancel.")
+        );
+    }
+
+    #[test]
+    fn burn_and_destroy() {
+        let (mut runtime, [alice, bob, charlie]) = with_item();
+        let res = Nfts::mint(&mut runtime, alice.clone(), 1, 8, alice.clone());
+        assert_eq!(res, Ok(()));
+        let data = b"certificado de conclusao".to_vec();
+        assert_eq!(runtime.nfts.set_metadata(bob.clone(), 1, 7, data), Ok(()));
+        let res =
+            runtime
+                .nfts
+                .set_attribute(bob.clone(), 1, 7, b"curso".to_vec(), b"rust".to_vec());
+        assert_eq!(res, Ok(()));
+
+        // Queimar os itens apaga os seus dados e devolve os depósitos ao dono da coleção
+        let res = Nfts::destroy(&mut runtime, alice.clone(), 1);
+        assert_eq!(res, Err("The collection still has items."));
+        assert_eq!(Nfts::burn(&mut runtime, charlie.clone(), 1, 7), Ok(()));
+        assert_eq!(Nfts::burn(&mut runtime, alice.clone(), 1, 8), Ok(()));
+        assert_eq!(runtime.nfts.metadata(&1, &7), None);
+        assert_eq!(runtime.nfts.attributes_of(&1, &7).count(), 0);
+        assert_eq!(runtime.nfts.collection(&1).unwrap().items, 0);
+        assert_eq!(Nfts::destroy(&mut runtime, alice.clone(), 1), Ok(()));
+        assert_eq!(runtime.balances.reserved_balance(&alice), 0);
+        assert!(matches!(
+            runtime.nfts.events().last(),
+            Some(Event::Destroyed { collection: 1 })
+        ));
+    }
+
+    #[test]
+    fn burn_only_by_owner() {
+        let (mut runtime, [alice, bob, charlie]) = with_item();
+        let res = Nfts::create(&mut runtime, alice.clone(), 2, bob.clone());
+        assert_eq!(res, Ok(()));
+        for (collection, item, owner) in [(1, 8, &alice), (2, 7, &charlie)] {
+            let res = Nfts::mint(&mut runtime, bob.clone(), collection, item, owner.clone());
+            assert_eq!(res, Ok(()));
+        }
+        for (collection, item) in [(1, 7), (1, 8), (2, 7)] {
+            let res = runtime.nfts.set_attribute(
+                bob.clone(),
+                collection,
+                item,
+                b"curso".to_vec(),
+                b"rust".to_vec(),
+            );
+            assert_eq!(res, Ok(()));
+        }
+
+        // Quem administra a coleção não pode queimar o item de outra conta
+        assert_eq!(
+            Nfts::burn(&mut runtime, bob.clone(), 1, 7),
+            Err("No permission.")
+        );
+        assert_eq!(
+            Nfts::burn(&mut runtime, alice.clone(), 1, 7),
+            Err("No permission.")
+        );
+
+        // Queimar um item apaga apenas os atributos dele
+        assert_eq!(Nfts::burn(&mut runtime, charlie.clone(), 1, 7), Ok(()));
+        assert_eq!(runtime.nfts.attributes_of(&1, &7).count(), 0);
+        assert_eq!(runtime.nfts.attributes_of(&1, &8).count(), 1);
+        assert_eq!(runtime.nfts.attributes_of(&2, &7).count(), 1);
+        assert_eq!(runtime.nfts.owner(&2, &7), Some(&charlie));
+    }
+}
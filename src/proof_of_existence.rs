@@ -1,48 +1,332 @@
-use crate::support::DispatchResult;
+use crate::merkle::{self, MerkleProof};
+use crate::support::{
+    storage_map, BalanceOf, DispatchResult, GetPallet, Hooks, NamedReservableCurrency,
+    ReserveIdentifier, Storage, Time,
+};
+use crate::system;
 use core::fmt::Debug;
-use std::collections::BTreeMap;
+use std::collections::{BTreeMap, BTreeSet};
+use std::ops::Bound;
 
-pub trait Config: crate::system::Config {
+pub trait Config:
+    system::Config + Sized + GetPallet<Pallet<Self>> + GetPallet<system::Pallet<Self>>
+{
     /// O tipo que representa o conteúdo que pode ser reivindicado usando este pallet.
     /// Pode ser o conteúdo diretamente como bytes, ou melhor ainda, o hash desse conteúdo.
     /// Deixamos essa decisão para o desenvolvedor do runtime.
     type Content: Debug + Ord + Clone;
+    /// A fonte do horário registrado em cada reivindicação. Use `()` se o runtime não tiver uma.
+    type Time: Time<Self>;
+    /// A moeda da qual é reservado o depósito de cada reivindicação.
+    type Currency: NamedReservableCurrency<Self>;
+    /// O tamanho máximo, em bytes, da descrição de uma reivindicação.
+    const MAX_DESCRIPTION_LENGTH: u32;
+    /// O número máximo de reivindicações que uma conta pode possuir.
+    const MAX_CLAIMS_PER_ACCOUNT: u32;
+    /// O depósito reservado do saldo do proprietário enquanto a reivindicação existir.
+    const CLAIM_DEPOSIT: DepositOf<Self>;
+    /// O número máximo de contas que podem atestar uma mesma reivindicação.
+    const MAX_ATTESTERS: u32;
 }
 
+/// O identificador da reserva que guarda os depósitos das reivindicações.
+pub const CLAIM_RESERVE_ID: ReserveIdentifier = *b"poeclaim";
+
+/// O tipo de saldo dos depósitos das reivindicações.
+pub type DepositOf<T> = BalanceOf<<T as Config>::Currency, T>;
+
+/// O tipo usado para representar um instante na fonte de tempo do runtime.
+pub type MomentOf<T> = <<T as Config>::Time as Time<T>>::Moment;
+
+/// Os eventos emitidos pelo Módulo de Prova de Existência.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Event<T: Config> {
+    /// A reivindicação de `claim`, que pertencia a `owner`, expirou e foi removida.
+    ClaimExpired {
+        claim: T::Content,
+        owner: T::AccountId,
+    },
+    /// `attester` atestou a reivindicação de `claim`.
+    Attested {
+        claim: T::Content,
+        attester: T::AccountId,
+    },
+    /// `attester` retirou o seu atestado da reivindicação de `claim`.
+    AttestationRevoked {
+        claim: T::Content,
+        attester: T::AccountId,
+    },
+}
+
+/// O registro de uma reivindicação: quem a fez e desde quando ela existe.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Claim<AccountId, BlockNumber, Moment, Balance> {
+    /// O proprietário da reivindicação.
+    pub owner: AccountId,
+    /// O bloco em que a reivindicação foi criada.
+    pub created_at: BlockNumber,
+    /// O horário em que a reivindicação foi criada, se o runtime tiver uma fonte de tempo.
+    pub timestamp: Option<Moment>,
+    /// Uma descrição livre do conteúdo reivindicado.
+    pub description: Vec<u8>,
+    /// O depósito reservado de `depositor`, devolvido quando a reivindicação é removida.
+    pub deposit: Balance,
+    /// A conta que criou a reivindicação e pagou o depósito. Continua a mesma quando a
+    /// reivindicação é transferida, para que só ela receba o depósito de volta.
+    pub depositor: AccountId,
+    /// O bloco ao fim do qual a reivindicação é removida, ou `None` se ela não expirar.
+    pub expires_at: Option<BlockNumber>,
+    /// Para uma reivindicação em lote, o número de documentos sob a raiz de Merkle reivindicada.
+    pub batch_size: Option<u32>,
+    /// As contas que atestaram a reivindicação, na ordem em que o fizeram.
+    pub attesters: Vec<AccountId>,
+    /// O número de atestados necessários para a reivindicação ser considerada atestada, se houver.
+    pub attestation_threshold: Option<u32>,
+}
+
+/// O registro de uma reivindicação com os tipos do runtime `T`.
+pub type ClaimOf<T> = Claim<
+    <T as system::Config>::AccountId,
+    <T as system::Config>::BlockNumber,
+    MomentOf<T>,
+    DepositOf<T>,
+>;
+
 /// Este é o Módulo de Prova de Existência.
 /// É um módulo simples que permite que contas reivindiquem a existência de alguns dados.
-#[derive(Debug)]
+#[derive(Debug, Clone)]
 pub struct Pallet<T: Config> {
-    /// Um simples mapa de armazenamento de conteúdo para o proprietário desse conteúdo.
+    /// Um simples mapa de armazenamento de conteúdo para o registro da reivindicação desse conteúdo.
     /// As contas podem fazer várias reivindicações diferentes, mas cada reivindicação só pode ter um proprietário.
-    claims: BTreeMap<T::Content, T::AccountId>,
+    #[allow(clippy::type_complexity)]
+    claims: BTreeMap<T::Content, Claim<T::AccountId, T::BlockNumber, MomentOf<T>, DepositOf<T>>>,
+    /// As transferências de reivindicações que aguardam a aceitação do novo proprietário.
+    pending_transfers: BTreeMap<T::Content, T::AccountId>,
+    /// As contas que podem revogar ou transferir uma reivindicação em nome do proprietário.
+    delegates: BTreeMap<T::Content, T::AccountId>,
+    /// O índice reverso de `claims`: o conteúdo reivindicado por cada conta.
+    claims_by_owner: BTreeMap<T::AccountId, BTreeSet<T::Content>>,
+    /// As reivindicações que expiram ao fim de cada bloco.
+    expiries: BTreeMap<T::BlockNumber, BTreeSet<T::Content>>,
+    /// Os eventos emitidos por este pallet no bloco atual, em ordem.
+    events: Vec<Event<T>>,
 }
 
 #[macros::call]
 impl<T: Config> Pallet<T> {
-    /// Cria uma nova reivindicação em nome do `caller`.
-    /// Esta função retornará um erro se alguém já tiver reivindicado esse conteúdo.
-    pub fn create_claim(&mut self, caller: T::AccountId, claim: T::Content) -> DispatchResult {
-        if self.claims.contains_key(&claim) {
-            return Err("This content is already claimed.");
+    /// Cria uma nova reivindicação em nome do `caller`, registrando o bloco atual, o horário atual
+    /// (se conhecido) e uma `description` do conteúdo. O depósito `CLAIM_DEPOSIT` é reservado do
+    /// saldo do `caller`.
+    /// Com `expires_at`, a reivindicação é removida ao fim desse bloco, a menos que seja renovada.
+    /// Com `attestation_threshold`, ela só é considerada atestada (veja `is_attested`) depois que
+    /// esse número de outras contas a atestar.
+    /// Esta função retornará um erro se alguém já tiver reivindicado esse conteúdo, se a
+    /// descrição for longa demais, se a expiração não estiver no futuro, se o limiar for maior
+    /// do que `MAX_ATTESTERS` ou se o `caller` não puder pagar o depósito.
+    pub fn create_claim(
+        runtime: &mut T,
+        caller: T::AccountId,
+        claim: T::Content,
+        description: Vec<u8>,
+        expires_at: Option<T::BlockNumber>,
+        attestation_threshold: Option<u32>,
+    ) -> DispatchResult {
+        Self::do_create_claim(
+            runtime,
+            caller,
+            claim,
+            description,
+            expires_at,
+            None,
+            attestation_threshold,
+        )
+    }
+
+    /// Cria uma reivindicação em lote: `root` é a raiz da árvore de Merkle (veja o módulo
+    /// `merkle`) sobre os hashes de `batch_size` documentos, que ficam todos reivindicados por uma
+    /// única reivindicação e um único depósito. A inclusão de cada documento é provada com uma
+    /// prova de Merkle. Fora isso, funciona como `create_claim`.
+    pub fn create_batch_claim(
+        runtime: &mut T,
+        caller: T::AccountId,
+        root: T::Content,
+        batch_size: u32,
+        description: Vec<u8>,
+        expires_at: Option<T::BlockNumber>,
+        attestation_threshold: Option<u32>,
+    ) -> DispatchResult {
+        if batch_size == 0 {
+            return Err("A batch needs at least one document.");
         }
+        Self::do_create_claim(
+            runtime,
+            caller,
+            root,
+            description,
+            expires_at,
+            Some(batch_size),
+            attestation_threshold,
+        )
+    }
+
+    /// Revoga uma reivindicação existente em algum conteúdo.
+    /// Esta função só deve ter sucesso se o chamador for o proprietário ou o delegado de uma
+    /// reivindicação existente.
+    /// Retornará um erro se a reivindicação não existir ou se o chamador não puder gerenciá-la.
+    /// O depósito é devolvido a quem o pagou.
+    pub fn revoke_claim(
+        runtime: &mut T,
+        caller: T::AccountId,
+        claim: T::Content,
+    ) -> DispatchResult {
+        let pallet: &mut Self = runtime.pallet_mut();
+        pallet.ensure_can_manage(&caller, &claim)?;
+        let Some(record) = pallet.remove_claim(&claim) else {
+            return Err("This claim is owned by someone else.");
+        };
 
-        self.claims.insert(claim, caller);
+        T::Currency::unreserve_named(
+            runtime,
+            &CLAIM_RESERVE_ID,
+            &record.depositor,
+            record.deposit,
+        );
         Ok(())
     }
 
-    /// Revoga uma reivindicação existente em algum conteúdo.
-    /// Esta função só deve ter sucesso se o chamador for o proprietário de uma reivindicação existente.
-    /// Retornará um erro se a reivindicação não existir ou se o chamador não for o proprietário.
-    pub fn revoke_claim(&mut self, caller: T::AccountId, claim: T::Content) -> DispatchResult {
-        let owner = self.claims.get(&claim);
-        match owner {
-            Some(account) if account == &caller => {
-                self.claims.remove(&claim);
-                Ok(())
-            }
-            _ => Err("This claim is owned by someone else."),
+    /// Transfere uma reivindicação para `new_owner`. Só o proprietário ou o delegado podem fazê-lo.
+    /// Com `require_accept`, a transferência fica pendente até que `new_owner` chame
+    /// `accept_claim`; uma nova chamada substitui a transferência pendente.
+    /// O depósito continua reservado de quem criou a reivindicação.
+    pub fn transfer_claim(
+        runtime: &mut T,
+        caller: T::AccountId,
+        claim: T::Content,
+        new_owner: T::AccountId,
+        require_accept: bool,
+    ) -> DispatchResult {
+        let pallet: &mut Self = runtime.pallet_mut();
+        pallet.ensure_can_manage(&caller, &claim)?;
+        if require_accept {
+            pallet.pending_transfers.insert(claim, new_owner);
+            Ok(())
+        } else {
+            Self::do_transfer(runtime, claim, new_owner)
+        }
+    }
+
+    /// Aceita uma transferência pendente de uma reivindicação para o `caller`.
+    pub fn accept_claim(
+        runtime: &mut T,
+        caller: T::AccountId,
+        claim: T::Content,
+    ) -> DispatchResult {
+        let pallet: &Self = runtime.pallet();
+        if pallet.pending_transfers.get(&claim) != Some(&caller) {
+            return Err("No pending transfer to the caller.");
         }
+        Self::do_transfer(runtime, claim, caller)
+    }
+
+    /// Cancela a transferência pendente de uma reivindicação.
+    pub fn cancel_claim_transfer(
+        &mut self,
+        caller: T::AccountId,
+        claim: T::Content,
+    ) -> DispatchResult {
+        self.ensure_can_manage(&caller, &claim)?;
+        self.pending_transfers.remove(&claim);
+        Ok(())
+    }
+
+    /// Adia a expiração de uma reivindicação para `expires_at`, ou a remove com `None`.
+    /// Só o proprietário ou o delegado podem renová-la, e a nova expiração precisa ser posterior à
+    /// atual.
+    pub fn renew_claim(
+        &mut self,
+        caller: T::AccountId,
+        claim: T::Content,
+        expires_at: Option<T::BlockNumber>,
+    ) -> DispatchResult {
+        self.ensure_can_manage(&caller, &claim)?;
+        let Some(record) = self.claims.get_mut(&claim) else {
+            return Err("This claim is owned by someone else.");
+        };
+        let Some(old) = record.expires_at else {
+            return Err("This claim does not expire.");
+        };
+        if expires_at.is_some_and(|expires_at| expires_at <= old) {
+            return Err("Renewal must extend the expiry.");
+        }
+
+        record.expires_at = expires_at;
+        self.unschedule_expiry(old, &claim);
+        if let Some(expires_at) = expires_at {
+            self.expiries.entry(expires_at).or_default().insert(claim);
+        }
+        Ok(())
+    }
+
+    /// Atesta a reivindicação de `claim` em nome do `caller`, que não pode ser o proprietário.
+    pub fn attest(&mut self, caller: T::AccountId, claim: T::Content) -> DispatchResult {
+        let Some(record) = self.claims.get_mut(&claim) else {
+            return Err("This claim does not exist.");
+        };
+        if record.owner == caller {
+            return Err("The owner cannot attest their own claim.");
+        }
+        if record.attesters.contains(&caller) {
+            return Err("Already attested.");
+        }
+        if record.attesters.len() >= T::MAX_ATTESTERS as usize {
+            return Err("Too many attesters.");
+        }
+
+        record.attesters.push(caller.clone());
+        self.deposit_event(Event::Attested {
+            claim,
+            attester: caller,
+        });
+        Ok(())
+    }
+
+    /// Retira o atestado do `caller` da reivindicação de `claim`.
+    pub fn revoke_attestation(
+        &mut self,
+        caller: T::AccountId,
+        claim: T::Content,
+    ) -> DispatchResult {
+        let Some(record) = self.claims.get_mut(&claim) else {
+            return Err("This claim does not exist.");
+        };
+        let Some(index) = record.attesters.iter().position(|a| a == &caller) else {
+            return Err("Not an attester of this claim.");
+        };
+        record.attesters.remove(index);
+        self.deposit_event(Event::AttestationRevoked {
+            claim,
+            attester: caller,
+        });
+        Ok(())
+    }
+
+    /// Define (ou remove, com `None`) a conta que pode revogar ou transferir a reivindicação em
+    /// nome do proprietário. Só o proprietário pode escolher o delegado.
+    pub fn set_delegate(
+        &mut self,
+        caller: T::AccountId,
+        claim: T::Content,
+        delegate: Option<T::AccountId>,
+    ) -> DispatchResult {
+        match self.claims.get(&claim) {
+            Some(record) if record.owner == caller => {}
+            _ => return Err("This claim is owned by someone else."),
+        }
+        match delegate {
+            Some(delegate) => self.delegates.insert(claim, delegate),
+            None => self.delegates.remove(&claim),
+        };
+        Ok(())
     }
 }
 
@@ -51,68 +335,781 @@ impl<T: Config> Pallet<T> {
     pub fn new() -> Self {
         Self {
             claims: BTreeMap::new(),
+            pending_transfers: BTreeMap::new(),
+            delegates: BTreeMap::new(),
+            claims_by_owner: BTreeMap::new(),
+            expiries: BTreeMap::new(),
+            events: Vec::new(),
         }
     }
 
-    /// Obtém o proprietário (se houver) de uma reivindicação.
-    pub fn get_claim(&self, claim: &T::Content) -> Option<&T::AccountId> {
+    /// Obtém o registro (se houver) de uma reivindicação.
+    pub fn get_claim(&self, claim: &T::Content) -> Option<&ClaimOf<T>> {
         self.claims.get(claim)
     }
+
+    /// Obtém o novo proprietário (se houver) de uma transferência pendente da reivindicação.
+    pub fn pending_transfer(&self, claim: &T::Content) -> Option<&T::AccountId> {
+        self.pending_transfers.get(claim)
+    }
+
+    /// Verifica se uma reivindicação existe e já tem os atestados exigidos na sua criação.
+    pub fn is_attested(&self, claim: &T::Content) -> bool {
+        self.claims.get(claim).is_some_and(|record| {
+            record
+                .attestation_threshold
+                .is_none_or(|threshold| record.attesters.len() >= threshold as usize)
+        })
+    }
+
+    /// Obtém o delegado (se houver) de uma reivindicação.
+    pub fn delegate(&self, claim: &T::Content) -> Option<&T::AccountId> {
+        self.delegates.get(claim)
+    }
+
+    /// Obtém até `limit` reivindicações de `owner`, em ordem, começando depois de `start_after`.
+    /// Para ler a próxima página, passe o último conteúdo da página anterior em `start_after`.
+    pub fn claims_of(
+        &self,
+        owner: &T::AccountId,
+        start_after: Option<&T::Content>,
+        limit: usize,
+    ) -> Vec<(&T::Content, &ClaimOf<T>)> {
+        let Some(owned) = self.claims_by_owner.get(owner) else {
+            return Vec::new();
+        };
+        owned
+            .range::<T::Content, _>(Self::after(start_after))
+            .take(limit)
+            .filter_map(|claim| self.claims.get_key_value(claim))
+            .collect()
+    }
+
+    /// Obtém até `limit` reivindicações de todas as contas, em ordem, começando depois de
+    /// `start_after`.
+    pub fn all_claims(
+        &self,
+        start_after: Option<&T::Content>,
+        limit: usize,
+    ) -> Vec<(&T::Content, &ClaimOf<T>)> {
+        self.claims
+            .range::<T::Content, _>(Self::after(start_after))
+            .take(limit)
+            .collect()
+    }
+
+    /// O número de reivindicações que `owner` possui.
+    pub fn claim_count(&self, owner: &T::AccountId) -> usize {
+        self.claims_by_owner.get(owner).map_or(0, BTreeSet::len)
+    }
+
+    /// Cria uma reivindicação, simples ou em lote, validando os dados e reservando o depósito.
+    fn do_create_claim(
+        runtime: &mut T,
+        caller: T::AccountId,
+        claim: T::Content,
+        description: Vec<u8>,
+        expires_at: Option<T::BlockNumber>,
+        batch_size: Option<u32>,
+        attestation_threshold: Option<u32>,
+    ) -> DispatchResult {
+        if description.len() > T::MAX_DESCRIPTION_LENGTH as usize {
+            return Err("Description too long.");
+        }
+        if attestation_threshold.is_some_and(|threshold| threshold > T::MAX_ATTESTERS) {
+            return Err("Threshold exceeds the attester limit.");
+        }
+        let system: &system::Pallet<T> = runtime.pallet();
+        if expires_at.is_some_and(|expires_at| expires_at <= system.block_number()) {
+            return Err("Expiry must be in the future.");
+        }
+
+        let pallet: &Self = runtime.pallet();
+        if pallet.claims.contains_key(&claim) {
+            return Err("This content is already claimed.");
+        }
+        pallet.ensure_can_own_more(&caller)?;
+
+        T::Currency::reserve_named(runtime, &CLAIM_RESERVE_ID, &caller, T::CLAIM_DEPOSIT)?;
+
+        let system: &system::Pallet<T> = runtime.pallet();
+        let record = Claim {
+            owner: caller.clone(),
+            created_at: system.block_number(),
+            timestamp: T::Time::now(runtime),
+            description,
+            deposit: T::CLAIM_DEPOSIT,
+            depositor: caller,
+            expires_at,
+            batch_size,
+            attesters: Vec::new(),
+            attestation_threshold,
+        };
+
+        let pallet: &mut Self = runtime.pallet_mut();
+        pallet.index_claim(&record.owner, claim.clone());
+        if let Some(expires_at) = expires_at {
+            pallet
+                .expiries
+                .entry(expires_at)
+                .or_default()
+                .insert(claim.clone());
+        }
+        pallet.claims.insert(claim, record);
+        Ok(())
+    }
+
+    /// Obtém os eventos emitidos por este pallet no bloco atual, em ordem.
+    pub fn events(&self) -> &[Event<T>] {
+        &self.events
+    }
+
+    /// Registra um evento emitido por este pallet.
+    fn deposit_event(&mut self, event: Event<T>) {
+        self.events.push(event);
+    }
+
+    /// Remove as reivindicações que expiram até o bloco `n`, devolvendo os depósitos a quem os
+    /// pagou e emitindo um `ClaimExpired` para cada uma.
+    fn expire_claims(runtime: &mut T, n: T::BlockNumber) {
+        let pallet: &mut Self = runtime.pallet_mut();
+        let due: Vec<T::BlockNumber> = pallet.expiries.range(..=n).map(|(at, _)| *at).collect();
+        for at in due {
+            let pallet: &mut Self = runtime.pallet_mut();
+            let claims = pallet.expiries.remove(&at).unwrap_or_default();
+            for claim in claims {
+                let pallet: &mut Self = runtime.pallet_mut();
+                let Some(record) = pallet.remove_claim(&claim) else {
+                    continue;
+                };
+                pallet.deposit_event(Event::ClaimExpired {
+                    claim,
+                    owner: record.owner.clone(),
+                });
+                T::Currency::unreserve_named(
+                    runtime,
+                    &CLAIM_RESERVE_ID,
+                    &record.depositor,
+                    record.deposit,
+                );
+            }
+        }
+    }
+
+    /// Remove uma reivindicação e tudo o que se refere a ela, devolvendo o seu registro.
+    /// O depósito continua reservado; cabe a quem chama devolvê-lo.
+    fn remove_claim(&mut self, claim: &T::Content) -> Option<ClaimOf<T>> {
+        let record = self.claims.remove(claim)?;
+        self.unindex_claim(&record.owner, claim);
+        if let Some(expires_at) = record.expires_at {
+            self.unschedule_expiry(expires_at, claim);
+        }
+        self.pending_transfers.remove(claim);
+        self.delegates.remove(claim);
+        Some(record)
+    }
+
+    /// Tira `claim` da lista de reivindicações que expiram ao fim do bloco `at`.
+    fn unschedule_expiry(&mut self, at: T::BlockNumber, claim: &T::Content) {
+        if let Some(claims) = self.expiries.get_mut(&at) {
+            claims.remove(claim);
+            if claims.is_empty() {
+                self.expiries.remove(&at);
+            }
+        }
+    }
+
+    /// O intervalo de uma página que começa depois de `start_after`.
+    fn after(start_after: Option<&T::Content>) -> (Bound<&T::Content>, Bound<&T::Content>) {
+        match start_after {
+            Some(claim) => (Bound::Excluded(claim), Bound::Unbounded),
+            None => (Bound::Unbounded, Bound::Unbounded),
+        }
+    }
+
+    /// Verifica se `who` ainda pode receber mais uma reivindicação.
+    fn ensure_can_own_more(&self, who: &T::AccountId) -> DispatchResult {
+        if self.claim_count(who) >= T::MAX_CLAIMS_PER_ACCOUNT as usize {
+            return Err("Too many claims for this account.");
+        }
+        Ok(())
+    }
+
+    /// Adiciona `claim` ao índice de reivindicações de `owner`.
+    fn index_claim(&mut self, owner: &T::AccountId, claim: T::Content) {
+        self.claims_by_owner
+            .entry(owner.clone())
+            .or_default()
+            .insert(claim);
+    }
+
+    /// Remove `claim` do índice de reivindicações de `owner`.
+    fn unindex_claim(&mut self, owner: &T::AccountId, claim: &T::Content) {
+        if let Some(owned) = self.claims_by_owner.get_mut(owner) {
+            owned.remove(claim);
+            if owned.is_empty() {
+                self.claims_by_owner.remove(owner);
+            }
+        }
+    }
+
+    /// Verifica se `who` é o proprietário ou o delegado de uma reivindicação existente.
+    fn ensure_can_manage(&self, who: &T::AccountId, claim: &T::Content) -> DispatchResult {
+        match self.claims.get(claim) {
+            Some(record) if &record.owner == who => Ok(()),
+            Some(_) if self.delegates.get(claim) == Some(who) => Ok(()),
+            _ => Err("This claim is owned by someone else."),
+        }
+    }
+
+    /// Passa a propriedade da reivindicação para `new_owner`. O depósito continua reservado de
+    /// `depositor`, e o delegado e a transferência pendente do proprietário anterior deixam de
+    /// valer.
+    fn do_transfer(runtime: &mut T, claim: T::Content, new_owner: T::AccountId) -> DispatchResult {
+        let pallet: &Self = runtime.pallet();
+        let Some(record) = pallet.claims.get(&claim) else {
+            return Err("This claim is owned by someone else.");
+        };
+        let old_owner = record.owner.clone();
+        if old_owner != new_owner {
+            pallet.ensure_can_own_more(&new_owner)?;
+        }
+
+        let pallet: &mut Self = runtime.pallet_mut();
+        pallet.unindex_claim(&old_owner, &claim);
+        pallet.index_claim(&new_owner, claim.clone());
+        pallet.pending_transfers.remove(&claim);
+        pallet.delegates.remove(&claim);
+        if let Some(record) = pallet.claims.get_mut(&claim) {
+            // O proprietário não atesta a própria reivindicação.
+            record.attesters.retain(|attester| attester != &new_owner);
+            record.owner = new_owner;
+        }
+        Ok(())
+    }
+}
+
+impl<T: Config<Content = <T as system::Config>::Hash>> Pallet<T> {
+    /// Verifica se o documento de hash `leaf` está incluído na reivindicação em lote de raiz
+    /// `root`, segundo a prova de Merkle `proof`. A prova precisa ser de uma árvore com o número
+    /// de documentos informado quando o lote foi reivindicado.
+    pub fn verify_batch_inclusion(
+        &self,
+        root: &T::Hash,
+        leaf: &T::Hash,
+        proof: &MerkleProof<T::Hash>,
+    ) -> bool {
+        match self.claims.get(root).and_then(|record| record.batch_size) {
+            Some(batch_size) => {
+                merkle::leaf_index(proof, batch_size as usize).is_some()
+                    && merkle::verify_proof::<T::Hashing>(root, leaf, proof)
+            }
+            None => false,
+        }
+    }
+}
+
+impl<T: Config> Hooks<T> for Pallet<T> {
+    fn on_initialize(runtime: &mut T, _n: T::BlockNumber) {
+        let pallet: &mut Self = runtime.pallet_mut();
+        pallet.events.clear();
+    }
+
+    fn on_finalize(runtime: &mut T, n: T::BlockNumber) {
+        Self::expire_claims(runtime, n);
+    }
+}
+
+impl<T: Config> Storage for Pallet<T> {
+    fn storage(&self) -> Vec<(Vec<u8>, Vec<u8>)> {
+        let mut storage: Vec<_> = storage_map("claims", &self.claims).collect();
+        storage.extend(storage_map("pending_transfers", &self.pending_transfers));
+        storage.extend(storage_map("delegates", &self.delegates));
+        storage.extend(storage_map("claims_by_owner", &self.claims_by_owner));
+        storage.extend(storage_map("expiries", &self.expiries));
+        storage
+    }
 }
 
 #[cfg(test)]
 mod test {
     use super::*;
-    use crate::system;
+    use crate::balances;
 
-    struct TestConfig;
-    impl super::Config for TestConfig {
+    /// Um runtime mínimo com os pallets dos quais este pallet depende.
+    struct TestRuntime {
+        system: system::Pallet<TestRuntime>,
+        balances: balances::Pallet<TestRuntime>,
+        proof_of_existence: Pallet<TestRuntime>,
+    }
+
+    impl TestRuntime {
+        /// Cria o runtime com saldo suficiente para os depósitos das contas usadas nos testes.
+        fn new() -> Self {
+            let mut runtime = Self {
+                system: system::Pallet::new(),
+                balances: balances::Pallet::new(),
+                proof_of_existence: Pallet::new(),
+            };
+            for who in ["alice", "bob", "charlie", "dave", "erin"] {
+                runtime.balances.set_balance(&who, 100);
+            }
+            runtime
+        }
+    }
+
+    impl super::Config for TestRuntime {
         type Content = &'static str;
+        type Time = ();
+        type Currency = balances::Pallet<Self>;
+        const MAX_DESCRIPTION_LENGTH: u32 = 8;
+        const MAX_CLAIMS_PER_ACCOUNT: u32 = 3;
+        const MAX_ATTESTERS: u32 = 2;
+        const CLAIM_DEPOSIT: u128 = 10;
     }
-    impl system::Config for TestConfig {
+
+    impl balances::Config for TestRuntime {
+        type Balance = u128;
+        const EXISTENTIAL_DEPOSIT: u128 = 1;
+    }
+
+    impl system::Config for TestRuntime {
         type AccountId = &'static str;
         type BlockNumber = u32;
         type Nonce = u32;
+        type Hash = crate::hashing::H256;
+        type Hashing = crate::hashing::Sha256;
+    }
+
+    impl GetPallet<system::Pallet<TestRuntime>> for TestRuntime {
+        fn pallet(&self) -> &system::Pallet<TestRuntime> {
+            &self.system
+        }
+
+        fn pallet_mut(&mut self) -> &mut system::Pallet<TestRuntime> {
+            &mut self.system
+        }
+    }
+
+    impl GetPallet<balances::Pallet<TestRuntime>> for TestRuntime {
+        fn pallet(&self) -> &balances::Pallet<TestRuntime> {
+            &self.balances
+        }
+
+        fn pallet_mut(&mut self) -> &mut balances::Pallet<TestRuntime> {
+            &mut self.balances
+        }
+    }
+
+    impl GetPallet<Pallet<TestRuntime>> for TestRuntime {
+        fn pallet(&self) -> &Pallet<TestRuntime> {
+            &self.proof_of_existence
+        }
+
+        fn pallet_mut(&mut self) -> &mut Pallet<TestRuntime> {
+            &mut self.proof_of_existence
+        }
+    }
+
+    type PoE = Pallet<TestRuntime>;
+
+    /// O proprietário (se houver) de uma reivindicação.
+    fn owner(runtime: &TestRuntime, claim: &'static str) -> Option<&'static str> {
+        runtime
+            .proof_of_existence
+            .get_claim(&claim)
+            .map(|c| c.owner)
     }
 
     #[test]
     fn basic_proof_of_existence() {
-        let mut pallet = Pallet::<TestConfig>::new();
+        let mut runtime = TestRuntime::new();
 
         // Estado inicial
-        assert_eq!(pallet.get_claim(&"conteudo"), None);
+        assert_eq!(runtime.proof_of_existence.get_claim(&"conteudo"), None);
 
         // Criação de reivindicação
-        let res = pallet.create_claim("alice", "conteudo");
+        let res = PoE::create_claim(&mut runtime, "alice", "conteudo", vec![], None, None);
         assert_eq!(res, Ok(()));
-        assert_eq!(pallet.get_claim(&"conteudo"), Some(&"alice"));
+        assert_eq!(owner(&runtime, "conteudo"), Some("alice"));
 
         // Tentativa de criar reivindicação duplicada
-        let res = pallet.create_claim("bob", "conteudo");
+        let res = PoE::create_claim(&mut runtime, "bob", "conteudo", vec![], None, None);
         assert_eq!(res, Err("This content is already claimed."));
-        assert_eq!(pallet.get_claim(&"conteudo"), Some(&"alice"));
+        assert_eq!(owner(&runtime, "conteudo"), Some("alice"));
 
         // Revogação de reivindicação por proprietário
-        let res = pallet.revoke_claim("alice", "conteudo");
+        let res = PoE::revoke_claim(&mut runtime, "alice", "conteudo");
         assert_eq!(res, Ok(()));
-        assert_eq!(pallet.get_claim(&"conteudo"), None);
+        assert_eq!(runtime.proof_of_existence.get_claim(&"conteudo"), None);
 
         // Tentativa de revogar reivindicação inexistente
-        let res = pallet.revoke_claim("alice", "conteudo");
+        let res = PoE::revoke_claim(&mut runtime, "alice", "conteudo");
         assert_eq!(res, Err("This claim is owned by someone else."));
 
         // Criação de nova reivindicação
-        let res = pallet.create_claim("bob", "outro conteudo");
+        let res = PoE::create_claim(&mut runtime, "bob", "outro conteudo", vec![], None, None);
         assert_eq!(res, Ok(()));
-        assert_eq!(pallet.get_claim(&"outro conteudo"), Some(&"bob"));
+        assert_eq!(owner(&runtime, "outro conteudo"), Some("bob"));
 
         // Tentativa de revogar reivindicação por não proprietário
-        let res = pallet.revoke_claim("alice", "outro conteudo");
+        let res = PoE::revoke_claim(&mut runtime, "alice", "outro conteudo");
         assert_eq!(res, Err("This claim is owned by someone else."));
 
         // Revogação de reivindicação por proprietário
-        let res = pallet.revoke_claim("bob", "outro conteudo");
+        let res = PoE::revoke_claim(&mut runtime, "bob", "outro conteudo");
+        assert_eq!(res, Ok(()));
+        assert_eq!(
+            runtime.proof_of_existence.get_claim(&"outro conteudo"),
+            None
+        );
+    }
+
+    #[test]
+    fn claim_metadata() {
+        let mut runtime = TestRuntime::new();
+        runtime.system.inc_block_number();
+        runtime.system.inc_block_number();
+
+        // A descrição é limitada
+        let res = PoE::create_claim(
+            &mut runtime,
+            "alice",
+            "contrato",
+            b"muito longa".to_vec(),
+            None,
+            None,
+        );
+        assert_eq!(res, Err("Description too long."));
+
+        // Um lote precisa de pelo menos um documento
+        let res = PoE::create_batch_claim(&mut runtime, "alice", "raiz", 0, vec![], None, None);
+        assert_eq!(res, Err("A batch needs at least one document."));
+        let res = PoE::create_batch_claim(&mut runtime, "alice", "raiz", 2, vec![], None, None);
+        assert_eq!(res, Ok(()));
+        let claim = runtime.proof_of_existence.get_claim(&"raiz");
+        assert_eq!(claim.and_then(|c| c.batch_size), Some(2));
+
+        let res = PoE::create_claim(
+            &mut runtime,
+            "alice",
+            "contrato",
+            b"v1".to_vec(),
+            None,
+            None,
+        );
+        assert_eq!(res, Ok(()));
+        assert_eq!(
+            runtime.proof_of_existence.get_claim(&"contrato"),
+            Some(&Claim {
+                owner: "alice",
+                created_at: 2,
+                timestamp: None,
+                description: b"v1".to_vec(),
+                deposit: 10,
+                depositor: "alice",
+                expires_at: None,
+                batch_size: None,
+                attesters: vec![],
+                attestation_threshold: None,
+            })
+        );
+    }
+
+    #[test]
+    fn transfer_and_delegate_claims() {
+        let mut runtime = TestRuntime::new();
+        let res = PoE::create_claim(&mut runtime, "alice", "contrato", vec![], None, None);
+        assert_eq!(res, Ok(()));
+
+        // Transferência direta
+        let res = PoE::transfer_claim(&mut runtime, "bob", "contrato", "bob", false);
+        assert_eq!(res, Err("This claim is owned by someone else."));
+        let res = PoE::transfer_claim(&mut runtime, "alice", "contrato", "bob", false);
+        assert_eq!(res, Ok(()));
+        assert_eq!(owner(&runtime, "contrato"), Some("bob"));
+
+        // Transferência em duas etapas
+        let res = PoE::transfer_claim(&mut runtime, "bob", "contrato", "charlie", true);
+        assert_eq!(res, Ok(()));
+        assert_eq!(owner(&runtime, "contrato"), Some("bob"));
+        let poe = &runtime.proof_of_existence;
+        assert_eq!(poe.pending_transfer(&"contrato"), Some(&"charlie"));
+        let res = PoE::accept_claim(&mut runtime, "alice", "contrato");
+        assert_eq!(res, Err("No pending transfer to the caller."));
+        let poe = &mut runtime.proof_of_existence;
+        assert_eq!(poe.cancel_claim_transfer("bob", "contrato"), Ok(()));
+        let res = PoE::accept_claim(&mut runtime, "charlie", "contrato");
+        assert_eq!(res, Err("No pending transfer to the caller."));
+        let res = PoE::transfer_claim(&mut runtime, "bob", "contrato", "charlie", true);
+        assert_eq!(res, Ok(()));
+        assert_eq!(
+            PoE::accept_claim(&mut runtime, "charlie", "contrato"),
+            Ok(())
+        );
+        assert_eq!(owner(&runtime, "contrato"), Some("charlie"));
+        let poe = &mut runtime.proof_of_existence;
+        assert_eq!(poe.pending_transfer(&"contrato"), None);
+
+        // Delegação: só o proprietário escolhe o delegado, que pode transferir e revogar
+        let res = poe.set_delegate("dave", "contrato", Some("dave"));
+        assert_eq!(res, Err("This claim is owned by someone else."));
+        assert_eq!(
+            poe.set_delegate("charlie", "contrato", Some("dave")),
+            Ok(())
+        );
+        let res = PoE::transfer_claim(&mut runtime, "dave", "contrato", "erin", false);
+        assert_eq!(res, Ok(()));
+        assert_eq!(owner(&runtime, "contrato"), Some("erin"));
+        // O delegado do proprietário anterior perde o acesso
+        assert_eq!(runtime.proof_of_existence.delegate(&"contrato"), None);
+        let res = PoE::revoke_claim(&mut runtime, "dave", "contrato");
+        assert_eq!(res, Err("This claim is owned by someone else."));
+        let poe = &mut runtime.proof_of_existence;
+        assert_eq!(poe.set_delegate("erin", "contrato", Some("dave")), Ok(()));
+        assert_eq!(PoE::revoke_claim(&mut runtime, "dave", "contrato"), Ok(()));
+        assert_eq!(runtime.proof_of_existence.get_claim(&"contrato"), None);
+        assert_eq!(runtime.proof_of_existence.delegate(&"contrato"), None);
+    }
+
+    #[test]
+    fn claims_by_owner_index() {
+        let mut runtime = TestRuntime::new();
+        for claim in ["a", "b", "c"] {
+            let res = PoE::create_claim(&mut runtime, "alice", claim, vec![], None, None);
+            assert_eq!(res, Ok(()));
+        }
+        assert_eq!(
+            PoE::create_claim(&mut runtime, "bob", "d", vec![], None, None),
+            Ok(())
+        );
+
+        // Limite de reivindicações por conta
+        let res = PoE::create_claim(&mut runtime, "alice", "e", vec![], None, None);
+        assert_eq!(res, Err("Too many claims for this account."));
+        let res = PoE::transfer_claim(&mut runtime, "bob", "d", "alice", false);
+        assert_eq!(res, Err("Too many claims for this account."));
+        let res = PoE::transfer_claim(&mut runtime, "bob", "d", "alice", true);
+        assert_eq!(res, Ok(()));
+        let res = PoE::accept_claim(&mut runtime, "alice", "d");
+        assert_eq!(res, Err("Too many claims for this account."));
+
+        // O índice acompanha transferências e revogações
+        let res = PoE::transfer_claim(&mut runtime, "alice", "b", "bob", false);
+        assert_eq!(res, Ok(()));
+        assert_eq!(PoE::revoke_claim(&mut runtime, "alice", "c"), Ok(()));
+        let poe = &runtime.proof_of_existence;
+        assert_eq!(poe.claim_count(&"alice"), 1);
+        assert_eq!(poe.claim_count(&"bob"), 2);
+        let owned: Vec<_> = poe
+            .claims_of(&"bob", None, 10)
+            .into_iter()
+            .map(|(c, _)| *c)
+            .collect();
+        assert_eq!(owned, vec!["b", "d"]);
+        assert!(poe.claims_of(&"charlie", None, 10).is_empty());
+
+        // Paginação
+        let page: Vec<_> = poe
+            .all_claims(None, 2)
+            .into_iter()
+            .map(|(c, _)| *c)
+            .collect();
+        assert_eq!(page, vec!["a", "b"]);
+        let page: Vec<_> = poe
+            .all_claims(Some(&"b"), 2)
+            .into_iter()
+            .map(|(c, _)| *c)
+            .collect();
+        assert_eq!(page, vec!["d"]);
+        let page: Vec<_> = poe
+            .claims_of(&"bob", Some(&"b"), 1)
+            .into_iter()
+            .map(|(c, _)| *c)
+            .collect();
+        assert_eq!(page, vec!["d"]);
+    }
+
+    #[test]
+    fn claim_deposits() {
+        let mut runtime = TestRuntime::new();
+        runtime.balances.set_balance(&"dave", 5);
+
+        // A reivindicação reserva o depósito do proprietário
+        assert_eq!(
+            PoE::create_claim(&mut runtime, "alice", "a", vec![], None, None),
+            Ok(())
+        );
+        assert_eq!(runtime.balances.balance(&"alice"), 90);
+        assert_eq!(
+            runtime
+                .balances
+                .reserved_balance_named(&CLAIM_RESERVE_ID, &"alice"),
+            10
+        );
+
+        // Sem saldo para o depósito, a reivindicação não é criada
+        let res = PoE::create_claim(&mut runtime, "dave", "b", vec![], None, None);
+        assert_eq!(res, Err("Insufficient balance"));
+        assert_eq!(runtime.proof_of_existence.get_claim(&"b"), None);
+
+        // O depósito continua reservado de quem criou a reivindicação quando ela é transferida
+        let res = PoE::transfer_claim(&mut runtime, "alice", "a", "bob", false);
+        assert_eq!(res, Ok(()));
+        assert_eq!(
+            runtime
+                .balances
+                .reserved_balance_named(&CLAIM_RESERVE_ID, &"alice"),
+            10
+        );
+        assert_eq!(
+            runtime
+                .balances
+                .reserved_balance_named(&CLAIM_RESERVE_ID, &"bob"),
+            0
+        );
+
+        // E é devolvido a quem o pagou, mesmo que um delegado transfira a reivindicação para si
+        // mesmo antes de revogá-la
+        let res = PoE::set_delegate(runtime.pallet_mut(), "bob", "a", Some("charlie"));
+        assert_eq!(res, Ok(()));
+        let res = PoE::transfer_claim(&mut runtime, "charlie", "a", "charlie", false);
+        assert_eq!(res, Ok(()));
+        assert_eq!(PoE::revoke_claim(&mut runtime, "charlie", "a"), Ok(()));
+        assert_eq!(
+            runtime
+                .balances
+                .reserved_balance_named(&CLAIM_RESERVE_ID, &"alice"),
+            0
+        );
+        assert_eq!(runtime.balances.balance(&"alice"), 100);
+        assert_eq!(runtime.balances.balance(&"bob"), 100);
+        assert_eq!(runtime.balances.balance(&"charlie"), 100);
+    }
+
+    #[test]
+    fn expiring_claims() {
+        let mut runtime = TestRuntime::new();
+        runtime.system.inc_block_number();
+
+        let res = PoE::create_claim(&mut runtime, "alice", "a", vec![], Some(1), None);
+        assert_eq!(res, Err("Expiry must be in the future."));
+        assert_eq!(
+            PoE::create_claim(&mut runtime, "alice", "a", vec![], Some(2), None),
+            Ok(())
+        );
+        assert_eq!(
+            PoE::create_claim(&mut runtime, "alice", "b", vec![], Some(2), None),
+            Ok(())
+        );
+        assert_eq!(
+            PoE::create_claim(&mut runtime, "bob", "c", vec![], None, None),
+            Ok(())
+        );
+
+        // Renovação
+        let poe = &mut runtime.proof_of_existence;
+        assert_eq!(
+            poe.renew_claim("bob", "a", Some(5)),
+            Err("This claim is owned by someone else.")
+        );
+        assert_eq!(
+            poe.renew_claim("bob", "c", Some(5)),
+            Err("This claim does not expire.")
+        );
+        assert_eq!(
+            poe.renew_claim("alice", "a", Some(2)),
+            Err("Renewal must extend the expiry.")
+        );
+        assert_eq!(poe.renew_claim("alice", "a", Some(3)), Ok(()));
+
+        // Ao fim do bloco 2 só "b" expira, e o depósito volta para o proprietário
+        PoE::on_finalize(&mut runtime, 2);
+        assert_eq!(runtime.proof_of_existence.get_claim(&"b"), None);
+        assert_eq!(owner(&runtime, "a"), Some("alice"));
+        assert_eq!(
+            runtime
+                .balances
+                .reserved_balance_named(&CLAIM_RESERVE_ID, &"alice"),
+            10
+        );
+        assert!(matches!(
+            runtime.proof_of_existence.events(),
+            [Event::ClaimExpired {
+                claim: "b",
+                owner: "alice"
+            }]
+        ));
+
+        // Sem expiração, a reivindicação não é removida
+        let poe = &mut runtime.proof_of_existence;
+        assert_eq!(poe.renew_claim("alice", "a", None), Ok(()));
+        PoE::on_finalize(&mut runtime, 3);
+        assert_eq!(owner(&runtime, "a"), Some("alice"));
+        assert_eq!(owner(&runtime, "c"), Some("bob"));
+        assert_eq!(runtime.proof_of_existence.events().len(), 1);
+    }
+
+    #[test]
+    fn attestations() {
+        let mut runtime = TestRuntime::new();
+        let res = PoE::create_claim(&mut runtime, "alice", "contrato", vec![], None, Some(3));
+        assert_eq!(res, Err("Threshold exceeds the attester limit."));
+        let res = PoE::create_claim(&mut runtime, "alice", "contrato", vec![], None, Some(2));
+        assert_eq!(res, Ok(()));
+        assert_eq!(
+            PoE::create_claim(&mut runtime, "alice", "nota", vec![], None, None),
+            Ok(())
+        );
+
+        // Sem limiar, a reivindicação já é considerada atestada
+        let poe = &mut runtime.proof_of_existence;
+        assert!(poe.is_attested(&"nota"));
+        assert!(!poe.is_attested(&"contrato"));
+        assert!(!poe.is_attested(&"outro"));
+
+        assert_eq!(
+            poe.attest("bob", "outro"),
+            Err("This claim does not exist.")
+        );
+        let res = poe.attest("alice", "contrato");
+        assert_eq!(res, Err("The owner cannot attest their own claim."));
+        assert_eq!(poe.attest("bob", "contrato"), Ok(()));
+        assert_eq!(poe.attest("bob", "contrato"), Err("Already attested."));
+        assert!(!poe.is_attested(&"contrato"));
+        assert_eq!(poe.attest("charlie", "contrato"), Ok(()));
+        assert!(poe.is_attested(&"contrato"));
+        assert_eq!(poe.attest("dave", "contrato"), Err("Too many attesters."));
+
+        // Retirada de atestado
+        let res = poe.revoke_attestation("dave", "contrato");
+        assert_eq!(res, Err("Not an attester of this claim."));
+        assert_eq!(poe.revoke_attestation("bob", "contrato"), Ok(()));
+        assert!(!poe.is_attested(&"contrato"));
+        let claim = poe.get_claim(&"contrato").unwrap();
+        assert_eq!(claim.attesters, vec!["charlie"]);
+        assert!(matches!(
+            poe.events(),
+            [
+                Event::Attested {
+                    attester: "bob",
+                    ..
+                },
+                Event::Attested {
+                    attester: "charlie",
+                    ..
+                },
+                Event::AttestationRevoked {
+                    attester: "bob",
+                    ..
+                },
+            ]
+        ));
+
+        // Um atestador que recebe a reivindicação deixa de atestá-la
+        let res = PoE::transfer_claim(&mut runtime, "alice", "contrato", "charlie", false);
         assert_eq!(res, Ok(()));
-        assert_eq!(pallet.get_claim(&"outro conteudo"), None);
+        let claim = runtime.proof_of_existence.get_claim(&"contrato").unwrap();
+        assert!(claim.attesters.is_empty());
     }
 }
@@ -0,0 +1,671 @@
+use crate::support::{
+    call_hash, storage_map, Dispatch, DispatchResult, GetPallet, Hooks, Origin, Storage,
+    ValidateUnsigned,
+};
+use crate::system;
+use core::fmt::Debug;
+use num::traits::{CheckedAdd, Zero};
+use std::collections::BTreeMap;
+
+/// Decide quais chamadas um tipo de proxy pode fazer em nome da conta real.
+pub trait InstanceFilter<Call> {
+    /// Retorna se `call` é permitida.
+    fn filter(&self, call: &Call) -> bool;
+}
+
+/// Os eventos emitidos pelo Módulo Proxy.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Event<T: Config> {
+    /// `delegator` registrou `delegatee` como proxy.
+    ProxyAdded {
+        delegator: T::AccountId,
+        delegatee: T::AccountId,
+        proxy_type: T::ProxyType,
+        delay: T::BlockNumber,
+    },
+    /// `delegator` removeu o proxy `delegatee`.
+    ProxyRemoved {
+        delegator: T::AccountId,
+        delegatee: T::AccountId,
+        proxy_type: T::ProxyType,
+        delay: T::BlockNumber,
+    },
+    /// `proxy` anunciou a chamada de hash `call_hash` em nome de `real`.
+    Announced {
+        real: T::AccountId,
+        proxy: T::AccountId,
+        call_hash: T::Hash,
+    },
+    /// Uma chamada foi despachada em nome da conta real, com o resultado `result`.
+    ProxyExecuted { result: DispatchResult },
+}
+
+/// Um proxy registrado por uma conta.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ProxyDefinition<AccountId, ProxyType, BlockNumber> {
+    /// A conta que pode agir em nome da conta real.
+    pub delegate: AccountId,
+    /// O tipo do proxy, que limita as chamadas que ele pode fazer.
+    pub proxy_type: ProxyType,
+    /// Quantos blocos uma chamada precisa ser anunciada antes de ser feita. Com zero, o proxy
+    /// age imediatamente.
+    pub delay: BlockNumber,
+}
+
+/// O tipo dos proxies registrados no runtime.
+pub type ProxyDefinitionOf<T> = ProxyDefinition<
+    <T as system::Config>::AccountId,
+    <T as Config>::ProxyType,
+    <T as system::Config>::BlockNumber,
+>;
+
+/// O anúncio de uma chamada que um proxy com atraso pretende fazer.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Announcement<AccountId, Hash, BlockNumber> {
+    /// A conta real em nome da qual a chamada será feita.
+    pub real: AccountId,
+    /// O hash da chamada.
+    pub call_hash: Hash,
+    /// O bloco em que a chamada foi anunciada.
+    pub height: BlockNumber,
+}
+
+/// A característica de configuração do Módulo Proxy.
+pub trait Config:
+    system::Config
+    + Sized
+    + Dispatch<Caller = Origin<Self::AccountId>, Call = Self::RuntimeCall>
+    + GetPallet<Pallet<Self>>
+    + GetPallet<system::Pallet<Self>>
+{
+    /// O tipo das chamadas do runtime, que podem ser de qualquer pallet.
+    type RuntimeCall: Debug;
+    /// Os tipos de proxy, cada um com as chamadas que permite.
+    type ProxyType: InstanceFilter<Self::RuntimeCall> + Debug + Clone + Eq;
+    /// O número máximo de proxies de uma conta.
+    const MAX_PROXIES: u32;
+    /// O número máximo de anúncios pendentes de um proxy.
+    const MAX_PENDING: u32;
+}
+
+/// Este é o Módulo Proxy.
+/// Permite que uma conta, a conta real, registre outras contas como proxies, que podem fazer
+/// chamadas em seu nome. Assim, uma carteira fria pode deixar uma carteira quente fazer apenas
+/// transferências, ou exigir que as chamadas sejam anunciadas com antecedência.
+#[derive(Debug, Clone)]
+pub struct Pallet<T: Config> {
+    /// Os proxies de cada conta real.
+    #[allow(clippy::type_complexity)]
+    proxies:
+        BTreeMap<T::AccountId, Vec<ProxyDefinition<T::AccountId, T::ProxyType, T::BlockNumber>>>,
+    /// Os anúncios pendentes de cada proxy.
+    #[allow(clippy::type_complexity)]
+    announcements: BTreeMap<T::AccountId, Vec<Announcement<T::AccountId, T::Hash, T::BlockNumber>>>,
+    /// Os eventos emitidos por este pallet no bloco atual, em ordem.
+    events: Vec<Event<T>>,
+}
+
+#[allow(clippy::boxed_local)]
+#[macros::call]
+impl<T: Config> Pallet<T> {
+    /// Registra `delegate` como proxy de `caller`, do tipo `proxy_type`, com o atraso `delay`.
+    pub fn add_proxy(
+        &mut self,
+        caller: T::AccountId,
+        delegate: T::AccountId,
+        proxy_type: T::ProxyType,
+        delay: T::BlockNumber,
+    ) -> DispatchResult {
+        if delegate == caller {
+            return Err("An account cannot be its own proxy.");
+        }
+        let definition = ProxyDefinition {
+            delegate: delegate.clone(),
+            proxy_type: proxy_type.clone(),
+            delay,
+        };
+        let proxies = self.proxies.entry(caller.clone()).or_default();
+        if proxies.contains(&definition) {
+            return Err("Proxy already registered.");
+        }
+        if proxies.len() >= T::MAX_PROXIES as usize {
+            return Err("Too many proxies.");
+        }
+        proxies.push(definition);
+
+        self.events.push(Event::ProxyAdded {
+            delegator: caller,
+            delegatee: delegate,
+            proxy_type,
+            delay,
+        });
+        Ok(())
+    }
+
+    /// Remove o proxy `delegate` de `caller`, registrado com o tipo `proxy_type` e o atraso
+    /// `delay`.
+    pub fn remove_proxy(
+        &mut self,
+        caller: T::AccountId,
+        delegate: T::AccountId,
+        proxy_type: T::ProxyType,
+        delay: T::BlockNumber,
+    ) -> DispatchResult {
+        let definition = ProxyDefinition {
+            delegate,
+            proxy_type,
+            delay,
+        };
+        let proxies = self.proxies.get_mut(&caller).ok_or("Proxy not found.")?;
+        let index = proxies
+            .iter()
+            .position(|p| p == &definition)
+            .ok_or("Proxy not found.")?;
+        let ProxyDefinition {
+            delegate,
+            proxy_type,
+            delay,
+        } = proxies.remove(index);
+        if proxies.is_empty() {
+            self.proxies.remove(&caller);
+        }
+
+        self.events.push(Event::ProxyRemoved {
+            delegator: caller,
+            delegatee: delegate,
+            proxy_type,
+            delay,
+        });
+        Ok(())
+    }
+
+    /// Remove todos os proxies de `caller`.
+    pub fn remove_proxies(&mut self, caller: T::AccountId) -> DispatchResult {
+        for definition in self.proxies.remove(&caller).unwrap_or_default() {
+            self.events.push(Event::ProxyRemoved {
+                delegator: caller.clone(),
+                delegatee: definition.delegate,
+                proxy_type: definition.proxy_type,
+                delay: definition.delay,
+            });
+        }
+        Ok(())
+    }
+
+    /// Despacha `call` em nome de `real`, de quem `caller` precisa ser um proxy sem atraso que
+    /// permita a chamada. Se `force_proxy_type` for dado, apenas proxies desse tipo são usados.
+    /// O resultado de `call` é registrado no evento `ProxyExecuted`.
+    pub fn proxy(
+        runtime: &mut T,
+        caller: T::AccountId,
+        real: T::AccountId,
+        force_proxy_type: Option<T::ProxyType>,
+        call: Box<T::RuntimeCall>,
+    ) -> DispatchResult {
+        let pallet: &Self = runtime.pallet();
+        let definition = pallet.find_proxy(&real, &caller, force_proxy_type.as_ref())?;
+        if !definition.delay.is_zero() {
+            return Err("This proxy must announce the call first.");
+        }
+        Self::do_proxy(runtime, definition.proxy_type, real, *call)
+    }
+
+    /// Anuncia a chamada de hash `call_hash`, que `caller` pretende fazer em nome de `real` com
+    /// `proxy_announced`, depois do atraso do proxy.
+    pub fn announce(
+        runtime: &mut T,
+        caller: T::AccountId,
+        real: T::AccountId,
+        call_hash: T::Hash,
+    ) -> DispatchResult {
+        let system: &system::Pallet<T> = runtime.pallet();
+        let height = system.block_number();
+        let pallet: &mut Self = runtime.pallet_mut();
+        pallet.find_proxy(&real, &caller, None)?;
+
+        let announcements = pallet.announcements.entry(caller.clone()).or_default();
+        if announcements.len() >= T::MAX_PENDING as usize {
+            return Err("Too many announcements.");
+        }
+        announcements.push(Announcement {
+            real: real.clone(),
+            call_hash,
+            height,
+        });
+
+        pallet.events.push(Event::Announced {
+            real,
+            proxy: caller,
+            call_hash,
+        });
+        Ok(())
+    }
+
+    /// Remove um anúncio feito por `caller` em nome de `real`.
+    pub fn remove_announcement(
+        &mut self,
+        caller: T::AccountId,
+        real: T::AccountId,
+        call_hash: T::Hash,
+    ) -> DispatchResult {
+        self.take_announcement(&caller, &real, &call_hash)?;
+        Ok(())
+    }
+
+    /// Rejeita um anúncio feito por `delegate` em nome de `caller`, que não poderá mais fazer a
+    /// chamada.
+    pub fn reject_announcement(
+        &mut self,
+        caller: T::AccountId,
+        delegate: T::AccountId,
+        call_hash: T::Hash,
+    ) -> DispatchResult {
+        self.take_announcement(&delegate, &caller, &call_hash)?;
+        Ok(())
+    }
+
+    /// Despacha `call`, anunciada por `delegate` em nome de `real`, depois que o atraso do proxy
+    /// tiver passado. Qualquer conta pode fazer esta chamada. O resultado de `call` é registrado no
+    /// evento `ProxyExecuted`.
+    pub fn proxy_announced(
+        runtime: &mut T,
+        _caller: T::AccountId,
+        delegate: T::AccountId,
+        real: T::AccountId,
+        force_proxy_type: Option<T::ProxyType>,
+        call: Box<T::RuntimeCall>,
+    ) -> DispatchResult {
+        let call_hash = call_hash::<T::Hashing>(&call);
+        let system: &system::Pallet<T> = runtime.pallet();
+        let now = system.block_number();
+        let pallet: &mut Self = runtime.pallet_mut();
+        let definition = pallet.find_proxy(&real, &delegate, force_proxy_type.as_ref())?;
+
+        let announcement = pallet
+            .announcements
+            .get(&delegate)
+            .and_then(|list| {
+                list.iter()
+                    .find(|a| a.real == real && a.call_hash == call_hash)
+            })
+            .ok_or("No announcement for this call.")?;
+        // Um atraso tão grande que o bloco em que a chamada fica pronta não cabe no tipo do número
+        // do bloco nunca passa.
+        let ready = announcement
+            .height
+            .checked_add(&definition.delay)
+            .is_some_and(|ready_at| ready_at <= now);
+        if !ready {
+            return Err("The announcement delay has not passed.");
+        }
+        pallet.take_announcement(&delegate, &real, &call_hash)?;
+
+        Self::do_proxy(runtime, definition.proxy_type, real, *call)
+    }
+
+    /// Remove o anúncio de `delegate` da chamada de hash `call_hash` em nome de `real`, depois que
+    /// `delegate` deixou de ser proxy de `real`. Um anúncio assim nunca pode ser feito, mas
+    /// voltaria a valer, com o atraso já cumprido, se o proxy fosse registrado de novo. Como
+    /// ninguém perde nada com a remoção, ela é uma transação não assinada.
+    pub fn remove_stale_announcement(
+        &mut self,
+        origin: Origin<T::AccountId>,
+        delegate: T::AccountId,
+        real: T::AccountId,
+        call_hash: T::Hash,
+    ) -> DispatchResult {
+        origin.ensure_none()?;
+        self.ensure_stale(&delegate, &real, &call_hash)?;
+        self.take_announcement(&delegate, &real, &call_hash)
+    }
+}
+
+impl<T: Config> Pallet<T> {
+    /// Cria uma nova instância do Módulo Proxy.
+    pub fn new() -> Self {
+        Self {
+            proxies: BTreeMap::new(),
+            announcements: BTreeMap::new(),
+            events: Vec::new(),
+        }
+    }
+
+    /// Obtém os proxies registrados por `real`.
+    pub fn proxies(&self, real: &T::AccountId) -> &[ProxyDefinitionOf<T>] {
+        self.proxies
+            .get(real)
+            .map(Vec::as_slice)
+            .unwrap_or_default()
+    }
+
+    /// Obtém os anúncios pendentes de `delegate`.
+    pub fn announcements(
+        &self,
+        delegate: &T::AccountId,
+    ) -> &[Announcement<T::AccountId, T::Hash, T::BlockNumber>] {
+        self.announcements
+            .get(delegate)
+            .map(Vec::as_slice)
+            .unwrap_or_default()
+    }
+
+    /// Obtém os eventos emitidos por este pallet no bloco atual, em ordem.
+    pub fn events(&self) -> &[Event<T>] {
+        &self.events
+    }
+
+    /// Encontra um proxy `delegate` de `real`, do tipo `proxy_type` se for dado. Se houver mais
+    /// de um, escolhe o de menor atraso.
+    fn find_proxy(
+        &self,
+        real: &T::AccountId,
+        delegate: &T::AccountId,
+        proxy_type: Option<&T::ProxyType>,
+    ) -> Result<ProxyDefinitionOf<T>, &'static str> {
+        self.proxies(real)
+            .iter()
+            .filter(|p| &p.delegate == delegate && proxy_type.is_none_or(|t| t == &p.proxy_type))
+            .min_by_key(|p| p.delay)
+            .cloned()
+            .ok_or("Not a proxy of this account.")
+    }
+
+    /// Verifica se há um anúncio de `delegate` da chamada de hash `call_hash` em nome de `real`, e
+    /// se `delegate` não é mais proxy de `real`.
+    fn ensure_stale(
+        &self,
+        delegate: &T::AccountId,
+        real: &T::AccountId,
+        call_hash: &T::Hash,
+    ) -> DispatchResult {
+        let announced = self
+            .announcements(delegate)
+            .iter()
+            .any(|a| &a.real == real && &a.call_hash == call_hash);
+        if !announced {
+            return Err("No announcement for this call.");
+        }
+        if self.find_proxy(real, delegate, None).is_ok() {
+            return Err("The announcement is not stale.");
+        }
+        Ok(())
+    }
+
+    /// Remove o anúncio de `delegate` da chamada de hash `call_hash` em nome de `real`.
+    fn take_announcement(
+        &mut self,
+        delegate: &T::AccountId,
+        real: &T::AccountId,
+        call_hash: &T::Hash,
+    ) -> DispatchResult {
+        let announcements = self
+            .announcements
+            .get_mut(delegate)
+            .ok_or("No announcement for this call.")?;
+        let index = announcements
+            .iter()
+            .position(|a| &a.real == real && &a.call_hash == call_hash)
+            .ok_or("No announcement for this call.")?;
+        announcements.remove(index);
+        if announcements.is_empty() {
+            self.announcements.remove(delegate);
+        }
+        Ok(())
+    }
+
+    /// Despacha `call` em nome de `real`, se o tipo de proxy `proxy_type` permitir.
+    fn do_proxy(
+        runtime: &mut T,
+        proxy_type: T::ProxyType,
+        real: T::AccountId,
+        call: T::RuntimeCall,
+    ) -> DispatchResult {
+        if !proxy_type.filter(&call) {
+            return Err("This call is not allowed for the proxy type.");
+        }
+        let result = runtime.dispatch(Origin::Signed(real), call);
+        let pallet: &mut Self = runtime.pallet_mut();
+        pallet.events.push(Event::ProxyExecuted { result });
+        Ok(())
+    }
+}
+
+impl<T: Config> Hooks<T> for Pallet<T> {
+    fn on_initialize(runtime: &mut T, _n: T::BlockNumber) {
+        let pallet: &mut Self = runtime.pallet_mut();
+        pallet.events.clear();
+    }
+}
+
+/// Apenas a remoção de anúncios obsoletos é aceita como transação não assinada.
+impl<T: Config> ValidateUnsigned<T, Call<T>> for Pallet<T> {
+    fn validate_unsigned(runtime: &T, call: &Call<T>) -> DispatchResult {
+        let pallet: &Self = runtime.pallet();
+        match call {
+            Call::remove_stale_announcement {
+                delegate,
+                real,
+                call_hash,
+            } => pallet.ensure_stale(delegate, real, call_hash),
+            _ => Err("Unsigned transactions are not accepted for this call."),
+        }
+    }
+}
+
+impl<T: Config> Storage for Pallet<T> {
+    fn storage(&self) -> Vec<(Vec<u8>, Vec<u8>)> {
+        let mut storage: Vec<_> = storage_map("proxies", &self.proxies).collect();
+        storage.extend(storage_map("announcements", &self.announcements));
+        storage
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::tests::{block, new_runtime, set_timestamp, transfer};
+    use crate::{proof_of_existence, support, types, ProxyType, Runtime, RuntimeCall};
+
+    type Proxy = Pallet<Runtime>;
+
+    #[test]
+    fn transfer_proxy() {
+        let (mut runtime, [cold, hot, backup]) = new_runtime(["cold", "hot", "backup"], 100);
+
+        // A carteira quente só pode fazer transferências em nome da fria
+        let res = runtime
+            .proxy
+            .add_proxy(cold.clone(), hot.clone(), ProxyType::Transfer, 0);
+        assert_eq!(res, Ok(()));
+        let res = runtime
+            .proxy
+            .add_proxy(cold.clone(), hot.clone(), ProxyType::Transfer, 0);
+        assert_eq!(res, Err("Proxy already registered."));
+        let res = Proxy::proxy(
+            &mut runtime,
+            hot.clone(),
+            cold.clone(),
+            None,
+            transfer("dave", 30),
+        );
+        assert_eq!(res, Ok(()));
+        assert_eq!(runtime.balances.balance(&cold), 70);
+        assert_eq!(runtime.balances.balance(&hot), 100);
+
+        let claim = Box::new(RuntimeCall::from(proof_of_existence::Call::create_claim {
+            claim: support::call_hash::<types::Hashing>(&transfer("dave", 30)),
+            description: Vec::new(),
+            expires_at: None,
+            attestation_threshold: None,
+        }));
+        let res = Proxy::proxy(&mut runtime, hot.clone(), cold.clone(), None, claim);
+        assert_eq!(res, Err("This call is not allowed for the proxy type."));
+        let res = Proxy::proxy(
+            &mut runtime,
+            backup,
+            cold.clone(),
+            None,
+            transfer("dave", 1),
+        );
+        assert_eq!(res, Err("Not a proxy of this account."));
+        let res = Proxy::proxy(
+            &mut runtime,
+            hot,
+            cold,
+            Some(ProxyType::Any),
+            transfer("dave", 1),
+        );
+        assert_eq!(res, Err("Not a proxy of this account."));
+    }
+
+    #[test]
+    fn announced_calls() {
+        let (mut runtime, [cold, hot, backup]) = new_runtime(["cold", "hot", "backup"], 100);
+
+        // Um proxy com atraso precisa anunciar a chamada e esperar
+        let res = runtime
+            .proxy
+            .add_proxy(cold.clone(), backup.clone(), ProxyType::Any, 2);
+        assert_eq!(res, Ok(()));
+        let res = Proxy::proxy(
+            &mut runtime,
+            backup.clone(),
+            cold.clone(),
+            None,
+            transfer("dave", 1),
+        );
+        assert_eq!(res, Err("This proxy must announce the call first."));
+        let call_hash = support::call_hash::<types::Hashing>(&transfer("dave", 100));
+        let res = Proxy::announce(&mut runtime, backup.clone(), cold.clone(), call_hash);
+        assert_eq!(res, Ok(()));
+
+        // Depois do atraso, qualquer conta pode despachar a chamada anunciada, uma única vez
+        let announced = |runtime: &mut Runtime| {
+            let call = transfer("dave", 100);
+            Proxy::proxy_announced(
+                runtime,
+                hot.clone(),
+                backup.clone(),
+                cold.clone(),
+                None,
+                call,
+            )
+        };
+        assert_eq!(
+            announced(&mut runtime),
+            Err("The announcement delay has not passed.")
+        );
+        runtime.system.inc_block_number();
+        runtime.system.inc_block_number();
+        assert_eq!(announced(&mut runtime), Ok(()));
+        assert_eq!(runtime.balances.balance(&cold), 0);
+        assert_eq!(
+            announced(&mut runtime),
+            Err("No announcement for this call.")
+        );
+    }
+
+    #[test]
+    fn unreachable_delay() {
+        let (mut runtime, [cold, hot]) = new_runtime(["cold", "hot"], 100);
+
+        // Um atraso tão grande que o bloco em que a chamada ficaria pronta não existe nunca passa
+        let res = runtime
+            .proxy
+            .add_proxy(cold.clone(), hot.clone(), ProxyType::Any, u32::MAX);
+        assert_eq!(res, Ok(()));
+        let call_hash = support::call_hash::<types::Hashing>(&transfer("dave", 70));
+        let res = Proxy::announce(&mut runtime, hot.clone(), cold.clone(), call_hash);
+        assert_eq!(res, Ok(()));
+        runtime.system.inc_block_number();
+        let res = Proxy::proxy_announced(
+            &mut runtime,
+            hot.clone(),
+            hot,
+            cold.clone(),
+            Some(ProxyType::Any),
+            transfer("dave", 70),
+        );
+        assert_eq!(res, Err("The announcement delay has not passed."));
+        assert_eq!(runtime.balances.balance(&cold), 100);
+    }
+
+    #[test]
+    fn reject_and_remove() {
+        let (mut runtime, [cold, hot, backup]) = new_runtime(["cold", "hot", "backup"], 100);
+        let res = runtime
+            .proxy
+            .add_proxy(cold.clone(), hot.clone(), ProxyType::Transfer, 0);
+        assert_eq!(res, Ok(()));
+        let res = runtime
+            .proxy
+            .add_proxy(cold.clone(), backup.clone(), ProxyType::Any, 2);
+        assert_eq!(res, Ok(()));
+
+        // A conta real pode rejeitar anúncios e remover proxies
+        let call_hash = support::call_hash::<types::Hashing>(&transfer("dave", 70));
+        let res = Proxy::announce(&mut runtime, backup.clone(), cold.clone(), call_hash);
+        assert_eq!(res, Ok(()));
+        let res = runtime
+            .proxy
+            .reject_announcement(cold.clone(), backup.clone(), call_hash);
+        assert_eq!(res, Ok(()));
+        assert!(runtime.proxy.announcements(&backup).is_empty());
+        let res = runtime
+            .proxy
+            .remove_proxy(cold.clone(), hot, ProxyType::Transfer, 0);
+        assert_eq!(res, Ok(()));
+        assert_eq!(runtime.proxy.proxies(&cold).len(), 1);
+        assert_eq!(runtime.proxy.remove_proxies(cold.clone()), Ok(()));
+        assert!(runtime.proxy.proxies(&cold).is_empty());
+        assert!(matches!(
+            runtime.proxy.events().last(),
+            Some(Event::ProxyRemoved { delay: 2, .. })
+        ));
+    }
+
+    #[test]
+    fn remove_stale_announcement() {
+        let (mut runtime, [cold, backup]) = new_runtime(["cold", "backup"], 100);
+        let call_hash = support::call_hash::<types::Hashing>(&transfer("dave", 1));
+        let remove_stale_call = || {
+            RuntimeCall::from(Call::remove_stale_announcement {
+                delegate: backup.clone(),
+                real: cold.clone(),
+                call_hash,
+            })
+        };
+        let remove_stale = |block_number| {
+            let unsigned = support::Extrinsic::Unsigned {
+                call: remove_stale_call(),
+            };
+            block(block_number, vec![set_timestamp(block_number), unsigned])
+        };
+        let res = runtime
+            .proxy
+            .add_proxy(cold.clone(), backup.clone(), ProxyType::Any, 2);
+        assert_eq!(res, Ok(()));
+        let res = Proxy::announce(&mut runtime, backup.clone(), cold.clone(), call_hash);
+        assert_eq!(res, Ok(()));
+
+        // Enquanto o proxy existir, o anúncio não pode ser removido sem assinatura
+        let res = runtime.execute_block(remove_stale(1));
+        assert_eq!(res, Err("The announcement is not stale."));
+        assert_eq!(runtime.proxy.announcements(&backup).len(), 1);
+
+        // Depois que o proxy é removido, qualquer um pode remover o anúncio
+        let res = runtime
+            .proxy
+            .remove_proxy(cold.clone(), backup.clone(), ProxyType::Any, 2);
+        assert_eq!(res, Ok(()));
+        let res = runtime.dispatch(Origin::Signed(cold.clone()), remove_stale_call());
+        assert_eq!(res, Err("Bad origin: expected no origin."));
+        let res = runtime.execute_block(remove_stale(1));
+        assert_eq!(res, Ok(()));
+        assert!(runtime.proxy.announcements(&backup).is_empty());
+
+        // Um anúncio que não existe mais não pode ser removido de novo
+        let res = runtime.execute_block(remove_stale(2));
+        assert_eq!(res, Err("No announcement for this call."));
+    }
+}
@@ -0,0 +1,617 @@
+use crate::support::{
+    storage_map, BalanceOf, Dispatch, DispatchResult, GetPallet, Hooks, NamedReservableCurrency,
+    Origin, ReserveIdentifier, Storage,
+};
+use crate::system;
+use core::fmt::Debug;
+use num::traits::{CheckedAdd, Zero};
+use std::collections::BTreeMap;
+
+/// O identificador da reserva que guarda os depósitos das tarefas agendadas.
+pub const SCHEDULER_RESERVE_ID: ReserveIdentifier = *b"schedulr";
+
+/// O tipo de saldo dos depósitos das tarefas agendadas.
+pub type DepositOf<T> = BalanceOf<<T as Config>::Currency, T>;
+
+/// O nome de uma tarefa agendada, usado para cancelá-la sem saber onde ela está na agenda.
+pub type TaskName = [u8; 8];
+
+/// A posição de uma tarefa na agenda: o bloco e o índice dentro do bloco.
+pub type TaskAddress<BlockNumber> = (BlockNumber, u32);
+
+/// Os eventos emitidos pelo Módulo Scheduler.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Event<T: Config> {
+    /// Uma tarefa foi agendada em `when`, na posição `index`.
+    Scheduled { when: T::BlockNumber, index: u32 },
+    /// A tarefa em `when`, na posição `index`, foi cancelada.
+    Canceled { when: T::BlockNumber, index: u32 },
+    /// A tarefa `task` foi despachada, com o resultado `result`.
+    Dispatched {
+        task: TaskAddress<T::BlockNumber>,
+        id: Option<TaskName>,
+        result: DispatchResult,
+    },
+    /// A tarefa periódica `task` não pôde ser agendada de novo, pois a agenda estava cheia ou o
+    /// próximo bloco não cabe no tipo do número do bloco.
+    PeriodicFailed {
+        task: TaskAddress<T::BlockNumber>,
+        id: Option<TaskName>,
+    },
+}
+
+/// Uma chamada agendada.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Scheduled<Call, AccountId, BlockNumber, Balance> {
+    /// O nome da tarefa, se houver.
+    pub maybe_id: Option<TaskName>,
+    /// A prioridade da tarefa: num mesmo bloco, valores menores são despachados antes.
+    pub priority: u8,
+    /// A chamada a despachar.
+    pub call: Call,
+    /// A origem com a qual a chamada é despachada.
+    pub origin: Origin<AccountId>,
+    /// Se a tarefa for periódica, o período e quantas vezes ela ainda vai ser despachada.
+    pub maybe_periodic: Option<(BlockNumber, u32)>,
+    /// O depósito reservado da conta que agendou a tarefa, devolvido quando ela termina ou é
+    /// cancelada.
+    pub deposit: Balance,
+}
+
+/// O tipo das tarefas agendadas no runtime.
+pub type ScheduledOf<T> = Scheduled<
+    <T as Config>::RuntimeCall,
+    <T as system::Config>::AccountId,
+    <T as system::Config>::BlockNumber,
+    DepositOf<T>,
+>;
+
+/// A característica de configuração do Módulo Scheduler.
+pub trait Config:
+    system::Config
+    + Sized
+    + Dispatch<Caller = Origin<Self::AccountId>, Call = Self::RuntimeCall>
+    + GetPallet<Pallet<Self>>
+    + GetPallet<system::Pallet<Self>>
+{
+    /// O tipo das chamadas do runtime, que podem ser de qualquer pallet.
+    type RuntimeCall: Debug + Clone;
+    /// A moeda da qual é reservado o depósito de cada tarefa agendada por uma conta.
+    type Currency: NamedReservableCurrency<Self>;
+    /// O depósito reservado de quem agenda uma tarefa, enquanto ela estiver na agenda.
+    const SCHEDULE_DEPOSIT: DepositOf<Self>;
+    /// O número máximo de tarefas num mesmo bloco, o que limita o trabalho feito no início de
+    /// cada bloco.
+    const MAX_SCHEDULED_PER_BLOCK: u32;
+}
+
+/// Este é o Módulo Scheduler.
+/// Guarda chamadas para serem despachadas no início de blocos futuros, uma vez ou
+/// periodicamente, com a origem de quem as agendou. Como a agenda de cada bloco é limitada, uma
+/// conta paga um depósito por tarefa enquanto ela ocupa a agenda; a origem privilegiada não paga.
+#[derive(Debug, Clone)]
+pub struct Pallet<T: Config> {
+    /// As tarefas de cada bloco. Uma tarefa cancelada ou despachada deixa um `None`, para que a
+    /// posição das outras não mude.
+    agenda: BTreeMap<T::BlockNumber, Vec<Option<ScheduledOf<T>>>>,
+    /// A posição de cada tarefa com nome.
+    lookup: BTreeMap<TaskName, TaskAddress<T::BlockNumber>>,
+    /// Os eventos emitidos por este pallet no bloco atual, em ordem.
+    events: Vec<Event<T>>,
+}
+
+#[allow(clippy::boxed_local)]
+#[macros::call]
+impl<T: Config> Pallet<T> {
+    /// Agenda `call` para ser despachada com `origin` no início do bloco `when`. Se
+    /// `maybe_periodic` for `(period, count)`, a chamada é despachada `count` vezes, a cada
+    /// `period` blocos. Uma conta paga o depósito `SCHEDULE_DEPOSIT` pela tarefa.
+    pub fn schedule(
+        runtime: &mut T,
+        origin: Origin<T::AccountId>,
+        when: T::BlockNumber,
+        maybe_periodic: Option<(T::BlockNumber, u32)>,
+        priority: u8,
+        call: Box<T::RuntimeCall>,
+    ) -> DispatchResult {
+        let task = Scheduled {
+            maybe_id: None,
+            priority,
+            call: *call,
+            origin,
+            maybe_periodic,
+            deposit: Zero::zero(),
+        };
+        Self::do_schedule(runtime, when, task)?;
+        Ok(())
+    }
+
+    /// Agenda `call` como `schedule`, com o nome `id`, que pode ser usado para cancelá-la.
+    #[allow(clippy::too_many_arguments)]
+    pub fn schedule_named(
+        runtime: &mut T,
+        origin: Origin<T::AccountId>,
+        id: TaskName,
+        when: T::BlockNumber,
+        maybe_periodic: Option<(T::BlockNumber, u32)>,
+        priority: u8,
+        call: Box<T::RuntimeCall>,
+    ) -> DispatchResult {
+        let pallet: &Self = runtime.pallet();
+        if pallet.lookup.contains_key(&id) {
+            return Err("Task name already in use.");
+        }
+        let task = Scheduled {
+            maybe_id: Some(id),
+            priority,
+            call: *call,
+            origin,
+            maybe_periodic,
+            deposit: Zero::zero(),
+        };
+        Self::do_schedule(runtime, when, task)?;
+        Ok(())
+    }
+
+    /// Cancela a tarefa em `when`, na posição `index`, e devolve o seu depósito. Apenas a origem
+    /// que agendou a tarefa, ou a origem privilegiada, pode cancelá-la.
+    pub fn cancel(
+        runtime: &mut T,
+        origin: Origin<T::AccountId>,
+        when: T::BlockNumber,
+        index: u32,
+    ) -> DispatchResult {
+        Self::do_cancel(runtime, &origin, (when, index))
+    }
+
+    /// Cancela a tarefa de nome `id`, como `cancel`.
+    pub fn cancel_named(
+        runtime: &mut T,
+        origin: Origin<T::AccountId>,
+        id: TaskName,
+    ) -> DispatchResult {
+        let pallet: &Self = runtime.pallet();
+        let address = *pallet.lookup.get(&id).ok_or("Task not found.")?;
+        Self::do_cancel(runtime, &origin, address)
+    }
+}
+
+impl<T: Config> Pallet<T> {
+    /// Cria uma nova instância do Módulo Scheduler.
+    pub fn new() -> Self {
+        Self {
+            agenda: BTreeMap::new(),
+            lookup: BTreeMap::new(),
+            events: Vec::new(),
+        }
+    }
+
+    /// Obtém a tarefa em `when`, na posição `index`, se houver.
+    pub fn task(&self, when: T::BlockNumber, index: u32) -> Option<&ScheduledOf<T>> {
+        self.agenda.get(&when)?.get(index as usize)?.as_ref()
+    }
+
+    /// Obtém a posição da tarefa de nome `id`, se houver.
+    pub fn lookup(&self, id: &TaskName) -> Option<TaskAddress<T::BlockNumber>> {
+        self.lookup.get(id).copied()
+    }
+
+    /// Obtém os eventos emitidos por este pallet no bloco atual, em ordem.
+    pub fn events(&self) -> &[Event<T>] {
+        &self.events
+    }
+
+    /// Valida e agenda `task` no bloco `when`, reserva o depósito de quem a agendou, e retorna a
+    /// posição da tarefa.
+    fn do_schedule(
+        runtime: &mut T,
+        when: T::BlockNumber,
+        mut task: ScheduledOf<T>,
+    ) -> Result<TaskAddress<T::BlockNumber>, &'static str> {
+        let system: &system::Pallet<T> = runtime.pallet();
+        if when <= system.block_number() {
+            return Err("Target block must be in the future.");
+        }
+        match task.maybe_periodic {
+            Some((period, _)) if period.is_zero() => {
+                return Err("Period must be greater than zero.")
+            }
+            // Uma tarefa que só vai ser despachada uma vez não é periódica.
+            Some((_, count)) if count <= 1 => task.maybe_periodic = None,
+            Some((period, _)) if when.checked_add(&period).is_none() => {
+                return Err("Period overflows the block number.")
+            }
+            _ => {}
+        }
+        let pallet: &Self = runtime.pallet();
+        if !pallet.has_room(when) {
+            return Err("Agenda is full.");
+        }
+
+        match &task.origin {
+            Origin::Root => {}
+            Origin::Signed(who) => {
+                T::Currency::reserve_named(
+                    runtime,
+                    &SCHEDULER_RESERVE_ID,
+                    who,
+                    T::SCHEDULE_DEPOSIT,
+                )?;
+                task.deposit = T::SCHEDULE_DEPOSIT;
+            }
+            Origin::None => return Err("Bad origin: expected a signed account or root."),
+        }
+        let pallet: &mut Self = runtime.pallet_mut();
+        let address = pallet.place(when, task);
+        pallet.events.push(Event::Scheduled {
+            when,
+            index: address.1,
+        });
+        Ok(address)
+    }
+
+    /// Verifica se a agenda do bloco `when` tem uma posição livre.
+    fn has_room(&self, when: T::BlockNumber) -> bool {
+        self.agenda.get(&when).is_none_or(|agenda| {
+            agenda.len() < T::MAX_SCHEDULED_PER_BLOCK as usize || agenda.iter().any(Option::is_none)
+        })
+    }
+
+    /// Coloca `task` na agenda do bloco `when`, ocupando a primeira posição livre, e registra o
+    /// nome da tarefa, se houver. Quem chama precisa verificar antes, com `has_room`, que a
+    /// agenda tem uma posição livre.
+    fn place(&mut self, when: T::BlockNumber, task: ScheduledOf<T>) -> TaskAddress<T::BlockNumber> {
+        let agenda = self.agenda.entry(when).or_default();
+        let index = match agenda.iter().position(Option::is_none) {
+            Some(index) => index,
+            None => {
+                agenda.push(None);
+                agenda.len() - 1
+            }
+        };
+        let address = (when, index as u32);
+        if let Some(id) = task.maybe_id {
+            self.lookup.insert(id, address);
+        }
+        agenda[index] = Some(task);
+        address
+    }
+
+    /// Cancela a tarefa em `address`, se `origin` puder cancelá-la, e devolve o seu depósito.
+    fn do_cancel(
+        runtime: &mut T,
+        origin: &Origin<T::AccountId>,
+        (when, index): TaskAddress<T::BlockNumber>,
+    ) -> DispatchResult {
+        let pallet: &mut Self = runtime.pallet_mut();
+        let slot = pallet
+            .agenda
+            .get_mut(&when)
+            .and_then(|agenda| agenda.get_mut(index as usize))
+            .ok_or("Task not found.")?;
+        let task = slot.as_ref().ok_or("Task not found.")?;
+        if origin != &Origin::Root && origin != &task.origin {
+            return Err("Only the origin of the task or root can cancel it.");
+        }
+        let task = slot.take().ok_or("Task not found.")?;
+        if let Some(id) = task.maybe_id {
+            pallet.lookup.remove(&id);
+        }
+        pallet.events.push(Event::Canceled { when, index });
+        Self::release_deposit(runtime, &task);
+        Ok(())
+    }
+
+    /// Devolve o depósito de `task` à conta que a agendou.
+    fn release_deposit(runtime: &mut T, task: &ScheduledOf<T>) {
+        if let Origin::Signed(who) = &task.origin {
+            T::Currency::unreserve_named(runtime, &SCHEDULER_RESERVE_ID, who, task.deposit);
+        }
+    }
+
+    /// Despacha as tarefas do bloco `n`, em ordem de prioridade, e agenda de novo as periódicas.
+    fn service_agenda(runtime: &mut T, n: T::BlockNumber) {
+        let pallet: &mut Self = runtime.pallet_mut();
+        let Some(agenda) = pallet.agenda.remove(&n) else {
+            return;
+        };
+        let mut tasks: Vec<_> = agenda
+            .into_iter()
+            .enumerate()
+            .filter_map(|(index, task)| Some((index as u32, task?)))
+            .collect();
+        tasks.sort_by_key(|(_, task)| task.priority);
+
+        for (index, mut task) in tasks {
+            let pallet: &mut Self = runtime.pallet_mut();
+            if let Some(id) = task.maybe_id {
+                pallet.lookup.remove(&id);
+            }
+            let result = runtime.dispatch(task.origin.clone(), task.call.clone());
+            let pallet: &mut Self = runtime.pallet_mut();
+            pallet.events.push(Event::Dispatched {
+                task: (n, index),
+                id: task.maybe_id,
+                result,
+            });
+
+            let Some((period, count)) = task.maybe_periodic else {
+                Self::release_deposit(runtime, &task);
+                continue;
+            };
+            task.maybe_periodic = (count > 2).then_some((period, count - 1));
+            // Uma repetição depois do último bloco representável é descartada, como uma que não
+            // cabe na agenda.
+            let next = n.checked_add(&period).filter(|next| pallet.has_room(*next));
+            match next {
+                Some(next) => {
+                    pallet.place(next, task);
+                }
+                None => {
+                    pallet.events.push(Event::PeriodicFailed {
+                        task: (n, index),
+                        id: task.maybe_id,
+                    });
+                    Self::release_deposit(runtime, &task);
+                }
+            }
+        }
+    }
+}
+
+impl<T: Config> Hooks<T> for Pallet<T> {
+    fn on_initialize(runtime: &mut T, n: T::BlockNumber) {
+        let pallet: &mut Self = runtime.pallet_mut();
+        pallet.events.clear();
+        Self::service_agenda(runtime, n);
+    }
+}
+
+impl<T: Config> Storage for Pallet<T> {
+    fn storage(&self) -> Vec<(Vec<u8>, Vec<u8>)> {
+        let mut storage: Vec<_> = storage_map("agenda", &self.agenda).collect();
+        storage.extend(storage_map("lookup", &self.lookup));
+        storage
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::tests::{new_runtime, run_to_block, transfer};
+    use crate::{balances, Runtime, RuntimeCall};
+
+    type Scheduler = Pallet<Runtime>;
+
+    #[test]
+    fn schedule_validation() {
+        let (mut runtime, [alice]) = new_runtime(["alice"], 100);
+        let origin = Origin::Signed(alice.clone());
+
+        // Apenas blocos futuros, e períodos não nulos
+        let res = Scheduler::schedule(&mut runtime, origin.clone(), 0, None, 0, transfer("bob", 1));
+        assert_eq!(res, Err("Target block must be in the future."));
+        let periodic = Some((0, 2));
+        let res = Scheduler::schedule(&mut runtime, origin, 2, periodic, 0, transfer("bob", 1));
+        assert_eq!(res, Err("Period must be greater than zero."));
+        assert_eq!(runtime.balances.reserved_balance(&alice), 0);
+    }
+
+    #[test]
+    fn priority_order() {
+        let (mut runtime, [alice]) = new_runtime(["alice"], 110);
+        let origin = Origin::Signed(alice.clone());
+        let bob = "bob".to_string();
+
+        // Tarefas do mesmo bloco são despachadas em ordem de prioridade, e cada uma devolve o seu
+        // depósito quando termina
+        let res = Scheduler::schedule(
+            &mut runtime,
+            origin.clone(),
+            2,
+            None,
+            1,
+            transfer("bob", 20),
+        );
+        assert_eq!(res, Ok(()));
+        let res = Scheduler::schedule(&mut runtime, origin, 2, None, 0, transfer("bob", 95));
+        assert_eq!(res, Ok(()));
+        assert_eq!(runtime.balances.reserved_balance(&alice), 10);
+        run_to_block(&mut runtime, 1);
+        assert_eq!(runtime.balances.balance(&bob), 0);
+        run_to_block(&mut runtime, 2);
+        assert_eq!(runtime.balances.balance(&bob), 95);
+        assert_eq!(runtime.balances.reserved_balance(&alice), 0);
+        assert!(runtime.scheduler.task(2, 0).is_none());
+        // Os eventos do agendamento são de blocos anteriores, e já foram descartados
+        assert!(matches!(
+            runtime.scheduler.events(),
+            [
+                Event::Dispatched {
+                    task: (2, 1),
+                    result: Ok(()),
+                    ..
+                },
+                Event::Dispatched {
+                    task: (2, 0),
+                    result: Err("Insufficient balance"),
+                    ..
+                }
+            ]
+        ));
+    }
+
+    #[test]
+    fn named_periodic_task() {
+        let (mut runtime, [alice, bob]) = new_runtime(["alice", "bob"], 100);
+        let origin = Origin::Signed(alice.clone());
+
+        // Uma tarefa periódica com nome, cancelada pelo nome depois de duas execuções
+        let periodic = Some((2, 10));
+        let res = Scheduler::schedule_named(
+            &mut runtime,
+            origin.clone(),
+            *b"vesting1",
+            4,
+            periodic,
+            0,
+            transfer("bob", 1),
+        );
+        assert_eq!(res, Ok(()));
+        let res = Scheduler::schedule_named(
+            &mut runtime,
+            origin.clone(),
+            *b"vesting1",
+            5,
+            None,
+            0,
+            transfer("bob", 1),
+        );
+        assert_eq!(res, Err("Task name already in use."));
+        run_to_block(&mut runtime, 7);
+        assert_eq!(runtime.balances.balance(&bob), 102);
+        assert_eq!(runtime.scheduler.lookup(b"vesting1"), Some((8, 0)));
+        assert_eq!(runtime.balances.reserved_balance(&alice), 5);
+        let res = Scheduler::cancel_named(&mut runtime, Origin::Signed(bob.clone()), *b"vesting1");
+        assert_eq!(
+            res,
+            Err("Only the origin of the task or root can cancel it.")
+        );
+        let res = Scheduler::cancel_named(&mut runtime, origin, *b"vesting1");
+        assert_eq!(res, Ok(()));
+        assert_eq!(runtime.balances.reserved_balance(&alice), 0);
+        assert_eq!(runtime.scheduler.lookup(b"vesting1"), None);
+        run_to_block(&mut runtime, 10);
+        assert_eq!(runtime.balances.balance(&bob), 102);
+    }
+
+    #[test]
+    fn periodic_count() {
+        let (mut runtime, [alice, bob]) = new_runtime(["alice", "bob"], 100);
+
+        // Uma tarefa periódica para depois do número de execuções pedido
+        let periodic = Some((1, 2));
+        let origin = Origin::Signed(alice.clone());
+        let res = Scheduler::schedule(&mut runtime, origin, 2, periodic, 0, transfer("bob", 1));
+        assert_eq!(res, Ok(()));
+        run_to_block(&mut runtime, 6);
+        assert_eq!(runtime.balances.balance(&bob), 102);
+        assert_eq!(runtime.balances.reserved_balance(&alice), 0);
+    }
+
+    #[test]
+    fn root_tasks() {
+        let (mut runtime, [alice, bob]) = new_runtime(["alice", "bob"], 100);
+
+        // A origem privilegiada pode agendar chamadas privilegiadas, sem depósito, e cancelar
+        // qualquer tarefa
+        let call = Box::new(RuntimeCall::from(balances::Call::force_set_balance {
+            who: bob.clone(),
+            amount: 1_000,
+        }));
+        assert_eq!(
+            Scheduler::schedule(&mut runtime, Origin::Root, 2, None, 0, call),
+            Ok(())
+        );
+        let origin = Origin::Signed(alice.clone());
+        let res = Scheduler::schedule(&mut runtime, origin, 2, None, 0, transfer("bob", 1));
+        assert_eq!(res, Ok(()));
+        assert_eq!(runtime.balances.reserved_balance(&alice), 5);
+        assert_eq!(Scheduler::cancel(&mut runtime, Origin::Root, 2, 1), Ok(()));
+        assert_eq!(
+            Scheduler::cancel(&mut runtime, Origin::Root, 2, 1),
+            Err("Task not found.")
+        );
+        assert_eq!(runtime.balances.reserved_balance(&alice), 0);
+        run_to_block(&mut runtime, 2);
+        assert_eq!(runtime.balances.balance(&bob), 1_000);
+    }
+
+    #[test]
+    fn agenda_limit() {
+        let (mut runtime, _) = new_runtime([], 0);
+
+        // O número de tarefas num mesmo bloco é limitado
+        for _ in 0..<Runtime as Config>::MAX_SCHEDULED_PER_BLOCK {
+            let res =
+                Scheduler::schedule(&mut runtime, Origin::Root, 2, None, 0, transfer("bob", 1));
+            assert_eq!(res, Ok(()));
+        }
+        let res = Scheduler::schedule(&mut runtime, Origin::Root, 2, None, 0, transfer("bob", 1));
+        assert_eq!(res, Err("Agenda is full."));
+
+        // Uma posição liberada pode ser ocupada de novo
+        assert_eq!(Scheduler::cancel(&mut runtime, Origin::Root, 2, 3), Ok(()));
+        let res = Scheduler::schedule(&mut runtime, Origin::Root, 2, None, 0, transfer("bob", 1));
+        assert_eq!(res, Ok(()));
+        assert!(runtime.scheduler.task(2, 3).is_some());
+    }
+
+    #[test]
+    fn schedule_deposit() {
+        let (mut runtime, [alice]) = new_runtime(["alice"], 7);
+        let origin = Origin::Signed(alice.clone());
+
+        // Cada tarefa agendada por uma conta reserva um depósito, e sem saldo não há agendamento
+        let res = Scheduler::schedule(&mut runtime, origin.clone(), 3, None, 0, transfer("bob", 1));
+        assert_eq!(res, Ok(()));
+        assert_eq!(runtime.balances.reserved_balance(&alice), 5);
+        let res = Scheduler::schedule(&mut runtime, origin.clone(), 3, None, 0, transfer("bob", 1));
+        assert_eq!(res, Err("Insufficient balance"));
+        assert!(runtime.scheduler.task(3, 1).is_none());
+
+        // Uma agenda cheia é recusada antes de reservar o depósito
+        for _ in 1..<Runtime as Config>::MAX_SCHEDULED_PER_BLOCK {
+            let res =
+                Scheduler::schedule(&mut runtime, Origin::Root, 3, None, 0, transfer("bob", 1));
+            assert_eq!(res, Ok(()));
+        }
+        runtime.balances.set_balance(&alice, 100);
+        let res = Scheduler::schedule(&mut runtime, origin, 3, None, 0, transfer("bob", 1));
+        assert_eq!(res, Err("Agenda is full."));
+        assert_eq!(runtime.balances.reserved_balance(&alice), 5);
+    }
+
+    #[test]
+    fn periodic_overflow() {
+        let (mut runtime, [alice]) = new_runtime(["alice"], 100);
+        let origin = Origin::Signed(alice.clone());
+
+        // Uma tarefa cuja primeira repetição passaria do último bloco é recusada
+        let periodic = Some((u32::MAX, 5));
+        let res = Scheduler::schedule(
+            &mut runtime,
+            origin.clone(),
+            1,
+            periodic,
+            0,
+            transfer("bob", 1),
+        );
+        assert_eq!(res, Err("Period overflows the block number."));
+
+        // Uma repetição posterior que passaria do último bloco é descartada, com o depósito
+        // devolvido
+        let periodic = Some((1, 3));
+        let res = Scheduler::schedule(
+            &mut runtime,
+            origin,
+            u32::MAX - 1,
+            periodic,
+            0,
+            transfer("bob", 1),
+        );
+        assert_eq!(res, Ok(()));
+        Scheduler::on_initialize(&mut runtime, u32::MAX - 1);
+        assert!(runtime.scheduler.task(u32::MAX, 0).is_some());
+        Scheduler::on_initialize(&mut runtime, u32::MAX);
+        assert_eq!(runtime.balances.balance(&"bob".to_string()), 2);
+        assert!(matches!(
+            runtime.scheduler.events().last(),
+            Some(Event::PeriodicFailed {
+                task: (u32::MAX, 0),
+                id: None
+            })
+        ));
+        assert_eq!(runtime.balances.reserved_balance(&alice), 0);
+    }
+}
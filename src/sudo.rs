@@ -0,0 +1,217 @@
+use crate::support::{storage_value, Dispatch, DispatchResult, GetPallet, Hooks, Origin, Storage};
+use crate::system;
+
+/// Os eventos emitidos pelo Módulo Sudo.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Event<T: Config> {
+    /// Uma chamada foi despachada com a origem privilegiada, com o resultado `sudo_result`.
+    Sudid { sudo_result: DispatchResult },
+    /// Uma chamada foi despachada em nome de outra conta, com o resultado `sudo_result`.
+    SudoAsDone { sudo_result: DispatchResult },
+    /// A chave de sudo passou de `old` para `new`.
+    KeyChanged {
+        old: Option<T::AccountId>,
+        new: T::AccountId,
+    },
+}
+
+/// A característica de configuração do Módulo Sudo.
+/// O runtime precisa saber despachar suas próprias chamadas com qualquer origem.
+pub trait Config:
+    system::Config
+    + Sized
+    + Dispatch<Caller = Origin<Self::AccountId>, Call = Self::RuntimeCall>
+    + GetPallet<Pallet<Self>>
+{
+    /// O tipo das chamadas do runtime, que podem ser de qualquer pallet.
+    type RuntimeCall;
+}
+
+/// Este é o Módulo Sudo.
+/// Guarda uma única conta, a chave de sudo, que pode despachar qualquer chamada como a origem
+/// privilegiada ou em nome de qualquer outra conta. Serve para redes de desenvolvimento e testes,
+/// em que um operador precisa corrigir o estado.
+#[derive(Debug, Clone)]
+pub struct Pallet<T: Config> {
+    /// A conta que pode usar este pallet, se houver.
+    key: Option<T::AccountId>,
+    /// Os eventos emitidos por este pallet no bloco atual, em ordem.
+    events: Vec<Event<T>>,
+}
+
+#[allow(clippy::boxed_local)]
+#[macros::call]
+impl<T: Config> Pallet<T> {
+    /// Despacha `call` com a origem privilegiada. Apenas a chave de sudo pode fazer esta chamada.
+    /// A chamada em si não falha se `call` falhar: o resultado é registrado no evento `Sudid`.
+    pub fn sudo(
+        runtime: &mut T,
+        caller: T::AccountId,
+        call: Box<T::RuntimeCall>,
+    ) -> DispatchResult {
+        Self::ensure_sudo(runtime, &caller)?;
+        let sudo_result = runtime.dispatch(Origin::Root, *call);
+        Self::deposit_event(runtime, Event::Sudid { sudo_result });
+        Ok(())
+    }
+
+    /// Despacha `call` em nome de `who`, como se `who` a tivesse assinado. Apenas a chave de sudo
+    /// pode fazer esta chamada. O resultado de `call` é registrado no evento `SudoAsDone`.
+    pub fn sudo_as(
+        runtime: &mut T,
+        caller: T::AccountId,
+        who: T::AccountId,
+        call: Box<T::RuntimeCall>,
+    ) -> DispatchResult {
+        Self::ensure_sudo(runtime, &caller)?;
+        let sudo_result = runtime.dispatch(Origin::Signed(who), *call);
+        Self::deposit_event(runtime, Event::SudoAsDone { sudo_result });
+        Ok(())
+    }
+
+    /// Passa a chave de sudo para `new`. Apenas a chave de sudo atual pode fazer esta chamada.
+    pub fn set_key(&mut self, caller: T::AccountId, new: T::AccountId) -> DispatchResult {
+        if self.key.as_ref() != Some(&caller) {
+            return Err("Only the sudo key can make this call.");
+        }
+        let old = self.key.replace(new.clone());
+        self.events.push(Event::KeyChanged { old, new });
+        Ok(())
+    }
+}
+
+impl<T: Config> Pallet<T> {
+    /// Cria uma nova instância do Módulo Sudo, sem chave de sudo.
+    pub fn new() -> Self {
+        Self {
+            key: None,
+            events: Vec::new(),
+        }
+    }
+
+    /// Define a chave de sudo na gênese da cadeia, sem nenhuma verificação.
+    pub fn initialize_key(&mut self, key: T::AccountId) {
+        self.key = Some(key);
+    }
+
+    /// Obtém a chave de sudo atual, se houver.
+    pub fn key(&self) -> Option<&T::AccountId> {
+        self.key.as_ref()
+    }
+
+    /// Obtém os eventos emitidos por este pallet no bloco atual, em ordem.
+    pub fn events(&self) -> &[Event<T>] {
+        &self.events
+    }
+
+    /// Garante que `who` seja a chave de sudo.
+    fn ensure_sudo(runtime: &T, who: &T::AccountId) -> DispatchResult {
+        let pallet: &Self = runtime.pallet();
+        if pallet.key.as_ref() != Some(who) {
+            return Err("Only the sudo key can make this call.");
+        }
+        Ok(())
+    }
+
+    /// Registra um evento emitido por este pallet.
+    fn deposit_event(runtime: &mut T, event: Event<T>) {
+        let pallet: &mut Self = runtime.pallet_mut();
+        pallet.events.push(event);
+    }
+}
+
+impl<T: Config> Hooks<T> for Pallet<T> {
+    fn on_initialize(runtime: &mut T, _n: T::BlockNumber) {
+        let pallet: &mut Self = runtime.pallet_mut();
+        pallet.events.clear();
+    }
+}
+
+impl<T: Config> Storage for Pallet<T> {
+    fn storage(&self) -> Vec<(Vec<u8>, Vec<u8>)> {
+        vec![storage_value("key", &self.key)]
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::{balances, Runtime, RuntimeCall};
+
+    fn force_set_balance(who: &str, amount: u128) -> Box<RuntimeCall> {
+        Box::new(
+            balances::Call::force_set_balance {
+                who: who.to_string(),
+                amount,
+            }
+            .into(),
+        )
+    }
+
+    #[test]
+    fn sudo_key() {
+        let alice = "alice".to_string();
+        let bob = "bob".to_string();
+        let mut runtime = Runtime::new();
+        type Sudo = Pallet<Runtime>;
+
+        // Sem chave, ninguém pode usar o sudo
+        let res = Sudo::sudo(&mut runtime, alice.clone(), force_set_balance("bob", 50));
+        assert_eq!(res, Err("Only the sudo key can make this call."));
+        runtime.sudo.initialize_key(alice.clone());
+        assert_eq!(runtime.sudo.key(), Some(&alice));
+
+        // Chamadas privilegiadas só podem ser feitas pela origem privilegiada
+        let res = runtime.dispatch(Origin::Signed(alice.clone()), *force_set_balance("bob", 50));
+        assert_eq!(res, Err("Bad origin: expected root."));
+        let res = Sudo::sudo(&mut runtime, bob.clone(), force_set_balance("bob", 50));
+        assert_eq!(res, Err("Only the sudo key can make this call."));
+        let res = Sudo::sudo(&mut runtime, alice.clone(), force_set_balance("bob", 50));
+        assert_eq!(res, Ok(()));
+        assert_eq!(runtime.balances.balance(&bob), 50);
+        assert_eq!(runtime.balances.total_issuance(), 50);
+
+        // A falha da chamada despachada fica registrada no evento
+        let transfer: RuntimeCall = balances::Call::transfer {
+            to: alice.clone(),
+            amount: 10,
+        }
+        .into();
+        let res = Sudo::sudo(&mut runtime, alice.clone(), Box::new(transfer));
+        assert_eq!(res, Ok(()));
+
+        // `sudo_as` despacha a chamada como se outra conta a tivesse assinado
+        let transfer: RuntimeCall = balances::Call::transfer {
+            to: alice.clone(),
+            amount: 10,
+        }
+        .into();
+        let res = Sudo::sudo_as(&mut runtime, alice.clone(), bob.clone(), Box::new(transfer));
+        assert_eq!(res, Ok(()));
+        assert_eq!(runtime.balances.balance(&alice), 10);
+        assert!(matches!(
+            runtime.sudo.events(),
+            [
+                Event::Sudid {
+                    sudo_result: Ok(())
+                },
+                Event::Sudid {
+                    sudo_result: Err("Bad origin: expected a signed account.")
+                },
+                Event::SudoAsDone {
+                    sudo_result: Ok(())
+                },
+            ]
+        ));
+
+        // Apenas a chave atual pode passá-la adiante
+        assert_eq!(
+            runtime.sudo.set_key(bob.clone(), bob.clone()),
+            Err("Only the sudo key can make this call.")
+        );
+        assert_eq!(runtime.sudo.set_key(alice.clone(), bob.clone()), Ok(()));
+        assert_eq!(runtime.sudo.key(), Some(&bob));
+        let res = Sudo::sudo(&mut runtime, alice, force_set_balance("bob", 0));
+        assert_eq!(res, Err("Only the sudo key can make this call."));
+    }
+}
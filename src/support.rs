@@ -1,3 +1,6 @@
+use core::fmt::Debug;
+use num::traits::{CheckedAdd, CheckedSub, Zero};
+
 /// A representação mais primitiva de um bloco de blockchain.
 pub struct Block<Header, Extrinsic> {
     /// O cabeçalho do bloco contém metadados sobre o bloco.
@@ -6,27 +9,78 @@ pub struct Block<Header, Extrinsic> {
     pub extrinsics: Vec<Extrinsic>,
 }
 
-/// Estamos usando um cabeçalho extremamente simplificado que contém apenas o número atual do bloco.
+/// Estamos usando um cabeçalho extremamente simplificado que contém apenas o número atual do bloco
+/// e, opcionalmente, a raiz do estado depois do bloco.
 /// Em uma blockchain real, você esperaria encontrar também:
 /// - hash do bloco pai
-/// - raiz do estado
 /// - raiz dos extrínsecos
 /// - etc...
-pub struct Header<BlockNumber> {
+pub struct Header<BlockNumber, Hash> {
     pub block_number: BlockNumber,
+    /// A raiz do estado esperada depois da execução do bloco. Se houver, o runtime rejeita o
+    /// bloco quando a raiz calculada for diferente.
+    pub state_root: Option<Hash>,
 }
 
 /// Este é um "extrínseco": literalmente uma mensagem externa de fora da blockchain.
-/// Esta versão simplificada de um extrínseco nos diz quem está fazendo a chamada e qual chamada eles estão fazendo.
-pub struct Extrinsic<Caller, Call> {
-    pub caller: Caller,
-    pub call: Call,
+/// Esta versão simplificada de um extrínseco nos diz quem está fazendo a chamada, se houver, e qual
+/// chamada está sendo feita.
+pub enum Extrinsic<Caller, Call> {
+    /// Uma chamada assinada por `caller`, despachada com a sua origem.
+    Signed { caller: Caller, call: Call },
+    /// Uma chamada sem assinatura, despachada sem origem. Como ninguém paga por ela, o pallet
+    /// da chamada precisa aceitá-la através de `ValidateUnsigned`, senão o bloco é inválido.
+    Unsigned { call: Call },
+    /// Um dado fornecido pelo autor do bloco, como o horário do bloco, despachado sem origem.
+    /// Os inerentes vêm antes de todos os outros extrínsecos, e se um deles falhar, o bloco
+    /// inteiro é inválido.
+    Inherent { call: Call },
 }
 
 /// O tipo Result para nosso runtime. Quando tudo é concluído com sucesso, retornamos `Ok(())`,
 /// caso contrário, retornamos uma mensagem de erro estática.
 pub type DispatchResult = Result<(), &'static str>;
 
+/// A origem de uma chamada: quem a está fazendo.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Origin<AccountId> {
+    /// A origem privilegiada, que não corresponde a nenhuma conta. Só pode ser usada por pallets
+    /// que despacham chamadas, como o sudo.
+    Root,
+    /// Uma conta que assinou a chamada.
+    Signed(AccountId),
+    /// Nenhuma origem: a chamada é um inerente, incluído no bloco pelo seu autor, ou uma
+    /// transação não assinada.
+    None,
+}
+
+impl<AccountId> Origin<AccountId> {
+    /// Obtém a conta que assinou a chamada, ou falha se a origem não for uma conta.
+    pub fn ensure_signed(self) -> Result<AccountId, &'static str> {
+        match self {
+            Origin::Signed(who) => Ok(who),
+            _ => Err("Bad origin: expected a signed account."),
+        }
+    }
+
+    /// Falha se a origem não for a origem privilegiada.
+    pub fn ensure_root(self) -> DispatchResult {
+        match self {
+            Origin::Root => Ok(()),
+            _ => Err("Bad origin: expected root."),
+        }
+    }
+
+    /// Falha se a chamada tiver uma origem, ou seja, se ela não for um inerente nem uma transação
+    /// não assinada.
+    pub fn ensure_none(self) -> DispatchResult {
+        match self {
+            Origin::None => Ok(()),
+            _ => Err("Bad origin: expected no origin."),
+        }
+    }
+}
+
 /// Uma trait que nos permite despachar um extrínseco recebido para a chamada de função de transição de estado apropriada.
 pub trait Dispatch {
     /// O tipo usado para identificar o chamador da função.
@@ -38,3 +92,416 @@ pub trait Dispatch {
     /// com base no resultado dessa chamada de função.
     fn dispatch(&mut self, caller: Self::Caller, call: Self::Call) -> DispatchResult;
 }
+
+/// Permite executar uma função de forma atômica: se ela falhar, todas as alterações que ela fez no
+/// estado são desfeitas.
+pub trait Transactional {
+    /// Executa `f`, desfazendo suas alterações no estado se ela retornar um erro.
+    fn transactional(&mut self, f: impl FnOnce(&mut Self) -> DispatchResult) -> DispatchResult;
+}
+
+/// Um algoritmo de hash usado pelo runtime.
+pub trait Hasher {
+    /// O tipo do hash produzido.
+    type Output: Debug + Ord + Clone + Copy + AsRef<[u8]>;
+
+    /// Calcula o hash de `data`.
+    fn hash(data: &[u8]) -> Self::Output;
+}
+
+/// Calcula o hash de uma chamada, codificada com a sua representação `Debug`. É assim que os
+/// pallets que aprovam ou anunciam uma chamada antes de despachá-la, como o multisig e o proxy, a
+/// identificam.
+pub fn call_hash<H: Hasher>(call: &impl Debug) -> H::Output {
+    H::hash(format!("{:?}", call).as_bytes())
+}
+
+/// Dá acesso a um pallet a partir do runtime que o contém.
+///
+/// O macro `#[macros::runtime]` implementa esta trait no `Runtime` para cada pallet incluído nele,
+/// o que permite que um pallet use outro através do runtime, sem conhecer o tipo concreto.
+pub trait GetPallet<P> {
+    /// Obtém uma referência ao pallet.
+    fn pallet(&self) -> &P;
+    /// Obtém uma referência mutável ao pallet.
+    fn pallet_mut(&mut self) -> &mut P;
+}
+
+/// Expõe o armazenamento de um pallet como pares chave-valor, sobre os quais é calculada a raiz do
+/// estado.
+///
+/// As chaves e os valores são codificados com a sua representação `Debug`, através de
+/// `storage_value` e `storage_map`. Os eventos não fazem parte do estado.
+///
+/// A representação `Debug` não é uma codificação estável: ela serve para comparar os estados de
+/// runtimes compilados a partir do mesmo código, mas pode mudar com os tipos do runtime ou com a
+/// versão do compilador.
+pub trait Storage {
+    /// Obtém todos os pares chave-valor guardados pelo pallet.
+    fn storage(&self) -> Vec<(Vec<u8>, Vec<u8>)>;
+}
+
+/// Codifica o item de armazenamento `item` que guarda um único valor.
+pub fn storage_value(item: &str, value: &impl Debug) -> (Vec<u8>, Vec<u8>) {
+    (
+        item.as_bytes().to_vec(),
+        format!("{:?}", value).into_bytes(),
+    )
+}
+
+/// Calcula a chave da entrada `key` do mapa de armazenamento `item`.
+pub fn storage_map_key(item: &str, key: &impl Debug) -> Vec<u8> {
+    format!("{}:{:?}", item, key).into_bytes()
+}
+
+/// Codifica cada entrada do mapa de armazenamento `item`.
+pub fn storage_map<'a, K: Debug + 'a, V: Debug + 'a>(
+    item: &'a str,
+    entries: impl IntoIterator<Item = (&'a K, &'a V)> + 'a,
+) -> impl Iterator<Item = (Vec<u8>, Vec<u8>)> + 'a {
+    entries.into_iter().map(move |(key, value)| {
+        (
+            storage_map_key(item, key),
+            format!("{:?}", value).into_bytes(),
+        )
+    })
+}
+
+/// As funções que um pallet executa no início e no fim de cada bloco.
+///
+/// O macro `#[macros::runtime]` chama `on_initialize` de todos os pallets antes dos extrínsecos de
+/// um bloco e `on_finalize` depois deles. Um pallet sem nada a fazer implementa a trait vazia.
+///
+/// Os pallets que emitem eventos os descartam em `on_initialize`, para guardar apenas os eventos do
+/// bloco atual: o runtime copia os pallets a cada bloco, então o histórico inteiro deixaria cada
+/// bloco mais caro.
+pub trait Hooks<T: crate::system::Config> {
+    /// Chamada no início do bloco `n`, antes dos extrínsecos.
+    fn on_initialize(_runtime: &mut T, _n: T::BlockNumber) {}
+
+    /// Chamada no fim do bloco `n`, depois dos extrínsecos.
+    fn on_finalize(_runtime: &mut T, _n: T::BlockNumber) {}
+}
+
+/// Os inerentes que um pallet aceita do autor do bloco, para as chamadas `Call` do pallet.
+///
+/// O macro `#[macros::runtime]` só consulta esta trait nos pallets marcados com `#[inherent]` no
+/// `Runtime`; as chamadas dos outros pallets nunca são inerentes. Um inerente só é aceito se
+/// `is_inherent` for verdadeira para a sua chamada, e o bloco que não incluir, antes de todos os
+/// outros extrínsecos, os inerentes exigidos é rejeitado.
+pub trait ProvideInherent<Call> {
+    /// Se todo bloco precisa incluir um inerente deste pallet.
+    const INHERENT_REQUIRED: bool = false;
+
+    /// Verifica se `call` pode ser incluída como um inerente.
+    fn is_inherent(call: &Call) -> bool;
+}
+
+/// Valida as transações não assinadas de um pallet, com chamadas `Call`, no runtime `T`.
+///
+/// O macro `#[macros::runtime]` só consulta esta trait nos pallets marcados com
+/// `#[validate_unsigned]` no `Runtime`; os outros pallets não aceitam transações não assinadas. O
+/// bloco que incluir uma transação não assinada que o seu pallet não aceite é rejeitado.
+pub trait ValidateUnsigned<T, Call> {
+    /// Verifica se `call` pode ser incluída como uma transação não assinada no estado atual.
+    fn validate_unsigned(runtime: &T, call: &Call) -> DispatchResult;
+}
+
+/// Uma fonte de tempo, acessada através do runtime `T` que a contém.
+pub trait Time<T: ?Sized> {
+    /// O tipo usado para representar um instante.
+    type Moment: Debug + Ord + Clone + Copy;
+
+    /// Obtém o instante atual, ou `None` se ele não for conhecido.
+    fn now(runtime: &T) -> Option<Self::Moment>;
+}
+
+/// Um runtime sem fonte de tempo usa `()`, que nunca conhece o instante atual.
+impl<T: ?Sized> Time<T> for () {
+    type Moment = u64;
+
+    fn now(_runtime: &T) -> Option<u64> {
+        None
+    }
+}
+
+/// O identificador de uma reserva nomeada.
+/// Cada pallet usa o seu próprio identificador para reter fundos por um motivo diferente.
+pub type ReserveIdentifier = [u8; 8];
+
+/// O identificador de um bloqueio de saldo.
+/// Cada pallet usa o seu próprio identificador, de forma que os bloqueios não se sobreponham.
+pub type LockIdentifier = [u8; 8];
+
+/// Os tipos de saque que um bloqueio impede.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum WithdrawReasons {
+    /// Transferências e reservas de saldo.
+    Transfer,
+    /// Pagamento de taxas.
+    Fees,
+    /// Todos os tipos de saque.
+    All,
+}
+
+impl WithdrawReasons {
+    /// Verifica se um bloqueio com estes motivos impede um saque feito por `reason`.
+    pub fn intersects(self, reason: WithdrawReasons) -> bool {
+        self == WithdrawReasons::All || reason == WithdrawReasons::All || self == reason
+    }
+
+    /// Combina dois conjuntos de motivos num conjunto que cobre ambos.
+    pub fn union(self, other: WithdrawReasons) -> WithdrawReasons {
+        if self == other {
+            self
+        } else {
+            WithdrawReasons::All
+        }
+    }
+}
+
+/// O destino dos fundos reservados quando eles são repatriados para outra conta.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum BalanceStatus {
+    /// Os fundos vão para o saldo livre do beneficiário.
+    Free,
+    /// Os fundos continuam reservados, agora na conta do beneficiário.
+    Reserved,
+}
+
+/// Indica se uma operação pode deixar a conta de origem abaixo do depósito existencial.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ExistenceRequirement {
+    /// A conta de origem precisa continuar com pelo menos o depósito existencial.
+    KeepAlive,
+    /// A conta de origem pode ficar com qualquer saldo.
+    AllowDeath,
+}
+
+/// Fundos que foram retirados de contas e deixaram de existir.
+///
+/// O total emitido da moeda já foi reduzido quando este valor é criado. Descartá-lo queima os
+/// fundos de vez; entregá-lo a `Currency::resolve_creating` os deposita em outra conta.
+#[must_use]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct NegativeImbalance<Balance>(Balance);
+
+/// Fundos que foram criados e depositados em alguma conta.
+///
+/// O total emitido da moeda já foi aumentado quando este valor é criado; ele serve como recibo de
+/// quanto foi emitido.
+#[must_use]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct PositiveImbalance<Balance>(Balance);
+
+impl<Balance: Copy + CheckedAdd> NegativeImbalance<Balance> {
+    /// Cria um desequilíbrio negativo de `amount`.
+    pub fn new(amount: Balance) -> Self {
+        Self(amount)
+    }
+
+    /// Obtém a quantia deste desequilíbrio.
+    pub fn peek(&self) -> Balance {
+        self.0
+    }
+
+    /// Junta dois desequilíbrios negativos num só.
+    pub fn merge(self, other: Self) -> Self {
+        Self(self.0.checked_add(&other.0).unwrap_or(self.0))
+    }
+}
+
+impl<Balance: Copy + CheckedAdd> PositiveImbalance<Balance> {
+    /// Cria um desequilíbrio positivo de `amount`.
+    pub fn new(amount: Balance) -> Self {
+        Self(amount)
+    }
+
+    /// Obtém a quantia deste desequilíbrio.
+    pub fn peek(&self) -> Balance {
+        self.0
+    }
+
+    /// Junta dois desequilíbrios positivos num só.
+    pub fn merge(self, other: Self) -> Self {
+        Self(self.0.checked_add(&other.0).unwrap_or(self.0))
+    }
+}
+
+/// O tipo de saldo de uma moeda `C` usada pelo runtime `T`.
+pub type BalanceOf<C, T> = <C as Currency<T>>::Balance;
+
+/// Uma moeda fungível, acessada através do runtime `T` que a contém.
+///
+/// Um pallet que precisa de uma moeda a declara na sua `Config`, por exemplo
+/// `type Currency: ReservableCurrency<Self>;`, e o runtime escolhe qual pallet a fornece, por
+/// exemplo `type Currency = balances::Pallet<Self>;`. O pallet então chama
+/// `T::Currency::reserve(runtime, &who, amount)` sem depender do pallet de saldos diretamente.
+pub trait Currency<T: crate::system::Config + ?Sized> {
+    /// O tipo usado para representar saldos desta moeda.
+    type Balance: Zero + CheckedSub + CheckedAdd + Copy + Ord + Debug;
+
+    /// Obtém a quantia total desta moeda que existe no runtime.
+    fn total_issuance(runtime: &T) -> Self::Balance;
+
+    /// Obtém o saldo total de `who`: livre mais reservado.
+    fn total_balance(runtime: &T, who: &T::AccountId) -> Self::Balance;
+
+    /// Obtém o saldo livre de `who`.
+    fn free_balance(runtime: &T, who: &T::AccountId) -> Self::Balance;
+
+    /// Transfere `amount` do saldo livre de `from` para o saldo livre de `to`.
+    fn transfer(
+        runtime: &mut T,
+        from: &T::AccountId,
+        to: &T::AccountId,
+        amount: Self::Balance,
+        existence: ExistenceRequirement,
+    ) -> DispatchResult;
+
+    /// Retira `amount` do saldo livre de `who` por `reason`, respeitando os bloqueios da conta.
+    /// Os fundos retirados deixam de existir e são devolvidos como um `NegativeImbalance`.
+    fn withdraw(
+        runtime: &mut T,
+        who: &T::AccountId,
+        amount: Self::Balance,
+        reason: WithdrawReasons,
+        existence: ExistenceRequirement,
+    ) -> Result<NegativeImbalance<Self::Balance>, &'static str>;
+
+    /// Cria `amount` de novos fundos no saldo livre de `who`.
+    /// Devolve a quantia que foi de fato criada, que é zero se o depósito não for possível.
+    fn deposit_creating(
+        runtime: &mut T,
+        who: &T::AccountId,
+        amount: Self::Balance,
+    ) -> PositiveImbalance<Self::Balance>;
+
+    /// Deposita no saldo livre de `who` os fundos retirados de outra conta.
+    /// Se o depósito não for possível, nada é depositado e os fundos são devolvidos, para que
+    /// quem os retirou decida o que fazer com eles.
+    fn resolve_creating(
+        runtime: &mut T,
+        who: &T::AccountId,
+        imbalance: NegativeImbalance<Self::Balance>,
+    ) -> Result<(), NegativeImbalance<Self::Balance>> {
+        let amount = imbalance.peek();
+        let created = Self::deposit_creating(runtime, who, amount);
+        if created.peek() == amount {
+            Ok(())
+        } else {
+            Err(imbalance)
+        }
+    }
+}
+
+/// Uma moeda que permite reservar parte do saldo de uma conta.
+/// Os fundos reservados continuam na conta, mas não podem ser transferidos nem gastos.
+pub trait ReservableCurrency<T: crate::system::Config + ?Sized>: Currency<T> {
+    /// Verifica se `who` pode reservar `amount` do seu saldo livre.
+    fn can_reserve(runtime: &T, who: &T::AccountId, amount: Self::Balance) -> bool;
+
+    /// Obtém o saldo reservado total de `who`.
+    fn reserved_balance(runtime: &T, who: &T::AccountId) -> Self::Balance;
+
+    /// Move `amount` do saldo livre de `who` para o seu saldo reservado.
+    fn reserve(runtime: &mut T, who: &T::AccountId, amount: Self::Balance) -> DispatchResult;
+
+    /// Devolve até `amount` do saldo reservado de `who` ao seu saldo livre.
+    /// Retorna a parte de `amount` que não pôde ser liberada.
+    fn unreserve(runtime: &mut T, who: &T::AccountId, amount: Self::Balance) -> Self::Balance;
+
+    /// Corta até `amount` do saldo reservado de `who`.
+    /// Retorna os fundos cortados e a parte de `amount` que não pôde ser cortada.
+    fn slash_reserved(
+        runtime: &mut T,
+        who: &T::AccountId,
+        amount: Self::Balance,
+    ) -> (NegativeImbalance<Self::Balance>, Self::Balance);
+
+    /// Move até `amount` do saldo reservado de `slashed` para o saldo de `beneficiary` indicado
+    /// por `status`. Retorna a parte de `amount` que não pôde ser movida.
+    fn repatriate_reserved(
+        runtime: &mut T,
+        slashed: &T::AccountId,
+        beneficiary: &T::AccountId,
+        amount: Self::Balance,
+        status: BalanceStatus,
+    ) -> Result<Self::Balance, &'static str>;
+}
+
+/// Uma moeda que permite reter fundos sob reservas nomeadas, uma para cada motivo.
+pub trait NamedReservableCurrency<T: crate::system::Config + ?Sized>:
+    ReservableCurrency<T>
+{
+    /// Obtém o saldo de `who` retido sob a reserva `id`.
+    fn reserved_balance_named(
+        runtime: &T,
+        id: &ReserveIdentifier,
+        who: &T::AccountId,
+    ) -> Self::Balance;
+
+    /// Move `amount` do saldo livre de `who` para a reserva `id`.
+    fn reserve_named(
+        runtime: &mut T,
+        id: &ReserveIdentifier,
+        who: &T::AccountId,
+        amount: Self::Balance,
+    ) -> DispatchResult;
+
+    /// Devolve até `amount` da reserva `id` de `who` ao seu saldo livre.
+    /// Retorna a parte de `amount` que não pôde ser liberada.
+    fn unreserve_named(
+        runtime: &mut T,
+        id: &ReserveIdentifier,
+        who: &T::AccountId,
+        amount: Self::Balance,
+    ) -> Self::Balance;
+
+    /// Corta até `amount` da reserva `id` de `who`.
+    /// Retorna os fundos cortados e a parte de `amount` que não pôde ser cortada.
+    fn slash_reserved_named(
+        runtime: &mut T,
+        id: &ReserveIdentifier,
+        who: &T::AccountId,
+        amount: Self::Balance,
+    ) -> (NegativeImbalance<Self::Balance>, Self::Balance);
+
+    /// Move até `amount` da reserva `id` de `slashed` para `beneficiary`. Com `status`
+    /// `Reserved`, os fundos ficam retidos no beneficiário sob o mesmo `id`.
+    /// Retorna a parte de `amount` que não pôde ser movida.
+    fn repatriate_reserved_named(
+        runtime: &mut T,
+        id: &ReserveIdentifier,
+        slashed: &T::AccountId,
+        beneficiary: &T::AccountId,
+        amount: Self::Balance,
+        status: BalanceStatus,
+    ) -> Result<Self::Balance, &'static str>;
+}
+
+/// Uma moeda que permite bloquear parte do saldo livre de uma conta.
+// Nenhum pallet do runtime de exemplo bloqueia saldo ainda, então só os testes usam esta trait.
+#[cfg(test)]
+pub trait LockableCurrency<T: crate::system::Config + ?Sized>: Currency<T> {
+    /// Cria ou substitui o bloqueio `id` sobre o saldo de `who`.
+    fn set_lock(
+        runtime: &mut T,
+        id: LockIdentifier,
+        who: &T::AccountId,
+        amount: Self::Balance,
+        reasons: WithdrawReasons,
+    );
+
+    /// Estende o bloqueio `id` sobre o saldo de `who`, ou o cria se não existir.
+    fn extend_lock(
+        runtime: &mut T,
+        id: LockIdentifier,
+        who: &T::AccountId,
+        amount: Self::Balance,
+        reasons: WithdrawReasons,
+    );
+
+    /// Remove o bloqueio `id` do saldo de `who`.
+    fn remove_lock(runtime: &mut T, id: LockIdentifier, who: &T::AccountId);
+}
@@ -1,21 +1,47 @@
+use crate::support::{storage_map, storage_value, Hasher, Storage};
+use crate::trie::{ReadProof, StateSnapshot, StateTrie};
+use core::fmt::Debug;
 use core::ops::AddAssign;
-use num::traits::{One, Zero};
+use num::traits::{CheckedAdd, One, Zero};
 use std::collections::BTreeMap;
 
+/// Quantos dos últimos blocos têm o estado guardado para gerar provas de leitura.
+pub const STATE_HISTORY: usize = 256;
+
 pub trait Config {
-    type AccountId: Ord + Clone;
-    type BlockNumber: One + Zero + AddAssign + Copy;
-    type Nonce: One + Zero + Copy;
+    type AccountId: Ord + Clone + Debug;
+    type BlockNumber: One + Zero + AddAssign + CheckedAdd + Copy + Ord + Debug;
+    type Nonce: One + Zero + Copy + Debug;
+    /// O tipo dos hashes usados no runtime.
+    type Hash: Debug + Ord + Clone + Copy + AsRef<[u8]>;
+    /// O algoritmo usado para calcular hashes, por exemplo do conteúdo das reivindicações.
+    type Hashing: Hasher<Output = Self::Hash>;
+}
+
+/// O estado do sistema que um bloco altera, guardado antes do bloco para que ele possa ser
+/// desfeito se for inválido.
+#[derive(Debug, Clone)]
+pub struct Checkpoint<T: Config> {
+    block_number: T::BlockNumber,
+    nonce: BTreeMap<T::AccountId, T::Nonce>,
 }
 
 /// Este é o Pallet do Sistema.
 /// Ele lida com o estado de baixo nível necessário para o blockchain.
-#[derive(Debug)]
+#[derive(Debug, Clone)]
 pub struct Pallet<T: Config> {
     /// O número do bloco atual.
     block_number: T::BlockNumber,
     /// Um mapa de uma conta até seu nonce.
     nonce: BTreeMap<T::AccountId, T::Nonce>,
+    /// A árvore sobre o armazenamento de todos os pallets, usada para calcular a raiz do estado.
+    /// Não faz parte do estado.
+    state_trie: StateTrie<T::Hashing>,
+    /// A raiz do estado depois de cada bloco executado. Não faz parte do estado.
+    state_roots: BTreeMap<T::BlockNumber, T::Hash>,
+    /// O estado depois de cada um dos últimos `STATE_HISTORY` blocos, para gerar provas de
+    /// leitura. Não faz parte do estado.
+    state_snapshots: BTreeMap<T::BlockNumber, StateSnapshot<T::Hashing>>,
 }
 
 // Atualize todas essas funções para usar seu novo traço de configuração.
@@ -25,6 +51,9 @@ impl<T: Config> Pallet<T> {
         Self {
             block_number: T::BlockNumber::zero(),
             nonce: BTreeMap::new(),
+            state_trie: StateTrie::new(),
+            state_roots: BTreeMap::new(),
+            state_snapshots: BTreeMap::new(),
         }
     }
 
@@ -44,6 +73,75 @@ impl<T: Config> Pallet<T> {
         let current_nonce = *self.nonce.get(account).unwrap_or(&T::Nonce::zero()) + T::Nonce::one();
         self.nonce.insert(account.clone(), current_nonce);
     }
+
+    /// Guarda o número do bloco e os nonces, para desfazer com `restore` um bloco inválido.
+    pub fn checkpoint(&self) -> Checkpoint<T> {
+        Checkpoint {
+            block_number: self.block_number,
+            nonce: self.nonce.clone(),
+        }
+    }
+
+    /// Volta o número do bloco e os nonces ao que eram em `checkpoint`.
+    pub fn restore(&mut self, checkpoint: Checkpoint<T>) {
+        self.block_number = checkpoint.block_number;
+        self.nonce = checkpoint.nonce;
+    }
+
+    /// Calcula a raiz do estado sobre o armazenamento de todos os pallets, `state`, e a registra
+    /// como a raiz do estado do bloco atual. O estado é guardado para que provas de leitura sobre
+    /// ele possam ser geradas depois.
+    ///
+    /// Se `expected` for informada e a raiz calculada for diferente, nada é registrado e a árvore
+    /// do estado continua como estava.
+    ///
+    /// Todo o armazenamento é recebido e tem o hash de cada par recalculado a cada bloco; apenas os
+    /// hashes das subárvores sem alterações são reaproveitados.
+    pub fn commit_state(
+        &mut self,
+        state: Vec<(Vec<u8>, Vec<u8>)>,
+        expected: Option<T::Hash>,
+    ) -> Result<T::Hash, &'static str> {
+        let mut state_trie = self.state_trie.clone();
+        state_trie.set_state(state);
+        let root = state_trie.root();
+        if expected.is_some_and(|expected| expected != root) {
+            return Err("state root does not match the header");
+        }
+
+        self.state_trie = state_trie;
+        self.state_roots.insert(self.block_number, root);
+        self.state_snapshots
+            .insert(self.block_number, self.state_trie.snapshot());
+        while self.state_snapshots.len() > STATE_HISTORY {
+            self.state_snapshots.pop_first();
+        }
+        Ok(root)
+    }
+
+    /// Obtém a raiz do estado registrada depois do bloco `block_number`, se ele foi executado.
+    pub fn state_root(&self, block_number: T::BlockNumber) -> Option<T::Hash> {
+        self.state_roots.get(&block_number).copied()
+    }
+
+    /// Gera a prova de leitura da chave de armazenamento `key` no estado depois do bloco
+    /// `block_number`, que pode ser verificada contra a raiz do estado desse bloco.
+    /// Retorna `None` se o estado do bloco não estiver guardado.
+    pub fn read_proof(
+        &self,
+        block_number: T::BlockNumber,
+        key: &[u8],
+    ) -> Option<ReadProof<T::Hash>> {
+        Some(self.state_snapshots.get(&block_number)?.prove(key))
+    }
+}
+
+impl<T: Config> Storage for Pallet<T> {
+    fn storage(&self) -> Vec<(Vec<u8>, Vec<u8>)> {
+        let mut storage = vec![storage_value("block_number", &self.block_number)];
+        storage.extend(storage_map("nonce", &self.nonce));
+        storage
+    }
 }
 
 #[cfg(test)]
@@ -55,9 +153,12 @@ mod test {
         type AccountId = String;
         type BlockNumber = u32;
         type Nonce = u32;
+        type Hash = crate::hashing::H256;
+        type Hashing = crate::hashing::Sha256;
     }
 
     #[test]
+    #[allow(clippy::unnecessary_to_owned)]
     fn init_system() {
         let mut system = super::Pallet::<TestConfig>::new();
 
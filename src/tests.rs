@@ -0,0 +1,54 @@
+use crate::{balances, support, timestamp, types, Runtime, RuntimeCall};
+
+/// Cria um runtime em que cada uma das contas `names` tem o saldo `balance`, e retorna as contas
+/// na mesma ordem.
+pub fn new_runtime<const N: usize>(names: [&str; N], balance: u128) -> (Runtime, [String; N]) {
+    let mut runtime = Runtime::new();
+    let accounts = names.map(String::from);
+    for who in &accounts {
+        runtime.balances.set_balance(who, balance);
+    }
+    (runtime, accounts)
+}
+
+/// Uma transferência de `amount` para `to`, como uma chamada do runtime.
+pub fn transfer(to: &str, amount: u128) -> Box<RuntimeCall> {
+    Box::new(
+        balances::Call::transfer {
+            to: to.to_string(),
+            amount,
+        }
+        .into(),
+    )
+}
+
+/// Um bloco de número `block_number` com os extrínsecos `extrinsics`, sem raiz do estado.
+pub fn block(block_number: u32, extrinsics: Vec<types::Extrinsic>) -> types::Block {
+    types::Block {
+        header: types::Header {
+            block_number,
+            state_root: None,
+        },
+        extrinsics,
+    }
+}
+
+/// O inerente com o horário do bloco `block_number`, que avança o intervalo mínimo a cada bloco.
+pub fn set_timestamp(block_number: u32) -> types::Extrinsic {
+    let period = <Runtime as timestamp::Config>::MINIMUM_PERIOD;
+    support::Extrinsic::Inherent {
+        call: timestamp::Call::set {
+            now: block_number as u64 * period,
+        }
+        .into(),
+    }
+}
+
+/// Executa blocos vazios, apenas com o horário, até o bloco `n`.
+pub fn run_to_block(runtime: &mut Runtime, n: u32) {
+    while runtime.system.block_number() < n {
+        let block_number = runtime.system.block_number() + 1;
+        let res = runtime.execute_block(block(block_number, vec![set_timestamp(block_number)]));
+        assert_eq!(res, Ok(()));
+    }
+}
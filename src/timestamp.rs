@@ -0,0 +1,235 @@
+use crate::support::{
+    storage_value, DispatchResult, GetPallet, Hooks, Origin, ProvideInherent, Storage, Time,
+};
+use crate::system;
+use core::fmt::Debug;
+use num::traits::{CheckedAdd, Zero};
+
+/// A característica de configuração do Módulo de Horário.
+pub trait Config: system::Config + Sized + GetPallet<Pallet<Self>> {
+    /// O tipo usado para representar um instante, por exemplo milissegundos desde a época Unix.
+    type Moment: Zero + CheckedAdd + Copy + Ord + Debug;
+    /// O intervalo mínimo entre os horários de dois blocos consecutivos.
+    const MINIMUM_PERIOD: Self::Moment;
+}
+
+/// Este é o Módulo de Horário.
+/// Guarda o horário do bloco atual, informado pelo autor do bloco através de um inerente, e o
+/// fornece aos outros pallets como fonte de tempo.
+#[derive(Debug, Clone)]
+pub struct Pallet<T: Config> {
+    /// O horário do bloco atual, se algum já foi informado.
+    now: Option<T::Moment>,
+    /// Se o horário já foi informado no bloco atual.
+    did_update: bool,
+}
+
+#[macros::call]
+impl<T: Config> Pallet<T> {
+    /// Define o horário do bloco atual como `now`. Precisa ser incluída pelo autor do bloco, como
+    /// um inerente, exatamente uma vez por bloco. O horário precisa avançar pelo menos
+    /// `MINIMUM_PERIOD` em relação ao bloco anterior.
+    pub fn set(&mut self, origin: Origin<T::AccountId>, now: T::Moment) -> DispatchResult {
+        origin.ensure_none()?;
+        if self.did_update {
+            return Err("Timestamp must be updated only once in the block.");
+        }
+        if let Some(prev) = self.now {
+            let earliest = prev.checked_add(&T::MINIMUM_PERIOD).ok_or("Overflow")?;
+            if now < earliest {
+                return Err("Timestamp must increment by at least MINIMUM_PERIOD between blocks.");
+            }
+        }
+        self.now = Some(now);
+        self.did_update = true;
+        Ok(())
+    }
+}
+
+impl<T: Config> Pallet<T> {
+    /// Cria uma nova instância do Módulo de Horário, sem horário conhecido.
+    pub fn new() -> Self {
+        Self {
+            now: None,
+            did_update: false,
+        }
+    }
+
+    /// Obtém o horário do bloco atual, ou `None` se nenhum bloco o informou ainda.
+    pub fn now(&self) -> Option<T::Moment> {
+        self.now
+    }
+
+    /// Verifica se o horário já foi informado no bloco atual.
+    pub fn did_update(&self) -> bool {
+        self.did_update
+    }
+}
+
+impl<T: Config> Hooks<T> for Pallet<T> {
+    fn on_finalize(runtime: &mut T, _n: T::BlockNumber) {
+        let pallet: &mut Self = runtime.pallet_mut();
+        pallet.did_update = false;
+    }
+}
+
+/// O horário é um inerente exigido em todo bloco.
+impl<T: Config> ProvideInherent<Call<T>> for Pallet<T> {
+    const INHERENT_REQUIRED: bool = true;
+
+    fn is_inherent(call: &Call<T>) -> bool {
+        matches!(call, Call::set { .. })
+    }
+}
+
+/// O Módulo de Horário é a fonte de tempo dos outros pallets do runtime.
+impl<T: Config> Time<T> for Pallet<T> {
+    type Moment = T::Moment;
+
+    fn now(runtime: &T) -> Option<T::Moment> {
+        let pallet: &Self = runtime.pallet();
+        pallet.now
+    }
+}
+
+impl<T: Config> Storage for Pallet<T> {
+    fn storage(&self) -> Vec<(Vec<u8>, Vec<u8>)> {
+        vec![storage_value("now", &self.now)]
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::support::{self, Dispatch, Hasher};
+    use crate::tests::block;
+    use crate::{balances, proof_of_existence, types, Runtime, RuntimeCall};
+
+    fn set(now: u64) -> types::Extrinsic {
+        support::Extrinsic::Inherent {
+            call: Call::set { now }.into(),
+        }
+    }
+
+    #[test]
+    fn set_timestamp() {
+        let alice = "alice".to_string();
+        let mut runtime = Runtime::new();
+        runtime.balances.set_balance(&alice, 100);
+        assert_eq!(runtime.timestamp.now(), None);
+
+        // O horário só pode ser definido por um inerente
+        let res = runtime.dispatch(Origin::Signed(alice.clone()), Call::set { now: 1 }.into());
+        assert_eq!(res, Err("Bad origin: expected no origin."));
+        let res = runtime.dispatch(Origin::Root, Call::set { now: 1 }.into());
+        assert_eq!(res, Err("Bad origin: expected no origin."));
+
+        // As reivindicações registram o horário do bloco em que foram criadas
+        let claim = types::Hashing::hash(b"contrato");
+        let create_claim = support::Extrinsic::Signed {
+            caller: alice.clone(),
+            call: RuntimeCall::proof_of_existence(proof_of_existence::Call::create_claim {
+                claim,
+                description: Vec::new(),
+                expires_at: None,
+                attestation_threshold: None,
+            }),
+        };
+        let res = runtime.execute_block(block(1, vec![set(1_000), create_claim]));
+        assert_eq!(res, Ok(()));
+        assert_eq!(runtime.timestamp.now(), Some(1_000));
+        assert!(!runtime.timestamp.did_update());
+        let stored = runtime.proof_of_existence.get_claim(&claim).unwrap();
+        assert_eq!(stored.timestamp, Some(1_000));
+
+        // O horário precisa avançar pelo menos o intervalo mínimo, senão o bloco é inválido e
+        // desfeito
+        let period = <Runtime as Config>::MINIMUM_PERIOD;
+        let res = runtime.execute_block(block(2, vec![set(1_000 + period - 1)]));
+        assert_eq!(
+            res,
+            Err("Timestamp must increment by at least MINIMUM_PERIOD between blocks.")
+        );
+        assert_eq!(runtime.system.block_number(), 1);
+        assert_eq!(runtime.timestamp.now(), Some(1_000));
+        let res = runtime.execute_block(block(2, vec![set(1_000 + period)]));
+        assert_eq!(res, Ok(()));
+        assert_eq!(runtime.timestamp.now(), Some(1_000 + period));
+
+        // E só pode ser definido uma vez em cada bloco
+        let res = runtime.execute_block(block(3, vec![set(20_000), set(40_000)]));
+        assert_eq!(
+            res,
+            Err("Timestamp must be updated only once in the block.")
+        );
+    }
+
+    #[test]
+    fn valid_block_after_rejected_block() {
+        let mut runtime = Runtime::new();
+        let res = runtime.execute_block(block(1, vec![set(1_000)]));
+        assert_eq!(res, Ok(()));
+
+        // Um bloco que define o horário duas vezes é recusado sem deixar o horário marcado como
+        // informado, então o mesmo runtime aceita o bloco válido seguinte
+        let res = runtime.execute_block(block(2, vec![set(20_000), set(40_000)]));
+        assert_eq!(
+            res,
+            Err("Timestamp must be updated only once in the block.")
+        );
+        assert!(!runtime.timestamp.did_update());
+        assert_eq!(runtime.timestamp.now(), Some(1_000));
+        let res = runtime.execute_block(block(2, vec![set(20_000)]));
+        assert_eq!(res, Ok(()));
+        assert_eq!(runtime.timestamp.now(), Some(20_000));
+        assert!(!runtime.timestamp.did_update());
+    }
+
+    #[test]
+    fn inherents_and_unsigned() {
+        let alice = "alice".to_string();
+        let mut runtime = Runtime::new();
+        runtime.balances.set_balance(&alice, 100);
+        let transfer = || support::Extrinsic::Signed {
+            caller: alice.clone(),
+            call: RuntimeCall::balances(balances::Call::transfer {
+                to: "bob".to_string(),
+                amount: 10,
+            }),
+        };
+
+        // Todo bloco precisa do horário, antes de todos os outros extrínsecos. Os inerentes são
+        // verificados antes de qualquer mudança no estado.
+        let res = runtime.execute_block(block(1, vec![transfer()]));
+        assert_eq!(res, Err("a required inherent is missing"));
+        let res = runtime.execute_block(block(1, vec![transfer(), set(1_000)]));
+        assert_eq!(res, Err("inherents must come before other extrinsics"));
+        assert_eq!(runtime.system.block_number(), 0);
+
+        // Apenas as chamadas aceitas pelo pallet podem ser inerentes
+        let inherent_transfer = support::Extrinsic::Inherent {
+            call: RuntimeCall::balances(balances::Call::force_set_balance {
+                who: alice.clone(),
+                amount: 1_000,
+            }),
+        };
+        let res = runtime.execute_block(block(1, vec![set(1_000), inherent_transfer]));
+        assert_eq!(res, Err("call is not an inherent"));
+
+        // Uma transação não assinada só é aceita se o seu pallet a validar
+        let unsigned = support::Extrinsic::Unsigned {
+            call: Call::set { now: 2_000 }.into(),
+        };
+        let res = runtime.execute_block(block(1, vec![set(1_000), transfer(), unsigned]));
+        assert_eq!(
+            res,
+            Err("Unsigned transactions are not accepted for this call.")
+        );
+        assert_eq!(runtime.balances.balance(&alice), 100);
+
+        // O mesmo runtime aceita depois um bloco válido
+        let res = runtime.execute_block(block(1, vec![set(1_000), transfer()]));
+        assert_eq!(res, Ok(()));
+        assert_eq!(runtime.balances.balance(&alice), 90);
+    }
+}
@@ -0,0 +1,448 @@
+use crate::merkle;
+use crate::support::Hasher;
+use core::fmt;
+use std::collections::BTreeMap;
+
+/// Calcula o hash de uma folha da árvore, que guarda o hash de uma chave e o hash do seu valor.
+/// Usa o mesmo prefixo das folhas do módulo `merkle`, e os nós internos usam
+/// `merkle::hash_node`.
+pub fn hash_leaf<H: Hasher>(key: &H::Output, value: &H::Output) -> H::Output {
+    let mut data = vec![0u8];
+    data.extend_from_slice(key.as_ref());
+    data.extend_from_slice(value.as_ref());
+    H::hash(&data)
+}
+
+/// O hash de uma subárvore sem nenhuma folha.
+pub fn empty_root<H: Hasher>() -> H::Output {
+    H::hash(&[2u8])
+}
+
+/// Obtém o bit de posição `depth` de `key`, começando pelo bit mais significativo.
+fn bit(key: &[u8], depth: usize) -> bool {
+    key[depth / 8] >> (7 - depth % 8) & 1 == 1
+}
+
+/// Os primeiros `depth` bits de `key`, que identificam uma subárvore naquela profundidade.
+fn prefix(key: &[u8], depth: usize) -> Vec<u8> {
+    let mut prefix = key[..depth.div_ceil(8)].to_vec();
+    if !depth.is_multiple_of(8) {
+        if let Some(last) = prefix.last_mut() {
+            *last &= 0xff << (8 - depth % 8);
+        }
+    }
+    prefix
+}
+
+/// A prova de leitura de uma chave: o que é preciso para verificar, a partir apenas da raiz do
+/// estado, o valor de uma chave, ou que ela não existe.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ReadProof<Hash> {
+    /// Os irmãos do caminho da chave, a partir da raiz.
+    pub siblings: Vec<Hash>,
+    /// A folha no fim do caminho, como `(hash da chave, hash do valor)`. Se for de outra chave, ou
+    /// se não houver folha, a prova mostra que a chave não existe.
+    pub leaf: Option<(Hash, Hash)>,
+}
+
+/// Calcula a raiz da subárvore de profundidade `depth` que contém `leaves`, ordenadas, sem usar
+/// nenhum hash guardado.
+fn compute_root<H: Hasher>(depth: usize, leaves: &[(H::Output, H::Output)]) -> H::Output {
+    match leaves {
+        [] => empty_root::<H>(),
+        [(key, value)] => hash_leaf::<H>(key, value),
+        _ => {
+            let split = leaves.partition_point(|(key, _)| !bit(key.as_ref(), depth));
+            let left = compute_root::<H>(depth + 1, &leaves[..split]);
+            let right = compute_root::<H>(depth + 1, &leaves[split..]);
+            merkle::hash_node::<H>(&left, &right)
+        }
+    }
+}
+
+/// Gera a prova de leitura de `key` sobre `leaves`, ordenadas.
+fn prove<H: Hasher>(leaves: &[(H::Output, H::Output)], key: &[u8]) -> ReadProof<H::Output> {
+    let key = H::hash(key);
+    let mut siblings = Vec::new();
+    let mut leaves = leaves;
+    let mut depth = 0;
+    while leaves.len() > 1 {
+        let split = leaves.partition_point(|(k, _)| !bit(k.as_ref(), depth));
+        let (left, right) = leaves.split_at(split);
+        if bit(key.as_ref(), depth) {
+            siblings.push(compute_root::<H>(depth + 1, left));
+            leaves = right;
+        } else {
+            siblings.push(compute_root::<H>(depth + 1, right));
+            leaves = left;
+        }
+        depth += 1;
+    }
+    ReadProof {
+        siblings,
+        leaf: leaves.first().copied(),
+    }
+}
+
+/// Verifica, com `proof`, que `key` tem o valor `value` no estado de raiz `root`, ou que `key` não
+/// existe nesse estado quando `value` é `None`.
+pub fn verify_read_proof<H: Hasher>(
+    root: &H::Output,
+    key: &[u8],
+    value: Option<&[u8]>,
+    proof: &ReadProof<H::Output>,
+) -> bool {
+    let key = H::hash(key);
+    let depth = proof.siblings.len();
+    if depth > key.as_ref().len() * 8 {
+        return false;
+    }
+
+    let leaf = match (&proof.leaf, value) {
+        // A folha precisa ser da própria chave, com o valor esperado.
+        (Some((leaf_key, leaf_value)), Some(value)) => {
+            if leaf_key != &key || leaf_value != &H::hash(value) {
+                return false;
+            }
+            hash_leaf::<H>(leaf_key, leaf_value)
+        }
+        // A folha precisa ser de outra chave que ocupa o lugar onde a chave estaria.
+        (Some((leaf_key, leaf_value)), None) => {
+            let same_path = (0..depth).all(|d| bit(leaf_key.as_ref(), d) == bit(key.as_ref(), d));
+            if leaf_key == &key || !same_path {
+                return false;
+            }
+            hash_leaf::<H>(leaf_key, leaf_value)
+        }
+        (None, Some(_)) => return false,
+        (None, None) => empty_root::<H>(),
+    };
+
+    let computed = proof
+        .siblings
+        .iter()
+        .enumerate()
+        .rev()
+        .fold(leaf, |node, (depth, sibling)| {
+            if bit(key.as_ref(), depth) {
+                merkle::hash_node::<H>(sibling, &node)
+            } else {
+                merkle::hash_node::<H>(&node, sibling)
+            }
+        });
+    &computed == root
+}
+
+/// O conteúdo da árvore do estado num momento, guardado para gerar provas de leitura depois.
+pub struct StateSnapshot<H: Hasher> {
+    leaves: Vec<(H::Output, H::Output)>,
+}
+
+impl<H: Hasher> StateSnapshot<H> {
+    /// Calcula a raiz do estado guardado.
+    #[cfg(test)]
+    pub fn root(&self) -> H::Output {
+        compute_root::<H>(0, &self.leaves)
+    }
+
+    /// Gera a prova de leitura de `key` no estado guardado.
+    pub fn prove(&self, key: &[u8]) -> ReadProof<H::Output> {
+        prove::<H>(&self.leaves, key)
+    }
+}
+
+// Implementado à mão para não exigir `Clone` do algoritmo de hash.
+impl<H: Hasher> Clone for StateSnapshot<H> {
+    fn clone(&self) -> Self {
+        Self {
+            leaves: self.leaves.clone(),
+        }
+    }
+}
+
+impl<H: Hasher> fmt::Debug for StateSnapshot<H> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("StateSnapshot")
+            .field("entries", &self.leaves.len())
+            .finish()
+    }
+}
+
+/// Uma árvore de Merkle esparsa sobre pares chave-valor, usada para calcular a raiz do estado.
+///
+/// Cada par fica numa folha cuja posição é dada pelos bits do hash da chave. Uma subárvore com uma
+/// única folha é representada pela própria folha, então a profundidade da árvore cresce apenas
+/// com o logaritmo do número de pares. A raiz depende apenas do conjunto de pares, não da ordem em
+/// que eles foram inseridos.
+///
+/// Os hashes das subárvores são guardados entre os cálculos da raiz, e uma alteração invalida
+/// apenas as subárvores no caminho da chave alterada.
+pub struct StateTrie<H: Hasher> {
+    /// O hash do valor de cada chave, indexado pelo hash da chave.
+    leaves: BTreeMap<H::Output, H::Output>,
+    /// Os hashes das subárvores com mais de uma folha, indexados pela profundidade e pelo
+    /// prefixo.
+    cache: BTreeMap<(usize, Vec<u8>), H::Output>,
+}
+
+impl<H: Hasher> StateTrie<H> {
+    /// Cria uma árvore vazia.
+    pub fn new() -> Self {
+        Self {
+            leaves: BTreeMap::new(),
+            cache: BTreeMap::new(),
+        }
+    }
+
+    /// Define o valor de `key`, ou a remove da árvore com `None`.
+    #[cfg(test)]
+    pub fn update(&mut self, key: &[u8], value: Option<&[u8]>) {
+        let key = H::hash(key);
+        let value = value.map(H::hash);
+        if self.leaves.get(&key) == value.as_ref() {
+            return;
+        }
+
+        self.invalidate(&key);
+        match value {
+            Some(value) => self.leaves.insert(key, value),
+            None => self.leaves.remove(&key),
+        };
+    }
+
+    /// Substitui todo o conteúdo da árvore por `entries`.
+    /// Só as chaves que mudaram de valor, foram criadas ou foram removidas invalidam subárvores.
+    pub fn set_state(&mut self, entries: impl IntoIterator<Item = (Vec<u8>, Vec<u8>)>) {
+        let new: BTreeMap<H::Output, H::Output> = entries
+            .into_iter()
+            .map(|(key, value)| (H::hash(&key), H::hash(&value)))
+            .collect();
+
+        let removed: Vec<H::Output> = self
+            .leaves
+            .keys()
+            .filter(|key| !new.contains_key(key))
+            .copied()
+            .collect();
+        for key in removed {
+            self.invalidate(&key);
+            self.leaves.remove(&key);
+        }
+        for (key, value) in new {
+            if self.leaves.get(&key) != Some(&value) {
+                self.invalidate(&key);
+                self.leaves.insert(key, value);
+            }
+        }
+    }
+
+    /// O número de pares na árvore.
+    #[cfg(test)]
+    pub fn len(&self) -> usize {
+        self.leaves.len()
+    }
+
+    /// Verifica se a árvore está vazia.
+    #[cfg(test)]
+    pub fn is_empty(&self) -> bool {
+        self.leaves.is_empty()
+    }
+
+    /// Guarda o conteúdo atual da árvore, para gerar provas de leitura sobre ele mais tarde.
+    pub fn snapshot(&self) -> StateSnapshot<H> {
+        StateSnapshot {
+            leaves: self.leaves.iter().map(|(k, v)| (*k, *v)).collect(),
+        }
+    }
+
+    /// Gera a prova de leitura de `key` no estado atual.
+    #[cfg(test)]
+    pub fn prove(&self, key: &[u8]) -> ReadProof<H::Output> {
+        self.snapshot().prove(key)
+    }
+
+    /// Calcula a raiz da árvore, reaproveitando as subárvores que não mudaram.
+    pub fn root(&mut self) -> H::Output {
+        let leaves: Vec<(H::Output, H::Output)> =
+            self.leaves.iter().map(|(k, v)| (*k, *v)).collect();
+        self.subtree_root(0, &leaves)
+    }
+
+    /// Calcula a raiz da subárvore de profundidade `depth` que contém `leaves`, ordenadas.
+    fn subtree_root(&mut self, depth: usize, leaves: &[(H::Output, H::Output)]) -> H::Output {
+        match leaves {
+            [] => empty_root::<H>(),
+            [(key, value)] => hash_leaf::<H>(key, value),
+            [(first, _), ..] => {
+                let id = (depth, prefix(first.as_ref(), depth));
+                if let Some(hash) = self.cache.get(&id) {
+                    return *hash;
+                }
+                let split = leaves.partition_point(|(key, _)| !bit(key.as_ref(), depth));
+                let left = self.subtree_root(depth + 1, &leaves[..split]);
+                let right = self.subtree_root(depth + 1, &leaves[split..]);
+                let hash = merkle::hash_node::<H>(&left, &right);
+                self.cache.insert(id, hash);
+                hash
+            }
+        }
+    }
+
+    /// Descarta os hashes guardados de todas as subárvores no caminho de `key`.
+    fn invalidate(&mut self, key: &H::Output) {
+        let bits = key.as_ref().len() * 8;
+        for depth in 0..=bits {
+            self.cache.remove(&(depth, prefix(key.as_ref(), depth)));
+        }
+    }
+}
+
+impl<H: Hasher> Default for StateTrie<H> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<H: Hasher> Clone for StateTrie<H> {
+    fn clone(&self) -> Self {
+        Self {
+            leaves: self.leaves.clone(),
+            cache: self.cache.clone(),
+        }
+    }
+}
+
+impl<H: Hasher> fmt::Debug for StateTrie<H> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("StateTrie")
+            .field("entries", &self.leaves.len())
+            .finish()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::hashing::Sha256;
+
+    fn entries(count: u8) -> Vec<(Vec<u8>, Vec<u8>)> {
+        (0..count).map(|i| (vec![i], vec![i, i])).collect()
+    }
+
+    /// Calcula a raiz de `entries` numa árvore nova, sem aproveitar nenhum cálculo anterior.
+    fn fresh_root(entries: Vec<(Vec<u8>, Vec<u8>)>) -> [u8; 32] {
+        let mut trie = StateTrie::<Sha256>::new();
+        trie.set_state(entries);
+        trie.root()
+    }
+
+    #[test]
+    fn state_root() {
+        let mut trie = StateTrie::<Sha256>::new();
+        assert_eq!(trie.root(), empty_root::<Sha256>());
+
+        // A raiz não depende da ordem de inserção
+        for (key, value) in entries(20).into_iter().rev() {
+            trie.update(&key, Some(&value));
+        }
+        assert_eq!(trie.len(), 20);
+        let root = trie.root();
+        assert_eq!(root, fresh_root(entries(20)));
+
+        // Uma alteração muda a raiz, e desfazê-la a restaura
+        trie.update(&[3], Some(b"outro"));
+        let changed = trie.root();
+        assert_ne!(changed, root);
+        let mut expected = entries(20);
+        expected[3].1 = b"outro".to_vec();
+        assert_eq!(changed, fresh_root(expected));
+        trie.update(&[3], Some(&[3, 3]));
+        assert_eq!(trie.root(), root);
+
+        // Substituir o estado remove as chaves ausentes
+        trie.set_state(entries(5));
+        assert_eq!(trie.len(), 5);
+        assert_eq!(trie.root(), fresh_root(entries(5)));
+        trie.update(&[0], None);
+        assert_eq!(trie.root(), fresh_root(entries(5)[1..].to_vec()));
+        trie.set_state(Vec::new());
+        assert!(trie.is_empty());
+        assert_eq!(trie.root(), empty_root::<Sha256>());
+    }
+
+    #[test]
+    fn read_proofs() {
+        let mut trie = StateTrie::<Sha256>::new();
+        trie.set_state(entries(20));
+        let root = trie.root();
+        let snapshot = trie.snapshot();
+        assert_eq!(snapshot.root(), root);
+
+        // Provas de chaves que existem
+        for (key, value) in entries(20) {
+            let proof = trie.prove(&key);
+            assert!(verify_read_proof::<Sha256>(
+                &root,
+                &key,
+                Some(&value),
+                &proof
+            ));
+            assert!(!verify_read_proof::<Sha256>(
+                &root,
+                &key,
+                Some(b"outro"),
+                &proof
+            ));
+            assert!(!verify_read_proof::<Sha256>(&root, &key, None, &proof));
+        }
+
+        // Provas de ausência
+        for key in [[20u8], [100], [255]] {
+            let proof = trie.prove(&key);
+            assert!(verify_read_proof::<Sha256>(&root, &key, None, &proof));
+            assert!(!verify_read_proof::<Sha256>(
+                &root,
+                &key,
+                Some(&[0]),
+                &proof
+            ));
+        }
+
+        // A prova de uma chave não serve para outra
+        let proof = trie.prove(&[1]);
+        assert!(!verify_read_proof::<Sha256>(
+            &root,
+            &[2],
+            Some(&[2, 2]),
+            &proof
+        ));
+        assert!(!verify_read_proof::<Sha256>(&root, &[2], None, &proof));
+
+        // O estado guardado continua provável depois de alterações
+        trie.update(&[1], Some(b"outro"));
+        let new_root = trie.root();
+        let proof = snapshot.prove(&[1]);
+        assert!(verify_read_proof::<Sha256>(
+            &root,
+            &[1],
+            Some(&[1, 1]),
+            &proof
+        ));
+        assert!(!verify_read_proof::<Sha256>(
+            &new_root,
+            &[1],
+            Some(&[1, 1]),
+            &proof
+        ));
+
+        // Num estado vazio, qualquer chave está ausente
+        let empty = StateTrie::<Sha256>::new();
+        let proof = empty.prove(&[1]);
+        assert!(verify_read_proof::<Sha256>(
+            &empty_root::<Sha256>(),
+            &[1],
+            None,
+            &proof
+        ));
+    }
+}
@@ -0,0 +1,308 @@
+use crate::support::{Dispatch, DispatchResult, GetPallet, Hooks, Origin, Storage, Transactional};
+use crate::system;
+use core::marker::PhantomData;
+
+/// Os eventos emitidos pelo Módulo Utility.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Event {
+    /// Uma chamada do lote foi executada com sucesso.
+    ItemCompleted,
+    /// Uma chamada do lote falhou com `error`, e o lote continuou.
+    ItemFailed { error: &'static str },
+    /// O lote foi interrompido pela falha da chamada de posição `index` com `error`.
+    BatchInterrupted { index: u32, error: &'static str },
+    /// Todas as chamadas do lote foram executadas com sucesso.
+    BatchCompleted,
+    /// Todas as chamadas do lote foram executadas, mas algumas falharam.
+    BatchCompletedWithErrors,
+}
+
+/// A característica de configuração do Módulo Utility.
+/// O runtime precisa saber despachar suas próprias chamadas, e desfazê-las, para executar lotes.
+pub trait Config:
+    system::Config
+    + Sized
+    + Dispatch<Caller = Origin<Self::AccountId>, Call = Self::RuntimeCall>
+    + Transactional
+    + GetPallet<Pallet<Self>>
+{
+    /// O tipo das chamadas do runtime, que podem ser de qualquer pallet, inclusive deste.
+    type RuntimeCall;
+    /// O número máximo de chamadas num lote.
+    const MAX_BATCH_SIZE: u32;
+    /// O número máximo de lotes executados um dentro do outro.
+    const MAX_BATCH_DEPTH: u32;
+}
+
+/// Este é o Módulo Utility.
+/// Permite enviar várias chamadas, de quaisquer pallets, num único extrínseco.
+#[derive(Debug, Clone)]
+pub struct Pallet<T: Config> {
+    /// Os eventos emitidos por este pallet no bloco atual, em ordem.
+    events: Vec<Event>,
+    /// Quantos lotes estão sendo executados um dentro do outro. Não faz parte do estado.
+    depth: u32,
+    _config: PhantomData<T>,
+}
+
+#[macros::call]
+impl<T: Config> Pallet<T> {
+    /// Executa `calls` em ordem, com a mesma `origin` do lote, parando na primeira chamada que falhar.
+    /// As chamadas anteriores à falha não são desfeitas, e o lote em si não falha: a falha é
+    /// registrada no evento `BatchInterrupted`.
+    pub fn batch(
+        runtime: &mut T,
+        origin: Origin<T::AccountId>,
+        calls: Vec<T::RuntimeCall>,
+    ) -> DispatchResult {
+        Self::ensure_batch_size(&calls)?;
+        Self::nested(runtime, |runtime| {
+            for (index, call) in calls.into_iter().enumerate() {
+                if let Err(error) = runtime.dispatch(origin.clone(), call) {
+                    let index = index as u32;
+                    Self::deposit_event(runtime, Event::BatchInterrupted { index, error });
+                    return Ok(());
+                }
+                Self::deposit_event(runtime, Event::ItemCompleted);
+            }
+            Self::deposit_event(runtime, Event::BatchCompleted);
+            Ok(())
+        })
+    }
+
+    /// Executa `calls` em ordem, com a mesma `origin` do lote, de forma atômica: se alguma chamada falhar,
+    /// todas são desfeitas e o lote falha com o mesmo erro.
+    /// Para poder desfazê-las, o lote guarda antes uma cópia de todos os pallets do runtime,
+    /// através de `Transactional`, então o seu custo cresce com todo o estado, e não apenas com o
+    /// que as chamadas alteram.
+    pub fn batch_all(
+        runtime: &mut T,
+        origin: Origin<T::AccountId>,
+        calls: Vec<T::RuntimeCall>,
+    ) -> DispatchResult {
+        Self::ensure_batch_size(&calls)?;
+        Self::nested(runtime, |runtime| {
+            runtime.transactional(|runtime| {
+                for call in calls {
+                    runtime.dispatch(origin.clone(), call)?;
+                    Self::deposit_event(runtime, Event::ItemCompleted);
+                }
+                Self::deposit_event(runtime, Event::BatchCompleted);
+                Ok(())
+            })
+        })
+    }
+
+    /// Executa todas as `calls` em ordem, com a mesma `origin` do lote, mesmo que algumas falhem.
+    /// Cada falha é registrada no evento `ItemFailed`.
+    pub fn force_batch(
+        runtime: &mut T,
+        origin: Origin<T::AccountId>,
+        calls: Vec<T::RuntimeCall>,
+    ) -> DispatchResult {
+        Self::ensure_batch_size(&calls)?;
+        Self::nested(runtime, |runtime| {
+            let mut failed = false;
+            for call in calls {
+                match runtime.dispatch(origin.clone(), call) {
+                    Ok(()) => Self::deposit_event(runtime, Event::ItemCompleted),
+                    Err(error) => {
+                        failed = true;
+                        Self::deposit_event(runtime, Event::ItemFailed { error });
+                    }
+                }
+            }
+            let event = if failed {
+                Event::BatchCompletedWithErrors
+            } else {
+                Event::BatchCompleted
+            };
+            Self::deposit_event(runtime, event);
+            Ok(())
+        })
+    }
+}
+
+impl<T: Config> Pallet<T> {
+    /// Cria uma nova instância do Módulo Utility.
+    pub fn new() -> Self {
+        Self {
+            events: Vec::new(),
+            depth: 0,
+            _config: PhantomData,
+        }
+    }
+
+    /// Obtém os eventos emitidos por este pallet no bloco atual, em ordem.
+    pub fn events(&self) -> &[Event] {
+        &self.events
+    }
+
+    /// Registra um evento emitido por este pallet.
+    fn deposit_event(runtime: &mut T, event: Event) {
+        let pallet: &mut Self = runtime.pallet_mut();
+        pallet.events.push(event);
+    }
+
+    /// Executa o lote `f` dentro dos lotes que já estão sendo executados, falhando se isso passar
+    /// de `MAX_BATCH_DEPTH` lotes, para que lotes aninhados não estourem a pilha.
+    fn nested(runtime: &mut T, f: impl FnOnce(&mut T) -> DispatchResult) -> DispatchResult {
+        let pallet: &mut Self = runtime.pallet_mut();
+        if pallet.depth >= T::MAX_BATCH_DEPTH {
+            return Err("Batches are nested too deeply.");
+        }
+        pallet.depth += 1;
+        let res = f(runtime);
+        let pallet: &mut Self = runtime.pallet_mut();
+        pallet.depth -= 1;
+        res
+    }
+
+    /// Garante que o lote não tenha mais chamadas do que `MAX_BATCH_SIZE`.
+    fn ensure_batch_size(calls: &[T::RuntimeCall]) -> DispatchResult {
+        if calls.len() > T::MAX_BATCH_SIZE as usize {
+            return Err("Too many calls in the batch.");
+        }
+        Ok(())
+    }
+}
+
+impl<T: Config> Hooks<T> for Pallet<T> {
+    fn on_initialize(runtime: &mut T, _n: T::BlockNumber) {
+        let pallet: &mut Self = runtime.pallet_mut();
+        pallet.events.clear();
+    }
+}
+
+// Este pallet não tem estado além dos eventos, que não fazem parte do estado.
+impl<T: Config> Storage for Pallet<T> {
+    fn storage(&self) -> Vec<(Vec<u8>, Vec<u8>)> {
+        Vec::new()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::hashing::Sha256;
+    use crate::support::Hasher;
+    use crate::tests::transfer;
+    use crate::{proof_of_existence, Runtime, RuntimeCall};
+
+    fn claim(content: &[u8]) -> RuntimeCall {
+        proof_of_existence::Call::create_claim {
+            claim: Sha256::hash(content),
+            description: Vec::new(),
+            expires_at: None,
+            attestation_threshold: None,
+        }
+        .into()
+    }
+
+    fn balance(runtime: &Runtime, who: &str) -> u128 {
+        runtime.balances.balance(&who.to_string())
+    }
+
+    #[test]
+    fn batches() {
+        let alice = Origin::Signed("alice".to_string());
+        let mut runtime = Runtime::new();
+        runtime.balances.set_balance(&"alice".to_string(), 100);
+        type Utility = Pallet<Runtime>;
+
+        // `batch` para na primeira falha, sem desfazer as chamadas anteriores
+        let calls = vec![
+            *transfer("bob", 10),
+            *transfer("bob", 1_000),
+            *transfer("bob", 10),
+        ];
+        assert_eq!(Utility::batch(&mut runtime, alice.clone(), calls), Ok(()));
+        assert_eq!(balance(&runtime, "bob"), 10);
+        assert_eq!(
+            runtime.utility.events(),
+            [
+                Event::ItemCompleted,
+                Event::BatchInterrupted {
+                    index: 1,
+                    error: "Insufficient balance"
+                }
+            ]
+        );
+
+        // `batch_all` desfaz todas as chamadas, inclusive de outros pallets, se uma falhar
+        let calls = vec![claim(b"a"), *transfer("bob", 10), *transfer("bob", 1_000)];
+        let res = Utility::batch_all(&mut runtime, alice.clone(), calls);
+        assert_eq!(res, Err("Insufficient balance"));
+        assert_eq!(balance(&runtime, "bob"), 10);
+        assert_eq!(balance(&runtime, "alice"), 90);
+        let hash = Sha256::hash(b"a");
+        assert!(runtime.proof_of_existence.get_claim(&hash).is_none());
+        assert_eq!(runtime.utility.events().len(), 2);
+
+        let calls = vec![claim(b"a"), *transfer("bob", 10)];
+        assert_eq!(
+            Utility::batch_all(&mut runtime, alice.clone(), calls),
+            Ok(())
+        );
+        assert_eq!(balance(&runtime, "bob"), 20);
+        assert!(runtime.proof_of_existence.get_claim(&hash).is_some());
+        assert_eq!(
+            runtime.utility.events()[2..],
+            [
+                Event::ItemCompleted,
+                Event::ItemCompleted,
+                Event::BatchCompleted
+            ]
+        );
+
+        // `force_batch` executa todas as chamadas, registrando as falhas
+        let calls = vec![*transfer("bob", 1_000), *transfer("bob", 10)];
+        assert_eq!(
+            Utility::force_batch(&mut runtime, alice.clone(), calls),
+            Ok(())
+        );
+        assert_eq!(balance(&runtime, "bob"), 30);
+        assert_eq!(
+            runtime.utility.events()[5..],
+            [
+                Event::ItemFailed {
+                    error: "Insufficient balance"
+                },
+                Event::ItemCompleted,
+                Event::BatchCompletedWithErrors
+            ]
+        );
+
+        // Lotes podem conter outros lotes, despachados pelo runtime
+        let inner = Call::<Runtime>::batch_all {
+            calls: vec![*transfer("bob", 10)],
+        };
+        let call = RuntimeCall::from(Call::<Runtime>::batch {
+            calls: vec![inner.into()],
+        });
+        assert_eq!(runtime.dispatch(alice.clone(), call), Ok(()));
+        assert_eq!(balance(&runtime, "bob"), 40);
+
+        // Mas não além de `MAX_BATCH_DEPTH` lotes, um dentro do outro
+        let nest = |depth| {
+            (0..depth).fold(*transfer("bob", 10), |call, _| {
+                Call::<Runtime>::batch_all { calls: vec![call] }.into()
+            })
+        };
+        let max_depth = <Runtime as Config>::MAX_BATCH_DEPTH;
+        let res = runtime.dispatch(alice.clone(), nest(max_depth + 1));
+        assert_eq!(res, Err("Batches are nested too deeply."));
+        assert_eq!(balance(&runtime, "bob"), 40);
+        assert_eq!(runtime.dispatch(alice.clone(), nest(max_depth)), Ok(()));
+        assert_eq!(balance(&runtime, "bob"), 50);
+        assert_eq!(runtime.utility.depth, 0);
+
+        // Lotes grandes demais são rejeitados
+        let calls = (0..=<Runtime as Config>::MAX_BATCH_SIZE)
+            .map(|_| *transfer("bob", 1))
+            .collect();
+        let res = Utility::force_batch(&mut runtime, alice, calls);
+        assert_eq!(res, Err("Too many calls in the batch."));
+        assert_eq!(balance(&runtime, "bob"), 50);
+    }
+}